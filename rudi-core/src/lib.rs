@@ -1,3 +1,9 @@
+use std::{
+    any::{self, TypeId},
+    cmp::Ordering,
+    hash::{Hash, Hasher},
+};
+
 /// Represents the scope of the provider.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub enum Scope {
@@ -17,6 +23,67 @@ pub enum Scope {
     /// 1. the constructor run only once.
     /// 2. instances taken from the context are reference instances.
     SingleOwner,
+    /// custom scope, the value identifies the type of the custom scope.
+    ///
+    /// 1. whether the constructor runs is decided by the custom scope registered in the context.
+    /// 2. the type implements [`Clone`] trait.
+    /// 3. instances taken from the context are instances with ownership.
+    Custom(CustomScopeId),
+}
+
+/// Represents the type of a custom scope.
+///
+/// Two ids are equal if they are created from the same type, the name of the type is only for display.
+#[derive(Clone, Copy, Debug)]
+pub struct CustomScopeId {
+    name: &'static str,
+    id: TypeId,
+}
+
+impl CustomScopeId {
+    /// Returns the id of the given custom scope type.
+    pub fn of<S: 'static>() -> Self {
+        Self {
+            name: any::type_name::<S>(),
+            id: TypeId::of::<S>(),
+        }
+    }
+
+    /// Returns the name of the custom scope type.
+    pub fn name(&self) -> &'static str {
+        self.name
+    }
+
+    /// Returns the unique identifier of the custom scope type.
+    pub fn id(&self) -> TypeId {
+        self.id
+    }
+}
+
+impl PartialEq for CustomScopeId {
+    fn eq(&self, other: &Self) -> bool {
+        self.id == other.id
+    }
+}
+
+impl Eq for CustomScopeId {}
+
+impl PartialOrd for CustomScopeId {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for CustomScopeId {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.id.cmp(&other.id)
+    }
+}
+
+impl Hash for CustomScopeId {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
 }
 
 /// Represents the color of the function, i.e., async or sync.
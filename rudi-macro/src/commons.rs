@@ -1,36 +1,164 @@
-use from_attr::{AttrsValue, FlagOrValue, FromAttr};
-use proc_macro2::TokenStream;
+use from_attr::{AttrsValue, FlagOrValue, FromAttr, PathValue};
+use proc_macro2::{Span, TokenStream};
 use quote::{format_ident, quote};
 use rudi_core::{Color, Scope};
 use syn::{
     parse_quote, punctuated::Punctuated, spanned::Spanned, AngleBracketedGenericArguments,
-    Attribute, Field, Fields, FieldsNamed, FieldsUnnamed, FnArg, GenericArgument, Ident, PatType,
-    Path, PathArguments, PathSegment, Stmt, Token, Type, TypePath, TypeReference,
+    Attribute, Expr, Field, Fields, FieldsNamed, FieldsUnnamed, FnArg, GenericArgument, Ident,
+    PatType, Path, PathArguments, PathSegment, Stmt, Token, Type, TypePath, TypeReference,
 };
 
-use crate::field_or_argument_attr::FieldOrArgumentAttr;
+use crate::{
+    field_or_argument_attr::FieldOrArgumentAttr,
+    struct_or_function_attr::{ClosureOrPath, Profiles},
+};
 
-pub(crate) fn generate_create_provider(scope: Scope, color: Color) -> TokenStream {
-    match (scope, color) {
+pub(crate) fn generate_create_provider(scope: Scope, color: Color) -> syn::Result<TokenStream> {
+    let create_provider = match (scope, color) {
         (Scope::Singleton, Color::Async) => quote! {
-            singleton_async
+            try_singleton_async
         },
         (Scope::Singleton, Color::Sync) => quote! {
-            singleton
+            try_singleton
         },
         (Scope::Transient, Color::Async) => quote! {
-            transient_async
+            try_transient_async
         },
         (Scope::Transient, Color::Sync) => quote! {
-            transient
+            try_transient
         },
         (Scope::SingleOwner, Color::Async) => quote! {
-            single_owner_async
+            try_single_owner_async
         },
         (Scope::SingleOwner, Color::Sync) => quote! {
-            single_owner
+            try_single_owner
         },
+        (Scope::Custom(_), _) => {
+            return Err(syn::Error::new(
+                Span::call_site(),
+                "a custom scope is set by `#[Singleton(scope = ...)]`",
+            ))
+        }
+    };
+
+    Ok(create_provider)
+}
+
+pub(crate) fn generate_custom_scope(
+    custom_scope: Option<PathValue<Type>>,
+    scope: Scope,
+) -> syn::Result<TokenStream> {
+    let Some(PathValue { path, value: ty }) = custom_scope else {
+        return Ok(quote! {});
+    };
+
+    if scope != Scope::Singleton {
+        return Err(syn::Error::new(
+            path,
+            "`scope` only support in `#[Singleton]`",
+        ));
     }
+
+    Ok(quote! {
+        .scope::<#ty>()
+    })
+}
+
+pub(crate) fn generate_qualifier(qualifier: Option<Type>) -> TokenStream {
+    match qualifier {
+        Some(ty) => quote! {
+            .qualifier::<#ty>()
+        },
+        None => quote! {},
+    }
+}
+
+pub(crate) fn generate_profiles(profile: Option<Profiles>) -> TokenStream {
+    match profile {
+        Some(Profiles(profiles)) => quote! {
+            .profiles([#(#profiles),*])
+        },
+        None => quote! {},
+    }
+}
+
+pub(crate) fn generate_condition(
+    condition: Option<ClosureOrPath>,
+    rudi_path: &Path,
+) -> TokenStream {
+    let Some(ClosureOrPath(condition)) = condition else {
+        return quote! {};
+    };
+
+    match condition {
+        Expr::Closure(_) => quote! {
+            .condition_with(#condition)
+        },
+        // called through a closure, so a `fn(&Context) -> bool` is accepted by deref coercion
+        _ => quote! {
+            .condition_with(|cx: &#rudi_path::ConditionContext| (#condition)(cx))
+        },
+    }
+}
+
+pub(crate) fn generate_presence(on_missing: Option<Type>, on_present: Option<Type>) -> TokenStream {
+    let on_missing = on_missing.map(|ty| quote!(.on_missing::<#ty>()));
+    let on_present = on_present.map(|ty| quote!(.on_present::<#ty>()));
+
+    quote! {
+        #on_missing
+        #on_present
+    }
+}
+
+pub(crate) fn generate_post_construct(
+    init: Option<ClosureOrPath>,
+    color: Color,
+    rudi_path: &Path,
+) -> TokenStream {
+    let Some(ClosureOrPath(init)) = init else {
+        return quote! {};
+    };
+
+    match color {
+        Color::Async => quote! {
+            .post_construct_async(|instance, cx| #rudi_path::FutureExt::boxed((#init)(instance, cx)))
+        },
+        Color::Sync => quote! {
+            .post_construct(#init)
+        },
+    }
+}
+
+pub(crate) fn generate_dependencies(dependencies: Vec<TokenStream>) -> TokenStream {
+    // declared even if empty, so the context knows the constructor resolves nothing else
+    quote! {
+        .dependencies(::std::vec![#(#dependencies),*])
+    }
+}
+
+pub(crate) fn generate_dispose(
+    dispose: Option<PathValue<Expr>>,
+    scope: Scope,
+) -> syn::Result<TokenStream> {
+    let Some(PathValue {
+        path,
+        value: dispose,
+    }) = dispose
+    else {
+        return Ok(quote! {});
+    };
+
+    if scope == Scope::Transient {
+        return Err(syn::Error::new(
+            path,
+            "`dispose` only support in `#[Singleton]` and `#[SingleOwner]`",
+        ));
+    }
+
+    Ok(quote! {
+        .on_dispose(#dispose)
+    })
 }
 
 fn extract_ref_type(ty: &Type) -> syn::Result<&Type> {
@@ -158,6 +286,36 @@ fn extract_path_type<'a>(ty: &'a Type, ty_name: &str) -> syn::Result<&'a Type> {
     }
 }
 
+pub(crate) fn extract_fallible_type(ty: &Type) -> syn::Result<&Type> {
+    let err = || {
+        syn::Error::new(
+            ty.span(),
+            "`fallible` requires the return type to be `Result<T, E>`",
+        )
+    };
+
+    let Type::Path(TypePath { qself: None, path }) = ty else {
+        return Err(err());
+    };
+
+    let Some(PathSegment {
+        ident,
+        arguments: PathArguments::AngleBracketed(AngleBracketedGenericArguments { args, .. }),
+    }) = path.segments.last()
+    else {
+        return Err(err());
+    };
+
+    if ident != "Result" {
+        return Err(err());
+    }
+
+    match args.first() {
+        Some(GenericArgument::Type(ty)) => Ok(ty),
+        _ => Err(err()),
+    }
+}
+
 fn extract_option_type(ty: &Type) -> syn::Result<&Type> {
     extract_path_type(ty, "Option")
 }
@@ -166,19 +324,135 @@ fn extract_vec_type(ty: &Type) -> syn::Result<&Type> {
     extract_path_type(ty, "Vec")
 }
 
+fn extract_map_type(ty: &Type) -> syn::Result<&Type> {
+    let err = || {
+        syn::Error::new(
+            ty.span(),
+            "only support `HashMap<Cow<'static, str>, T>` type",
+        )
+    };
+
+    let Type::Path(TypePath { qself: None, path }) = ty else {
+        return Err(err());
+    };
+
+    let Some(PathSegment {
+        ident,
+        arguments: PathArguments::AngleBracketed(AngleBracketedGenericArguments { args, .. }),
+    }) = path.segments.last()
+    else {
+        return Err(err());
+    };
+
+    if ident != "HashMap" {
+        return Err(err());
+    }
+
+    match args.iter().nth(1) {
+        Some(GenericArgument::Type(ty)) => extract_ref_type(ty),
+        _ => Err(err()),
+    }
+}
+
+fn is_async_factory_type(ty: &Type) -> syn::Result<bool> {
+    if let Type::Path(TypePath { qself: None, path }) = ty {
+        match path.segments.last() {
+            Some(segment) if segment.ident == "Factory" => return Ok(false),
+            Some(segment) if segment.ident == "AsyncFactory" => return Ok(true),
+            _ => {}
+        }
+    }
+
+    Err(syn::Error::new(
+        ty.span(),
+        "only support `Factory<T>` or `AsyncFactory<T>` type",
+    ))
+}
+
 enum ResolveOneValue {
     Owned {
         resolve: Stmt,
     },
     Ref {
         create_single: Stmt,
-        get_single: Stmt,
+        get_single: Box<Stmt>,
     },
 }
 
 struct ResolveOne {
     stmt: ResolveOneValue,
     variable: Ident,
+    dependency: Option<TokenStream>,
+}
+
+fn generate_dependency(
+    attr: &FieldOrArgumentAttr,
+    field_or_argument_ty: &Type,
+    rudi_path: &Path,
+) -> syn::Result<TokenStream> {
+    let FieldOrArgumentAttr {
+        name,
+        qualifier,
+        option,
+        default,
+        vec,
+        map,
+        lazy,
+        factory,
+        ref_,
+        config: _,
+    } = attr;
+
+    let wrapped = || quote!(<#field_or_argument_ty as #rudi_path::Wrapper>::Inner);
+
+    let (kind, ty) = if *lazy {
+        (quote!(Lazy), wrapped())
+    } else if *factory {
+        let kind = if is_async_factory_type(field_or_argument_ty)? {
+            quote!(AsyncFactory)
+        } else {
+            quote!(Factory)
+        };
+
+        (kind, wrapped())
+    } else {
+        let (kind, extract): (_, fn(&Type) -> syn::Result<&Type>) = if *option {
+            (quote!(Option), extract_option_type)
+        } else if *vec {
+            (quote!(Vec), extract_vec_type)
+        } else if *map {
+            (quote!(Map), extract_map_type)
+        } else if !matches!(default, FlagOrValue::None) {
+            (quote!(Default), extract_ref_type)
+        } else {
+            (quote!(Required), extract_ref_type)
+        };
+
+        let ty = match ref_ {
+            FlagOrValue::None if *option || *vec || *map => wrapped(),
+            FlagOrValue::None => quote!(#field_or_argument_ty),
+            FlagOrValue::Flag { .. } => {
+                let ty = extract(field_or_argument_ty)?;
+                quote!(#ty)
+            }
+            FlagOrValue::Value { value: ty, .. } => quote!(#ty),
+        };
+
+        (kind, ty)
+    };
+
+    let by_ref = match ref_ {
+        FlagOrValue::None => quote! {},
+        FlagOrValue::Flag { .. } | FlagOrValue::Value { .. } => quote!(.by_ref()),
+    };
+
+    let qualifier = qualifier
+        .as_ref()
+        .map(|qualifier| quote!(.qualifier::<#qualifier>()));
+
+    Ok(quote! {
+        #rudi_path::Dependency::new::<#ty>(#name, #rudi_path::DependencyKind::#kind)#qualifier #by_ref
+    })
 }
 
 fn generate_only_one_field_or_argument_resolve_stmt(
@@ -186,24 +460,78 @@ fn generate_only_one_field_or_argument_resolve_stmt(
     color: Color,
     index: usize,
     field_or_argument_ty: &Type,
+    rudi_path: &Path,
 ) -> syn::Result<ResolveOne> {
+    let attr = match FieldOrArgumentAttr::remove_attributes(attrs) {
+        Ok(Some(AttrsValue { value, .. })) => value,
+        Ok(None) => FieldOrArgumentAttr::default(),
+        Err(AttrsValue { value, .. }) => return Err(value),
+    };
+
+    if let Some(key) = attr.config {
+        check_config_feature(key.span(), "#[di(config)]")?;
+
+        return Ok(generate_config_resolve_stmt(
+            key,
+            attr.option,
+            attr.default,
+            index,
+        ));
+    }
+
+    let dependency = Some(generate_dependency(&attr, field_or_argument_ty, rudi_path)?);
+
     let FieldOrArgumentAttr {
         name,
+        qualifier,
         option,
         default,
         vec,
+        map,
+        lazy,
+        factory,
         ref_,
-    } = match FieldOrArgumentAttr::remove_attributes(attrs) {
-        Ok(Some(AttrsValue { value, .. })) => value,
-        Ok(None) => FieldOrArgumentAttr::default(),
-        Err(AttrsValue { value, .. }) => return Err(value),
-    };
+        config: _,
+    } = attr;
 
     let ident = match ref_ {
         FlagOrValue::None => format_ident!("owned_{}", index),
         FlagOrValue::Flag { .. } | FlagOrValue::Value { .. } => format_ident!("ref_{}", index),
     };
 
+    if lazy {
+        let ty = quote!(<#field_or_argument_ty as #rudi_path::Wrapper>::Inner);
+        let lazy = name_or_qualified("lazy_{}", &ty, &name, &qualifier);
+
+        let resolve = parse_quote! {
+            let #ident = cx.#lazy;
+        };
+
+        return Ok(ResolveOne {
+            stmt: ResolveOneValue::Owned { resolve },
+            variable: ident,
+            dependency,
+        });
+    }
+
+    if factory {
+        let resolve = if is_async_factory_type(field_or_argument_ty)? {
+            parse_quote! {
+                let #ident = cx.try_async_factory_with_name(#name)?;
+            }
+        } else {
+            parse_quote! {
+                let #ident = cx.try_factory_with_name(#name)?;
+            }
+        };
+
+        return Ok(ResolveOne {
+            stmt: ResolveOneValue::Owned { resolve },
+            variable: ident,
+            dependency,
+        });
+    }
+
     if option {
         let ty = match ref_ {
             FlagOrValue::None => None,
@@ -216,40 +544,42 @@ fn generate_only_one_field_or_argument_resolve_stmt(
 
         return match ty {
             Some(ty) => {
-                let create_single = match color {
-                    Color::Async => parse_quote! {
-                        cx.try_just_create_single_with_name_async::<#ty>(#name).await;
-                    },
-                    Color::Sync => parse_quote! {
-                        cx.try_just_create_single_with_name::<#ty>(#name);
-                    },
-                };
-
+                let create_single = generate_create_single(
+                    "try_just_create_single_{}_checked",
+                    color,
+                    &ty,
+                    &name,
+                    &qualifier,
+                );
+
+                let get_single = name_or_qualified("get_single_option_{}", &ty, &name, &qualifier);
                 let get_single = parse_quote! {
-                    let #ident = cx.get_single_option_with_name(#name);
+                    let #ident = cx.#get_single;
                 };
 
                 Ok(ResolveOne {
                     stmt: ResolveOneValue::Ref {
                         create_single,
-                        get_single,
+                        get_single: Box::new(get_single),
                     },
                     variable: ident,
+                    dependency,
                 })
             }
             None => {
-                let resolve = match color {
-                    Color::Async => parse_quote! {
-                        let #ident = cx.resolve_option_with_name_async(#name).await;
-                    },
-                    Color::Sync => parse_quote! {
-                        let #ident = cx.resolve_option_with_name(#name);
-                    },
-                };
+                let resolve = generate_resolve(
+                    "try_resolve_option_{}",
+                    color,
+                    &name,
+                    &qualifier,
+                    |resolve| quote!(#resolve),
+                    &ident,
+                );
 
                 Ok(ResolveOne {
                     stmt: ResolveOneValue::Owned { resolve },
                     variable: ident,
+                    dependency,
                 })
             }
         };
@@ -271,61 +601,117 @@ fn generate_only_one_field_or_argument_resolve_stmt(
             FlagOrValue::Value { value: ty, .. } => Some(quote!(#ty)),
         };
 
+        return match ty {
+            Some(ty) => {
+                let create_single = generate_create_single(
+                    "try_just_create_single_{}_checked",
+                    color,
+                    &ty,
+                    &name,
+                    &qualifier,
+                );
+
+                let get_single = name_or_qualified("get_single_option_{}", &ty, &name, &qualifier);
+                let get_single = parse_quote! {
+                    let #ident = match cx.#get_single {
+                        Some(value) => value,
+                        None => #default,
+                    };
+                };
+
+                Ok(ResolveOne {
+                    stmt: ResolveOneValue::Ref {
+                        create_single,
+                        get_single: Box::new(get_single),
+                    },
+                    variable: ident,
+                    dependency,
+                })
+            }
+            None => {
+                let resolve = generate_resolve(
+                    "try_resolve_option_{}",
+                    color,
+                    &name,
+                    &qualifier,
+                    |resolve| {
+                        quote! {
+                            match #resolve {
+                                Some(value) => value,
+                                None => #default,
+                            }
+                        }
+                    },
+                    &ident,
+                );
+
+                Ok(ResolveOne {
+                    stmt: ResolveOneValue::Owned { resolve },
+                    variable: ident,
+                    dependency,
+                })
+            }
+        };
+    }
+
+    if vec {
+        let ty = match ref_ {
+            FlagOrValue::None => None,
+            FlagOrValue::Flag { .. } => {
+                let ty = extract_vec_type(field_or_argument_ty)?;
+                Some(quote!(#ty))
+            }
+            FlagOrValue::Value { value: ty, .. } => Some(quote!(#ty)),
+        };
+
         return match ty {
             Some(ty) => {
                 let create_single = match color {
                     Color::Async => parse_quote! {
-                        cx.try_just_create_single_with_name_async::<#ty>(#name).await;
+                        cx.try_just_create_singles_by_type_checked_async::<#ty>().await?;
                     },
                     Color::Sync => parse_quote! {
-                        cx.try_just_create_single_with_name::<#ty>(#name);
+                        cx.try_just_create_singles_by_type_checked::<#ty>()?;
                     },
                 };
 
                 let get_single = parse_quote! {
-                    let #ident = match cx.get_single_option_with_name(#name) {
-                        Some(value) => value,
-                        None => #default,
-                    };
+                    let #ident = cx.get_singles_by_type();
                 };
 
                 Ok(ResolveOne {
                     stmt: ResolveOneValue::Ref {
                         create_single,
-                        get_single,
+                        get_single: Box::new(get_single),
                     },
                     variable: ident,
+                    dependency,
                 })
             }
             None => {
                 let resolve = match color {
                     Color::Async => parse_quote! {
-                        let #ident = match cx.resolve_option_with_name_async(#name).await {
-                            Some(value) => value,
-                            None => #default,
-                        };
+                        let #ident = cx.try_resolve_by_type_async().await?;
                     },
                     Color::Sync => parse_quote! {
-                        let #ident = match cx.resolve_option_with_name(#name) {
-                            Some(value) => value,
-                            None => #default,
-                        };
+                        let #ident = cx.try_resolve_by_type()?;
                     },
                 };
 
                 Ok(ResolveOne {
                     stmt: ResolveOneValue::Owned { resolve },
                     variable: ident,
+                    dependency,
                 })
             }
         };
     }
 
-    if vec {
+    if map {
         let ty = match ref_ {
             FlagOrValue::None => None,
             FlagOrValue::Flag { .. } => {
-                let ty = extract_vec_type(field_or_argument_ty)?;
+                let ty = extract_map_type(field_or_argument_ty)?;
                 Some(quote!(#ty))
             }
             FlagOrValue::Value { value: ty, .. } => Some(quote!(#ty)),
@@ -335,38 +721,40 @@ fn generate_only_one_field_or_argument_resolve_stmt(
             Some(ty) => {
                 let create_single = match color {
                     Color::Async => parse_quote! {
-                        cx.try_just_create_singles_by_type_async::<#ty>().await;
+                        cx.try_just_create_singles_by_type_checked_async::<#ty>().await?;
                     },
                     Color::Sync => parse_quote! {
-                        cx.try_just_create_singles_by_type::<#ty>();
+                        cx.try_just_create_singles_by_type_checked::<#ty>()?;
                     },
                 };
 
                 let get_single = parse_quote! {
-                    let #ident = cx.get_singles_by_type();
+                    let #ident = cx.get_singles_map_by_type();
                 };
 
                 Ok(ResolveOne {
                     stmt: ResolveOneValue::Ref {
                         create_single,
-                        get_single,
+                        get_single: Box::new(get_single),
                     },
                     variable: ident,
+                    dependency,
                 })
             }
             None => {
                 let resolve = match color {
                     Color::Async => parse_quote! {
-                        let #ident = cx.resolve_by_type_async().await;
+                        let #ident = cx.try_resolve_map_by_type_async().await?;
                     },
                     Color::Sync => parse_quote! {
-                        let #ident = cx.resolve_by_type();
+                        let #ident = cx.try_resolve_map_by_type()?;
                     },
                 };
 
                 Ok(ResolveOne {
                     stmt: ResolveOneValue::Owned { resolve },
                     variable: ident,
+                    dependency,
                 })
             }
         };
@@ -383,60 +771,194 @@ fn generate_only_one_field_or_argument_resolve_stmt(
 
     match ty {
         Some(ty) => {
-            let create_single = match color {
-                Color::Async => parse_quote! {
-                    cx.just_create_single_with_name_async::<#ty>(#name).await;
-                },
-                Color::Sync => parse_quote! {
-                    cx.just_create_single_with_name::<#ty>(#name);
-                },
-            };
-
+            let create_single = generate_create_single(
+                "just_create_single_{}_checked",
+                color,
+                &ty,
+                &name,
+                &qualifier,
+            );
+
+            let get_single = name_or_qualified("get_single_{}", &ty, &name, &qualifier);
             let get_single = parse_quote! {
-                let #ident = cx.get_single_with_name(#name);
+                let #ident = cx.#get_single;
             };
 
             Ok(ResolveOne {
                 stmt: ResolveOneValue::Ref {
                     create_single,
-                    get_single,
+                    get_single: Box::new(get_single),
                 },
                 variable: ident,
+                dependency,
             })
         }
         None => {
-            let resolve = match color {
-                Color::Async => parse_quote! {
-                    let #ident = cx.resolve_with_name_async(#name).await;
-                },
-                Color::Sync => parse_quote! {
-                    let #ident = cx.resolve_with_name(#name);
-                },
-            };
+            let resolve = generate_resolve(
+                "try_resolve_{}",
+                color,
+                &name,
+                &qualifier,
+                |resolve| quote!(#resolve),
+                &ident,
+            );
 
             Ok(ResolveOne {
                 stmt: ResolveOneValue::Owned { resolve },
                 variable: ident,
+                dependency,
             })
         }
     }
 }
 
+/// The generated code of the config attributes calls `Context::config`,
+/// which only exists when the `config` feature of `rudi` is enabled.
+pub(crate) fn check_config_feature(span: Span, attribute: &str) -> syn::Result<()> {
+    if cfg!(feature = "config") {
+        return Ok(());
+    }
+
+    Err(syn::Error::new(
+        span,
+        format!(
+            "`{}` requires the `config` feature of `rudi`, please enable it: `rudi = {{ features = [\"config\"] }}`",
+            attribute
+        ),
+    ))
+}
+
+/// Config values are looked up in the config of the context instead of being resolved from providers,
+/// so they are not declared as dependencies.
+fn generate_config_resolve_stmt(
+    key: Expr,
+    option: bool,
+    default: FlagOrValue<Expr>,
+    index: usize,
+) -> ResolveOne {
+    let ident = format_ident!("owned_{}", index);
+
+    let default: Option<Expr> = match default {
+        FlagOrValue::None => None,
+        FlagOrValue::Flag { .. } => Some(parse_quote!(::core::default::Default::default())),
+        FlagOrValue::Value { value: expr, .. } => Some(expr),
+    };
+
+    let resolve = if option {
+        parse_quote! {
+            let #ident = cx.config().get_option(#key)?;
+        }
+    } else if let Some(default) = default {
+        parse_quote! {
+            let #ident = match cx.config().get_option(#key)? {
+                Some(value) => value,
+                None => #default,
+            };
+        }
+    } else {
+        parse_quote! {
+            let #ident = cx.config().get(#key)?;
+        }
+    };
+
+    ResolveOne {
+        stmt: ResolveOneValue::Owned { resolve },
+        variable: ident,
+        dependency: None,
+    }
+}
+
+/// Returns the call of the method with the name, e.g. `try_resolve_with_name::<T>(name)`,
+/// or the call of the method with the qualifier, e.g. `try_resolve_qualified::<T, Q>()`,
+/// where `{}` in the given method is replaced by `with_name` or `qualified`.
+fn name_or_qualified(
+    method: &str,
+    ty: &TokenStream,
+    name: &Expr,
+    qualifier: &Option<Type>,
+) -> TokenStream {
+    match qualifier {
+        Some(qualifier) => {
+            let method = format_ident!("{}", method.replace("{}", "qualified"));
+            quote!(#method::<#ty, #qualifier>())
+        }
+        None => {
+            let method = format_ident!("{}", method.replace("{}", "with_name"));
+            quote!(#method::<#ty>(#name))
+        }
+    }
+}
+
+fn generate_create_single(
+    method: &str,
+    color: Color,
+    ty: &TokenStream,
+    name: &Expr,
+    qualifier: &Option<Type>,
+) -> Stmt {
+    match color {
+        Color::Async => {
+            let create_single =
+                name_or_qualified(&format!("{}_async", method), ty, name, qualifier);
+            parse_quote! {
+                cx.#create_single.await?;
+            }
+        }
+        Color::Sync => {
+            let create_single = name_or_qualified(method, ty, name, qualifier);
+            parse_quote! {
+                cx.#create_single?;
+            }
+        }
+    }
+}
+
+fn generate_resolve(
+    method: &str,
+    color: Color,
+    name: &Expr,
+    qualifier: &Option<Type>,
+    wrap: impl FnOnce(TokenStream) -> TokenStream,
+    ident: &Ident,
+) -> Stmt {
+    let ty = quote!(_);
+
+    let resolve = match color {
+        Color::Async => {
+            let resolve = name_or_qualified(&format!("{}_async", method), &ty, name, qualifier);
+            quote!(cx.#resolve.await?)
+        }
+        Color::Sync => {
+            let resolve = name_or_qualified(method, &ty, name, qualifier);
+            quote!(cx.#resolve?)
+        }
+    };
+
+    let resolve = wrap(resolve);
+
+    parse_quote! {
+        let #ident = #resolve;
+    }
+}
+
 pub(crate) struct ArgumentResolveStmts {
     pub(crate) ref_mut_cx_stmts: Vec<Stmt>,
     pub(crate) ref_cx_stmts: Vec<Stmt>,
     pub(crate) args: Vec<Ident>,
+    pub(crate) dependencies: Vec<TokenStream>,
 }
 
 pub(crate) fn generate_argument_resolve_methods(
     inputs: &mut Punctuated<FnArg, Token![,]>,
     color: Color,
+    rudi_path: &Path,
 ) -> syn::Result<ArgumentResolveStmts> {
     let capacity = inputs.len();
 
     let mut ref_mut_cx_stmts = Vec::with_capacity(capacity);
     let mut ref_cx_stmts = Vec::with_capacity(capacity);
     let mut args = Vec::with_capacity(capacity);
+    let mut dependencies = Vec::with_capacity(capacity);
 
     for (index, input) in inputs.iter_mut().enumerate() {
         match input {
@@ -444,8 +966,13 @@ pub(crate) fn generate_argument_resolve_methods(
                 return Err(syn::Error::new(r.span(), "not support `self` receiver"))
             }
             FnArg::Typed(PatType { attrs, ty, .. }) => {
-                let ResolveOne { stmt, variable } =
-                    generate_only_one_field_or_argument_resolve_stmt(attrs, color, index, ty)?;
+                let ResolveOne {
+                    stmt,
+                    variable,
+                    dependency,
+                } = generate_only_one_field_or_argument_resolve_stmt(
+                    attrs, color, index, ty, rudi_path,
+                )?;
 
                 match stmt {
                     ResolveOneValue::Owned { resolve } => ref_mut_cx_stmts.push(resolve),
@@ -454,11 +981,12 @@ pub(crate) fn generate_argument_resolve_methods(
                         get_single,
                     } => {
                         ref_mut_cx_stmts.push(create_single);
-                        ref_cx_stmts.push(get_single);
+                        ref_cx_stmts.push(*get_single);
                     }
                 }
 
                 args.push(variable);
+                dependencies.extend(dependency);
             }
         }
     }
@@ -467,6 +995,7 @@ pub(crate) fn generate_argument_resolve_methods(
         ref_mut_cx_stmts,
         ref_cx_stmts,
         args,
+        dependencies,
     })
 }
 
@@ -517,6 +1046,7 @@ pub(crate) fn check_generics_when_enable_auto_register(
 pub(crate) struct FieldResolveStmts {
     pub(crate) ref_mut_cx_stmts: Vec<Stmt>,
     pub(crate) ref_cx_stmts: Vec<Stmt>,
+    pub(crate) dependencies: Vec<TokenStream>,
     pub(crate) fields: ResolvedFields,
 }
 
@@ -532,11 +1062,13 @@ pub(crate) enum ResolvedFields {
 pub(crate) fn generate_field_resolve_stmts(
     fields: &mut Fields,
     color: Color,
+    rudi_path: &Path,
 ) -> syn::Result<FieldResolveStmts> {
     match fields {
         Fields::Unit => Ok(FieldResolveStmts {
             ref_mut_cx_stmts: Vec::new(),
             ref_cx_stmts: Vec::new(),
+            dependencies: Vec::new(),
             fields: ResolvedFields::Unit,
         }),
         Fields::Named(FieldsNamed { named, .. }) => {
@@ -545,6 +1077,7 @@ pub(crate) fn generate_field_resolve_stmts(
             let mut ref_mut_cx_stmts = Vec::with_capacity(capacity);
             let mut ref_cx_stmts = Vec::with_capacity(capacity);
             let mut field_values = Vec::with_capacity(capacity);
+            let mut dependencies = Vec::with_capacity(capacity);
 
             let mut field_names = Vec::with_capacity(capacity);
 
@@ -561,7 +1094,10 @@ pub(crate) fn generate_field_resolve_stmts(
                 let ResolveOne {
                     stmt,
                     variable: field_value,
-                } = generate_only_one_field_or_argument_resolve_stmt(attrs, color, index, ty)?;
+                    dependency,
+                } = generate_only_one_field_or_argument_resolve_stmt(
+                    attrs, color, index, ty, rudi_path,
+                )?;
 
                 match stmt {
                     ResolveOneValue::Owned { resolve } => ref_mut_cx_stmts.push(resolve),
@@ -570,17 +1106,19 @@ pub(crate) fn generate_field_resolve_stmts(
                         get_single,
                     } => {
                         ref_mut_cx_stmts.push(create_single);
-                        ref_cx_stmts.push(get_single);
+                        ref_cx_stmts.push(*get_single);
                     }
                 }
 
                 field_values.push(field_value);
+                dependencies.extend(dependency);
                 field_names.push(field_name.clone().unwrap());
             }
 
             Ok(FieldResolveStmts {
                 ref_mut_cx_stmts,
                 ref_cx_stmts,
+                dependencies,
                 fields: ResolvedFields::Named {
                     field_names,
                     field_values,
@@ -593,12 +1131,16 @@ pub(crate) fn generate_field_resolve_stmts(
             let mut ref_mut_cx_stmts = Vec::with_capacity(capacity);
             let mut ref_cx_stmts = Vec::with_capacity(capacity);
             let mut field_values = Vec::with_capacity(capacity);
+            let mut dependencies = Vec::with_capacity(capacity);
 
             for (index, Field { attrs, ty, .. }) in unnamed.into_iter().enumerate() {
                 let ResolveOne {
                     stmt,
                     variable: field_value,
-                } = generate_only_one_field_or_argument_resolve_stmt(attrs, color, index, ty)?;
+                    dependency,
+                } = generate_only_one_field_or_argument_resolve_stmt(
+                    attrs, color, index, ty, rudi_path,
+                )?;
 
                 match stmt {
                     ResolveOneValue::Owned { resolve } => ref_mut_cx_stmts.push(resolve),
@@ -607,16 +1149,18 @@ pub(crate) fn generate_field_resolve_stmts(
                         get_single,
                     } => {
                         ref_mut_cx_stmts.push(create_single);
-                        ref_cx_stmts.push(get_single);
+                        ref_cx_stmts.push(*get_single);
                     }
                 }
 
                 field_values.push(field_value);
+                dependencies.extend(dependency);
             }
 
             Ok(FieldResolveStmts {
                 ref_mut_cx_stmts,
                 ref_cx_stmts,
+                dependencies,
                 fields: ResolvedFields::Unnamed(field_values),
             })
         }
@@ -0,0 +1,22 @@
+use from_attr::FromAttr;
+use syn::{parse_quote, Expr};
+
+// #[Decorator(name = "hello", auto_register = false)]
+
+#[derive(FromAttr)]
+#[attribute(idents = [Decorator])]
+pub(crate) struct DecoratorAttr {
+    #[attribute(default = default_name())]
+    pub(crate) name: Expr,
+
+    #[cfg(feature = "auto-register")]
+    #[attribute(default = DEFAULT_AUTO_REGISTER)]
+    pub(crate) auto_register: bool,
+}
+
+fn default_name() -> Expr {
+    parse_quote!("")
+}
+
+#[cfg(feature = "auto-register")]
+const DEFAULT_AUTO_REGISTER: bool = true;
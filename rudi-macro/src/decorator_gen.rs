@@ -0,0 +1,86 @@
+use from_attr::{AttrsValue, FromAttr};
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{spanned::Spanned, ItemFn, ReturnType};
+
+use crate::{decorator_attr::DecoratorAttr, di_attr::DiAttr};
+
+// #[Decorator(name = "hello")]
+// fn Double(n: i32, cx: &mut Context) -> i32 {
+//     n * 2
+// }
+
+pub(crate) fn generate(attr: DecoratorAttr, mut item_fn: ItemFn) -> syn::Result<TokenStream> {
+    let DiAttr { rudi_path } = match DiAttr::remove_attributes(&mut item_fn.attrs) {
+        Ok(Some(AttrsValue { value: attr, .. })) => attr,
+        Ok(None) => DiAttr::default(),
+        Err(AttrsValue { value: e, .. }) => return Err(e),
+    };
+
+    let DecoratorAttr {
+        name,
+        #[cfg(feature = "auto-register")]
+        auto_register,
+    } = attr;
+
+    if let Some(asyncness) = item_fn.sig.asyncness {
+        return Err(syn::Error::new(
+            asyncness.span(),
+            "`async fn` is not supported, a decorator must be a sync fn",
+        ));
+    }
+
+    if !item_fn.sig.generics.params.is_empty() {
+        return Err(syn::Error::new(
+            item_fn.sig.generics.span(),
+            "generics are not supported in decorators",
+        ));
+    }
+
+    if item_fn.sig.inputs.len() != 2 || matches!(item_fn.sig.output, ReturnType::Default) {
+        return Err(syn::Error::new(
+            item_fn.sig.span(),
+            "a decorator must have the signature `fn(T, &mut Context) -> T`",
+        ));
+    }
+
+    let vis = &item_fn.vis;
+
+    let docs = item_fn
+        .attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("doc"));
+
+    let ident = &item_fn.sig.ident;
+
+    #[cfg(not(feature = "auto-register"))]
+    let auto_register = quote! {};
+
+    #[cfg(feature = "auto-register")]
+    let auto_register = if auto_register {
+        quote! {
+            #rudi_path::register_decorator!(<#ident as #rudi_path::DefaultDecorator>::decorator());
+        }
+    } else {
+        quote! {}
+    };
+
+    let expand = quote! {
+        #(#docs)*
+        #[allow(non_camel_case_types)]
+        #vis struct #ident;
+
+        impl #rudi_path::DefaultDecorator for #ident {
+            fn decorator() -> #rudi_path::DynDecorator {
+                #[allow(non_snake_case)]
+                #item_fn
+
+                #rudi_path::DynDecorator::new(#name, #ident)
+            }
+        }
+
+        #auto_register
+    };
+
+    Ok(expand)
+}
@@ -3,29 +3,49 @@ use syn::{parse_quote, Expr, Type};
 
 // #[di(
 //     name = "..",
+//     qualifier = Q,
 //     option,
 //     default = 42,
 //     vec,
-//     ref = T
+//     map,
+//     lazy,
+//     factory,
+//     ref = T,
+//     config = "..",
 // )]
 
 #[derive(FromAttr)]
 #[attribute(idents = [di])]
 pub(crate) struct FieldOrArgumentAttr {
-    #[attribute(default = default_name(), conflicts = [vec])]
+    #[attribute(default = default_name(), conflicts = [qualifier, vec, map, config])]
     pub(crate) name: Expr,
 
-    #[attribute(conflicts = [default, vec])]
+    #[attribute(conflicts = [name, vec, map, factory, config])]
+    pub(crate) qualifier: Option<Type>,
+
+    #[attribute(conflicts = [default, vec, map])]
     pub(crate) option: bool,
 
-    #[attribute(conflicts = [option, vec])]
+    #[attribute(conflicts = [option, vec, map])]
     pub(crate) default: FlagOrValue<Expr>,
 
-    #[attribute(conflicts = [name, option, default])]
+    #[attribute(conflicts = [name, qualifier, option, default, map, config])]
     pub(crate) vec: bool,
 
-    #[attribute(rename = "ref")]
+    #[attribute(conflicts = [name, qualifier, option, default, vec, config])]
+    pub(crate) map: bool,
+
+    #[attribute(conflicts = [option, default, vec, map, ref_, config])]
+    pub(crate) lazy: bool,
+
+    #[attribute(conflicts = [qualifier, option, default, vec, map, lazy, ref_, config])]
+    pub(crate) factory: bool,
+
+    #[attribute(rename = "ref", conflicts = [config])]
     pub(crate) ref_: FlagOrValue<Type>,
+
+    #[attribute(conflicts = [name, qualifier, vec, map, lazy, factory, ref_])]
+    pub(crate) config: Option<Expr>,
 }
 
 fn default_name() -> Expr {
@@ -36,10 +56,15 @@ impl Default for FieldOrArgumentAttr {
     fn default() -> Self {
         Self {
             name: default_name(),
+            qualifier: Default::default(),
             option: Default::default(),
             default: Default::default(),
             vec: Default::default(),
+            map: Default::default(),
+            lazy: Default::default(),
+            factory: Default::default(),
             ref_: Default::default(),
+            config: Default::default(),
         }
     }
 }
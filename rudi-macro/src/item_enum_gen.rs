@@ -8,7 +8,7 @@ use crate::{
     commons::{self, FieldResolveStmts, ResolvedFields},
     di_attr::DiAttr,
     impl_fn_or_enum_variant_attr::ImplFnOrEnumVariantAttr,
-    struct_or_function_attr::{ClosureOrPath, StructOrFunctionAttr},
+    struct_or_function_attr::StructOrFunctionAttr,
 };
 
 pub(crate) fn generate(
@@ -26,12 +26,37 @@ pub(crate) fn generate(
         return Err(syn::Error::new(item_enum.span(), "not support empty enum"));
     }
 
+    if let Some(PathValue { path, .. }) = attr.fallible {
+        return Err(syn::Error::new(
+            path,
+            "`fallible` only support in function and impl block, please use a function that returns `Result<T, E>` instead",
+        ));
+    }
+
+    if let Some(PathValue { path, .. }) = attr.config_prefix {
+        return Err(syn::Error::new(
+            path,
+            "`config_prefix` only support in struct, please use `#[di(config = \"..\")]` on the fields instead",
+        ));
+    }
+
     let StructOrFunctionAttr {
         name,
         eager_create,
+        order,
+        primary,
+        qualifier,
+        profile,
         condition,
+        on_missing,
+        on_present,
         binds,
         async_,
+        fallible: _,
+        scope: custom_scope,
+        init,
+        dispose,
+        config_prefix: _,
         #[cfg(feature = "auto-register")]
         auto_register,
     } = attr;
@@ -49,9 +74,7 @@ pub(crate) fn generate(
         _ => Color::Sync,
     };
 
-    let condition = condition
-        .map(|ClosureOrPath(expr)| quote!(Some(#expr)))
-        .unwrap_or_else(|| quote!(None));
+    let condition = commons::generate_condition(condition, &rudi_path);
 
     let mut variant_spans = Vec::new();
 
@@ -109,10 +132,22 @@ pub(crate) fn generate(
     let FieldResolveStmts {
         ref_mut_cx_stmts,
         ref_cx_stmts,
+        dependencies,
         fields,
-    } = commons::generate_field_resolve_stmts(&mut variant.fields, color)?;
+    } = commons::generate_field_resolve_stmts(&mut variant.fields, color, &rudi_path)?;
+
+    let create_provider = commons::generate_create_provider(scope, color)?;
+
+    let custom_scope = commons::generate_custom_scope(custom_scope, scope)?;
+    let qualifier = commons::generate_qualifier(qualifier);
+    let profiles = commons::generate_profiles(profile);
+    let presence = commons::generate_presence(on_missing, on_present);
+
+    let post_construct = commons::generate_post_construct(init, color, &rudi_path);
+
+    let dependencies = commons::generate_dependencies(dependencies);
 
-    let create_provider = commons::generate_create_provider(scope, color);
+    let dispose = commons::generate_dispose(dispose, scope)?;
 
     let enum_ident = &item_enum.ident;
     let variant_ident = &variant.ident;
@@ -153,17 +188,17 @@ pub(crate) fn generate(
                 |cx| ::std::boxed::Box::pin(async {
                     #(#ref_mut_cx_stmts)*
                     #(#ref_cx_stmts)*
-                    #enum_ident::#instance
+                    ::core::result::Result::<_, #rudi_path::BoxError>::Ok(#enum_ident::#instance)
                 })
             }
         }
         Color::Sync => {
             quote! {
                 #[allow(unused_variables)]
-                |cx| {
+                |cx| -> ::core::result::Result<_, #rudi_path::BoxError> {
                     #(#ref_mut_cx_stmts)*
                     #(#ref_cx_stmts)*
-                    #enum_ident::#instance
+                    ::core::result::Result::Ok(#enum_ident::#instance)
                 }
             }
         }
@@ -192,7 +227,16 @@ pub(crate) fn generate(
                     #rudi_path::#create_provider(#constructor)
                         .name(#name)
                         .eager_create(#eager_create)
-                        .condition(#condition)
+                        .order(#order)
+                        .primary(#primary)
+                        #qualifier
+                        #profiles
+                        #condition
+                        #presence
+                        #dependencies
+                        #custom_scope
+                        #post_construct
+                        #dispose
                         #(
                             .bind(#binds)
                         )*
@@ -2,12 +2,12 @@ use from_attr::{AttrsValue, FromAttr, PathValue};
 use proc_macro2::TokenStream;
 use quote::quote;
 use rudi_core::{Color, Scope};
-use syn::{GenericParam, ItemFn, ReturnType};
+use syn::{spanned::Spanned, GenericParam, ItemFn, ReturnType};
 
 use crate::{
     commons::{self, ArgumentResolveStmts},
     di_attr::DiAttr,
-    struct_or_function_attr::{ClosureOrPath, StructOrFunctionAttr},
+    struct_or_function_attr::StructOrFunctionAttr,
 };
 
 // #[Singleton]
@@ -33,12 +33,30 @@ pub(crate) fn generate(
         ));
     }
 
+    if let Some(PathValue { path, .. }) = attr.config_prefix {
+        return Err(syn::Error::new(
+            path,
+            "`config_prefix` only support in struct, please use `#[di(config = \"..\")]` on the arguments instead",
+        ));
+    }
+
     let StructOrFunctionAttr {
         name,
         eager_create,
+        order,
+        primary,
+        qualifier,
+        profile,
         condition,
+        on_missing,
+        on_present,
         binds,
         async_: _,
+        fallible,
+        scope: custom_scope,
+        init,
+        dispose,
+        config_prefix: _,
         #[cfg(feature = "auto-register")]
         auto_register,
     } = attr;
@@ -56,17 +74,27 @@ pub(crate) fn generate(
         None => Color::Sync,
     };
 
-    let condition = condition
-        .map(|ClosureOrPath(expr)| quote!(Some(#expr)))
-        .unwrap_or_else(|| quote!(None));
+    let condition = commons::generate_condition(condition, &rudi_path);
 
     let ArgumentResolveStmts {
         ref_mut_cx_stmts,
         ref_cx_stmts,
         args,
-    } = commons::generate_argument_resolve_methods(&mut item_fn.sig.inputs, color)?;
+        dependencies,
+    } = commons::generate_argument_resolve_methods(&mut item_fn.sig.inputs, color, &rudi_path)?;
+
+    let create_provider = commons::generate_create_provider(scope, color)?;
+
+    let custom_scope = commons::generate_custom_scope(custom_scope, scope)?;
+    let qualifier = commons::generate_qualifier(qualifier);
+    let profiles = commons::generate_profiles(profile);
+    let presence = commons::generate_presence(on_missing, on_present);
+
+    let post_construct = commons::generate_post_construct(init, color, &rudi_path);
 
-    let create_provider = commons::generate_create_provider(scope, color);
+    let dependencies = commons::generate_dependencies(dependencies);
+
+    let dispose = commons::generate_dispose(dispose, scope)?;
 
     let (impl_generics, ty_generics, where_clause) = item_fn.sig.generics.split_for_impl();
 
@@ -79,10 +107,24 @@ pub(crate) fn generate(
 
     let ident = &item_fn.sig.ident;
 
+    let fallible = matches!(fallible, Some(PathValue { value: true, .. }));
+
     let return_type_ident = match &item_fn.sig.output {
+        ReturnType::Default if fallible => {
+            return Err(syn::Error::new(
+                item_fn.sig.span(),
+                "`fallible` requires the return type to be `Result<T, E>`",
+            ))
+        }
         ReturnType::Default => quote! {
             ()
         },
+        ReturnType::Type(_, ty) if fallible => {
+            let ty = commons::extract_fallible_type(ty)?;
+            quote! {
+                #ty
+            }
+        }
         ReturnType::Type(_, ty) => quote! {
             #ty
         },
@@ -118,22 +160,34 @@ pub(crate) fn generate(
     let turbofish = ty_generics.as_turbofish();
     let constructor = match color {
         Color::Async => {
+            let instance = if fallible {
+                quote!(#ident #turbofish (#(#args,)*).await?)
+            } else {
+                quote!(#ident #turbofish (#(#args,)*).await)
+            };
+
             quote! {
                 #[allow(unused_variables)]
                 |cx| ::std::boxed::Box::pin(async {
                     #(#ref_mut_cx_stmts)*
                     #(#ref_cx_stmts)*
-                    #ident #turbofish (#(#args,)*).await
+                    ::core::result::Result::<_, #rudi_path::BoxError>::Ok(#instance)
                 })
             }
         }
         Color::Sync => {
+            let instance = if fallible {
+                quote!(#ident #turbofish (#(#args,)*)?)
+            } else {
+                quote!(#ident #turbofish (#(#args,)*))
+            };
+
             quote! {
                 #[allow(unused_variables)]
-                |cx| {
+                |cx| -> ::core::result::Result<_, #rudi_path::BoxError> {
                     #(#ref_mut_cx_stmts)*
                     #(#ref_cx_stmts)*
-                    #ident #turbofish (#(#args,)*)
+                    ::core::result::Result::Ok(#instance)
                 }
             }
         }
@@ -167,7 +221,16 @@ pub(crate) fn generate(
                     #rudi_path::#create_provider(#constructor)
                         .name(#name)
                         .eager_create(#eager_create)
-                        .condition(#condition)
+                        .order(#order)
+                        .primary(#primary)
+                        #qualifier
+                        #profiles
+                        #condition
+                        #presence
+                        #dependencies
+                        #custom_scope
+                        #post_construct
+                        #dispose
                         #(
                             .bind(#binds)
                         )*
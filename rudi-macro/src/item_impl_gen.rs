@@ -11,7 +11,7 @@ use crate::{
     commons::{self, ArgumentResolveStmts},
     di_attr::DiAttr,
     impl_fn_or_enum_variant_attr::ImplFnOrEnumVariantAttr,
-    struct_or_function_attr::{ClosureOrPath, StructOrFunctionAttr},
+    struct_or_function_attr::StructOrFunctionAttr,
 };
 
 // struct A {
@@ -44,6 +44,13 @@ pub(crate) fn generate(
         ));
     }
 
+    if let Some(PathValue { path, .. }) = attr.config_prefix {
+        return Err(syn::Error::new(
+            path,
+            "`config_prefix` only support in struct, please use `#[di(config = \"..\")]` on the arguments instead",
+        ));
+    }
+
     let impl_span = item_impl.span();
 
     let ItemImpl {
@@ -131,9 +138,20 @@ fn generate_default_provider_impl<'a>(
     let StructOrFunctionAttr {
         name,
         eager_create,
+        order,
+        primary,
+        qualifier,
+        profile,
         condition,
+        on_missing,
+        on_present,
         binds,
         async_: _,
+        fallible,
+        scope: custom_scope,
+        init,
+        dispose,
+        config_prefix: _,
         #[cfg(feature = "auto-register")]
         auto_register,
     } = attr;
@@ -146,8 +164,19 @@ fn generate_default_provider_impl<'a>(
         scope,
     )?;
 
+    let fallible = matches!(fallible, Some(PathValue { value: true, .. }));
+
     let return_type: Type = match &impl_item_fn.sig.output {
+        ReturnType::Default if fallible => {
+            return Err(syn::Error::new(
+                impl_item_fn.sig.span(),
+                "`fallible` requires the return type to be `Result<T, E>`",
+            ))
+        }
         ReturnType::Default => parse_quote!(()),
+        ReturnType::Type(_, return_type) if fallible => {
+            commons::extract_fallible_type(return_type)?.clone()
+        }
         ReturnType::Type(_, return_type) => *return_type.clone(),
     };
 
@@ -169,10 +198,17 @@ fn generate_default_provider_impl<'a>(
     if !return_type_is_named && !return_type_is_self {
         return Err(syn::Error::new(
             impl_item_fn.sig.span(),
-            format!(
-                "return type must be `{}` or `Self`",
-                type_with_generics.into_token_stream()
-            ),
+            if fallible {
+                format!(
+                    "return type must be `Result<{}, E>` or `Result<Self, E>`",
+                    type_with_generics.into_token_stream()
+                )
+            } else {
+                format!(
+                    "return type must be `{}` or `Self`",
+                    type_with_generics.into_token_stream()
+                )
+            },
         ));
     }
 
@@ -181,17 +217,31 @@ fn generate_default_provider_impl<'a>(
         None => Color::Sync,
     };
 
-    let condition = condition
-        .map(|ClosureOrPath(expr)| quote!(Some(#expr)))
-        .unwrap_or_else(|| quote!(None));
+    let condition = commons::generate_condition(condition, &rudi_path);
 
     let ArgumentResolveStmts {
         ref_mut_cx_stmts,
         ref_cx_stmts,
         args,
-    } = commons::generate_argument_resolve_methods(&mut impl_item_fn.sig.inputs, color)?;
+        dependencies,
+    } = commons::generate_argument_resolve_methods(
+        &mut impl_item_fn.sig.inputs,
+        color,
+        &rudi_path,
+    )?;
+
+    let create_provider = commons::generate_create_provider(scope, color)?;
+
+    let custom_scope = commons::generate_custom_scope(custom_scope, scope)?;
+    let qualifier = commons::generate_qualifier(qualifier);
+    let profiles = commons::generate_profiles(profile);
+    let presence = commons::generate_presence(on_missing, on_present);
+
+    let post_construct = commons::generate_post_construct(init, color, &rudi_path);
+
+    let dependencies = commons::generate_dependencies(dependencies);
 
-    let create_provider = commons::generate_create_provider(scope, color);
+    let dispose = commons::generate_dispose(dispose, scope)?;
 
     let (impl_generics, _, where_clause) = generics.split_for_impl();
 
@@ -204,22 +254,34 @@ fn generate_default_provider_impl<'a>(
 
     let constructor = match color {
         Color::Async => {
+            let instance = if fallible {
+                quote!(#self_path::#fn_ident(#(#args,)*).await?)
+            } else {
+                quote!(#self_path::#fn_ident(#(#args,)*).await)
+            };
+
             quote! {
                 #[allow(unused_variables)]
                 |cx| ::std::boxed::Box::pin(async {
                     #(#ref_mut_cx_stmts)*
                     #(#ref_cx_stmts)*
-                    #self_path::#fn_ident(#(#args,)*).await
+                    ::core::result::Result::<_, #rudi_path::BoxError>::Ok(#instance)
                 })
             }
         }
         Color::Sync => {
+            let instance = if fallible {
+                quote!(#self_path::#fn_ident(#(#args,)*)?)
+            } else {
+                quote!(#self_path::#fn_ident(#(#args,)*))
+            };
+
             quote! {
                 #[allow(unused_variables)]
-                |cx| {
+                |cx| -> ::core::result::Result<_, #rudi_path::BoxError> {
                     #(#ref_mut_cx_stmts)*
                     #(#ref_cx_stmts)*
-                    #self_path::#fn_ident(#(#args,)*)
+                    ::core::result::Result::Ok(#instance)
                 }
             }
         }
@@ -246,7 +308,16 @@ fn generate_default_provider_impl<'a>(
                     #rudi_path::#create_provider(#constructor)
                         .name(#name)
                         .eager_create(#eager_create)
-                        .condition(#condition)
+                        .order(#order)
+                        .primary(#primary)
+                        #qualifier
+                        #profiles
+                        #condition
+                        #presence
+                        #dependencies
+                        #custom_scope
+                        #post_construct
+                        #dispose
                         #(
                             .bind(#binds)
                         )*
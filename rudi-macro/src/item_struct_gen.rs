@@ -7,7 +7,7 @@ use syn::ItemStruct;
 use crate::{
     commons::{self, FieldResolveStmts, ResolvedFields},
     di_attr::DiAttr,
-    struct_or_function_attr::{ClosureOrPath, StructOrFunctionAttr},
+    struct_or_function_attr::StructOrFunctionAttr,
 };
 
 pub(crate) fn generate(
@@ -21,12 +21,30 @@ pub(crate) fn generate(
         Err(AttrsValue { value: e, .. }) => return Err(e),
     };
 
+    if let Some(PathValue { path, .. }) = attr.fallible {
+        return Err(syn::Error::new(
+            path,
+            "`fallible` only support in function and impl block, please use a function that returns `Result<T, E>` instead",
+        ));
+    }
+
     let StructOrFunctionAttr {
         name,
         eager_create,
+        order,
+        primary,
+        qualifier,
+        profile,
         condition,
+        on_missing,
+        on_present,
         binds,
         async_,
+        fallible: _,
+        scope: custom_scope,
+        init,
+        dispose,
+        config_prefix,
         #[cfg(feature = "auto-register")]
         auto_register,
     } = attr;
@@ -39,35 +57,64 @@ pub(crate) fn generate(
         scope,
     )?;
 
+    if let Some(PathValue { path, .. }) = &config_prefix {
+        commons::check_config_feature(*path, "config_prefix")?;
+    }
+
     let color = match async_ {
         Some(PathValue { value: true, .. }) => Color::Async,
         _ => Color::Sync,
     };
 
-    let condition = condition
-        .map(|ClosureOrPath(expr)| quote!(Some(#expr)))
-        .unwrap_or_else(|| quote!(None));
+    let condition = commons::generate_condition(condition, &rudi_path);
 
+    // the whole struct is deserialized from the config, so the fields are not resolved
     let FieldResolveStmts {
         ref_mut_cx_stmts,
         ref_cx_stmts,
+        dependencies,
         fields,
-    } = commons::generate_field_resolve_stmts(&mut item_struct.fields, color)?;
+    } = match config_prefix {
+        Some(_) => FieldResolveStmts {
+            ref_mut_cx_stmts: Vec::new(),
+            ref_cx_stmts: Vec::new(),
+            dependencies: Vec::new(),
+            fields: ResolvedFields::Unit,
+        },
+        None => commons::generate_field_resolve_stmts(&mut item_struct.fields, color, &rudi_path)?,
+    };
+
+    let create_provider = commons::generate_create_provider(scope, color)?;
 
-    let create_provider = commons::generate_create_provider(scope, color);
+    let custom_scope = commons::generate_custom_scope(custom_scope, scope)?;
+    let qualifier = commons::generate_qualifier(qualifier);
+    let profiles = commons::generate_profiles(profile);
+    let presence = commons::generate_presence(on_missing, on_present);
+
+    let post_construct = commons::generate_post_construct(init, color, &rudi_path);
+
+    let dependencies = commons::generate_dependencies(dependencies);
+
+    let dispose = commons::generate_dispose(dispose, scope)?;
 
     let struct_ident = &item_struct.ident;
 
     let (impl_generics, ty_generics, where_clause) = item_struct.generics.split_for_impl();
 
-    let instance = match fields {
-        ResolvedFields::Unit => quote! {
+    let instance = match (config_prefix, fields) {
+        (Some(PathValue { value: prefix, .. }), _) => quote! {
+            cx.config().get::<Self>(#prefix)?
+        },
+        (None, ResolvedFields::Unit) => quote! {
             #struct_ident
         },
-        ResolvedFields::Named {
-            field_names,
-            field_values,
-        } => {
+        (
+            None,
+            ResolvedFields::Named {
+                field_names,
+                field_values,
+            },
+        ) => {
             quote! {
                 #struct_ident {
                     #(
@@ -76,7 +123,7 @@ pub(crate) fn generate(
                 }
             }
         }
-        ResolvedFields::Unnamed(field_values) => {
+        (None, ResolvedFields::Unnamed(field_values)) => {
             quote! {
                 #struct_ident(
                     #(
@@ -94,17 +141,17 @@ pub(crate) fn generate(
                 |cx| ::std::boxed::Box::pin(async {
                     #(#ref_mut_cx_stmts)*
                     #(#ref_cx_stmts)*
-                    #instance
+                    ::core::result::Result::<_, #rudi_path::BoxError>::Ok(#instance)
                 })
             }
         }
         Color::Sync => {
             quote! {
                 #[allow(unused_variables)]
-                |cx| {
+                |cx| -> ::core::result::Result<_, #rudi_path::BoxError> {
                     #(#ref_mut_cx_stmts)*
                     #(#ref_cx_stmts)*
-                    #instance
+                    ::core::result::Result::Ok(#instance)
                 }
             }
         }
@@ -133,7 +180,16 @@ pub(crate) fn generate(
                     #rudi_path::#create_provider(#constructor)
                         .name(#name)
                         .eager_create(#eager_create)
-                        .condition(#condition)
+                        .order(#order)
+                        .primary(#primary)
+                        #qualifier
+                        #profiles
+                        #condition
+                        #presence
+                        #dependencies
+                        #custom_scope
+                        #post_construct
+                        #dispose
                         #(
                             .bind(#binds)
                         )*
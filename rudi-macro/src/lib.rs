@@ -1,4 +1,6 @@
 mod commons;
+mod decorator_attr;
+mod decorator_gen;
 mod di_attr;
 mod field_or_argument_attr;
 mod impl_fn_or_enum_variant_attr;
@@ -11,9 +13,9 @@ mod struct_or_function_attr;
 use from_attr::FromAttr;
 use proc_macro::TokenStream;
 use rudi_core::Scope;
-use syn::{parse_macro_input, spanned::Spanned, Item};
+use syn::{parse_macro_input, spanned::Spanned, Item, ItemFn};
 
-use crate::struct_or_function_attr::StructOrFunctionAttr;
+use crate::{decorator_attr::DecoratorAttr, struct_or_function_attr::StructOrFunctionAttr};
 
 fn generate(attr: TokenStream, item: TokenStream, scope: Scope) -> TokenStream {
     let attr = match StructOrFunctionAttr::from_tokens(attr.into()) {
@@ -63,3 +65,21 @@ pub fn Transient(attr: TokenStream, item: TokenStream) -> TokenStream {
 pub fn SingleOwner(attr: TokenStream, item: TokenStream) -> TokenStream {
     generate(attr, item, Scope::SingleOwner)
 }
+
+/// Define a decorator.
+#[doc = ""]
+#[doc = include_str!("./docs/decorator_macro.md")]
+#[proc_macro_attribute]
+#[allow(non_snake_case)]
+pub fn Decorator(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let attr = match DecoratorAttr::from_tokens(attr.into()) {
+        Ok(attr) => attr,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    let item_fn = parse_macro_input!(item as ItemFn);
+
+    decorator_gen::generate(attr, item_fn)
+        .unwrap_or_else(|e| e.to_compile_error())
+        .into()
+}
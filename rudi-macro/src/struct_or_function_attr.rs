@@ -1,5 +1,5 @@
 use from_attr::{ConvertParsed, FromAttr, PathValue};
-use syn::{parse_quote, spanned::Spanned, Expr, ExprPath};
+use syn::{parse_quote, spanned::Spanned, Expr, ExprPath, Type};
 
 #[derive(FromAttr)]
 #[attribute(idents = [di])]
@@ -9,13 +9,36 @@ pub(crate) struct StructOrFunctionAttr {
 
     pub(crate) eager_create: bool,
 
+    #[attribute(default = default_order())]
+    pub(crate) order: Expr,
+
+    pub(crate) primary: bool,
+
+    pub(crate) qualifier: Option<Type>,
+
+    pub(crate) profile: Option<Profiles>,
+
     pub(crate) condition: Option<ClosureOrPath>,
 
+    pub(crate) on_missing: Option<Type>,
+
+    pub(crate) on_present: Option<Type>,
+
     pub(crate) binds: Vec<ExprPath>,
 
     #[attribute(rename = "async")]
     pub(crate) async_: Option<PathValue<bool>>,
 
+    pub(crate) fallible: Option<PathValue<bool>>,
+
+    pub(crate) scope: Option<PathValue<Type>>,
+
+    pub(crate) init: Option<ClosureOrPath>,
+
+    pub(crate) dispose: Option<PathValue<Expr>>,
+
+    pub(crate) config_prefix: Option<PathValue<Expr>>,
+
     #[cfg(feature = "auto-register")]
     #[attribute(default = DEFAULT_AUTO_REGISTER)]
     pub(crate) auto_register: bool,
@@ -25,9 +48,26 @@ fn default_name() -> Expr {
     parse_quote!("")
 }
 
+fn default_order() -> Expr {
+    parse_quote!(0)
+}
+
 #[cfg(feature = "auto-register")]
 const DEFAULT_AUTO_REGISTER: bool = true;
 
+pub(crate) struct Profiles(pub(crate) Vec<Expr>);
+
+impl ConvertParsed for Profiles {
+    type Type = Expr;
+
+    fn convert(path_value: PathValue<Self::Type>) -> syn::Result<Self> {
+        match path_value.value {
+            Expr::Array(array) => Ok(Self(array.elems.into_iter().collect())),
+            expr => Ok(Self(vec![expr])),
+        }
+    }
+}
+
 pub(crate) struct ClosureOrPath(pub(crate) Expr);
 
 impl ConvertParsed for ClosureOrPath {
@@ -0,0 +1,217 @@
+use std::{borrow::Cow, cell::RefCell, collections::HashSet, env, ops::Deref};
+
+use super::{Context, Evaluate, MaybeSendSync, Provider, Shared, Type};
+
+#[cfg(feature = "config")]
+use super::Config;
+
+/// The condition of a provider, which keeps the function it is created from,
+/// so that [`Provider::condition`](crate::Provider::condition) can still return it.
+#[derive(Clone)]
+pub(crate) struct Condition {
+    function: Option<fn(&Context) -> bool>,
+    evaluate: Evaluate,
+}
+
+impl Condition {
+    pub(crate) fn from_function(function: fn(&Context) -> bool) -> Self {
+        Self {
+            function: Some(function),
+            evaluate: Shared::new(move |cx: &ConditionContext| function(cx)),
+        }
+    }
+
+    pub(crate) fn from_closure<F>(closure: F) -> Self
+    where
+        F: Fn(&ConditionContext) -> bool + 'static + MaybeSendSync,
+    {
+        Self {
+            function: None,
+            evaluate: Shared::new(closure),
+        }
+    }
+
+    pub(crate) fn function(&self) -> Option<fn(&Context) -> bool> {
+        self.function
+    }
+
+    pub(crate) fn evaluate(&self) -> &dyn Fn(&ConditionContext) -> bool {
+        &*self.evaluate
+    }
+}
+
+/// The view of a [`Context`] passed to the [`condition_with`](crate::SingletonProvider::condition_with) of a provider.
+///
+/// It exposes what a condition can depend on: the active profiles, the configuration values,
+/// the environment variables, the instances that are already created and the registered providers.
+///
+/// A condition may be evaluated several times while the context is flushed,
+/// see [`Context::flush`] for more details.
+///
+/// It dereferences to the [`Context`], so a condition written for `fn(&Context) -> bool` keeps working,
+/// but such a condition is evaluated again whenever a provider of any type is inserted or removed while flushing.
+///
+/// # Example
+///
+/// ```rust
+/// use rudi::{modules, providers, transient, Context, DynProvider, Module};
+///
+/// struct MyModule;
+///
+/// impl Module for MyModule {
+///     fn providers() -> Vec<DynProvider> {
+///         let verbose = true;
+///
+///         providers![
+///             transient(|_| "verbose").condition_with(move |_| verbose),
+///             transient(|_| 42).condition_with(|cx| cx.contains_provider::<&str>()),
+///         ]
+///     }
+/// }
+///
+/// # fn main() {
+/// let mut cx = Context::create(modules![MyModule]);
+/// assert_eq!(cx.resolve::<i32>(), 42);
+/// # }
+/// ```
+pub struct ConditionContext<'a> {
+    cx: &'a Context,
+    reads: Option<&'a RefCell<ConditionReads>>,
+}
+
+/// What a condition has read from the [`Context`] while it was evaluated.
+#[derive(Default)]
+pub(crate) struct ConditionReads {
+    // the types of the providers queried by the condition
+    types: HashSet<Type>,
+    // whether the condition accessed the context directly, so it may have queried any provider
+    context: bool,
+}
+
+impl ConditionReads {
+    /// Returns whether the result of the condition may change when the providers of the given types change.
+    pub(crate) fn is_affected_by(&self, changed: &HashSet<Type>) -> bool {
+        self.context || !self.types.is_disjoint(changed)
+    }
+}
+
+impl<'a> ConditionContext<'a> {
+    pub(crate) fn new(cx: &'a Context) -> Self {
+        Self { cx, reads: None }
+    }
+
+    /// Creates a view that records the types of the providers queried by the condition into `reads`.
+    pub(crate) fn recording(cx: &'a Context, reads: &'a RefCell<ConditionReads>) -> Self {
+        Self {
+            cx,
+            reads: Some(reads),
+        }
+    }
+
+    fn read<T: 'static>(&self) {
+        if let Some(reads) = self.reads {
+            reads.borrow_mut().types.insert(Type::new::<T>());
+        }
+    }
+
+    /// Returns the active profiles of the context, see [`Context::profiles`].
+    pub fn profiles(&self) -> &'a [Cow<'static, str>] {
+        self.cx.profiles()
+    }
+
+    /// Returns whether the given profile is active in the context, see [`Context::is_profile_active`].
+    pub fn is_profile_active(&self, profile: &str) -> bool {
+        self.cx.is_profile_active(profile)
+    }
+
+    /// Returns the configuration values of the context, see [`Context::config`].
+    #[cfg_attr(docsrs, doc(cfg(feature = "config")))]
+    #[cfg(feature = "config")]
+    pub fn config(&self) -> &'a Config {
+        self.cx.config()
+    }
+
+    /// Returns the value of the environment variable, or `None` if it is not set or not valid unicode.
+    pub fn env_var(&self, key: &str) -> Option<String> {
+        env::var(key).ok()
+    }
+
+    /// Returns true if the context contains a provider for the specified type and default name `""`.
+    pub fn contains_provider<T: 'static>(&self) -> bool {
+        self.read::<T>();
+        self.cx.contains_provider::<T>()
+    }
+
+    /// Returns true if the context contains a provider for the specified type and name.
+    pub fn contains_provider_with_name<T: 'static>(
+        &self,
+        name: impl Into<Cow<'static, str>>,
+    ) -> bool {
+        self.read::<T>();
+        self.cx.contains_provider_with_name::<T>(name)
+    }
+
+    /// Returns a reference to an provider based on the given type and default name `""`.
+    pub fn get_provider<T: 'static>(&self) -> Option<&'a Provider<T>> {
+        self.read::<T>();
+        self.cx.get_provider()
+    }
+
+    /// Returns a reference to an provider based on the given type and name.
+    pub fn get_provider_with_name<T: 'static>(
+        &self,
+        name: impl Into<Cow<'static, str>>,
+    ) -> Option<&'a Provider<T>> {
+        self.read::<T>();
+        self.cx.get_provider_with_name(name)
+    }
+
+    /// Returns a collection of references to providers based on the given type.
+    pub fn get_providers_by_type<T: 'static>(&self) -> Vec<&'a Provider<T>> {
+        self.read::<T>();
+        self.cx.get_providers_by_type()
+    }
+
+    /// Returns true if the context contains a [`Singleton`](crate::Scope::Singleton) or [`SingleOwner`](crate::Scope::SingleOwner) instance for the specified type and default name `""`.
+    pub fn contains_single<T: 'static>(&self) -> bool {
+        self.cx.contains_single::<T>()
+    }
+
+    /// Returns true if the context contains a [`Singleton`](crate::Scope::Singleton) or [`SingleOwner`](crate::Scope::SingleOwner) instance for the specified type and name.
+    pub fn contains_single_with_name<T: 'static>(
+        &self,
+        name: impl Into<Cow<'static, str>>,
+    ) -> bool {
+        self.cx.contains_single_with_name::<T>(name)
+    }
+
+    /// Returns an optional reference to a [`Singleton`](crate::Scope::Singleton) or [`SingleOwner`](crate::Scope::SingleOwner) instance based on the given type and default name `""`.
+    pub fn get_single_option<T: 'static>(&self) -> Option<&'a T> {
+        self.cx.get_single_option()
+    }
+
+    /// Returns an optional reference to a [`Singleton`](crate::Scope::Singleton) or [`SingleOwner`](crate::Scope::SingleOwner) instance based on the given type and name.
+    pub fn get_single_option_with_name<T: 'static>(
+        &self,
+        name: impl Into<Cow<'static, str>>,
+    ) -> Option<&'a T> {
+        self.cx.get_single_option_with_name(name)
+    }
+
+    /// Returns an optional reference to a [`Singleton`](crate::Scope::Singleton) or [`SingleOwner`](crate::Scope::SingleOwner) instance based on the given type and qualifier `Q`.
+    pub fn get_single_option_qualified<T: 'static, Q: 'static>(&self) -> Option<&'a T> {
+        self.cx.get_single_option_qualified::<T, Q>()
+    }
+}
+
+impl Deref for ConditionContext<'_> {
+    type Target = Context;
+
+    fn deref(&self) -> &Self::Target {
+        if let Some(reads) = self.reads {
+            reads.borrow_mut().context = true;
+        }
+
+        self.cx
+    }
+}
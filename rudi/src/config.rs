@@ -0,0 +1,247 @@
+use std::{
+    env,
+    error::Error,
+    fmt::{self, Display, Formatter},
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Value};
+
+/// The configuration values of a [`Context`](crate::Context), merged from the sources registered with
+/// [`ContextOptions::config_file`](crate::ContextOptions::config_file),
+/// [`ContextOptions::config_env`](crate::ContextOptions::config_env) and
+/// [`ContextOptions::config_values`](crate::ContextOptions::config_values).
+///
+/// The sources are loaded in the order in which they are registered, and a value of a later source
+/// overrides the value with the same key of an earlier source, tables are merged key by key.
+///
+/// A value is looked up by a dotted key, e.g. `database.url`, and deserialized into the requested type.
+/// A string value, e.g. from an environment variable, is also accepted for a number, a boolean or an array,
+/// if the string is the JSON representation of the requested value.
+///
+/// # Example
+///
+/// ```rust
+/// use rudi::{modules, Context};
+///
+/// # fn main() {
+/// let cx = Context::options()
+///     .config_values([("database.url", "postgres://localhost"), ("database.port", "5432")])
+///     .create(modules![]);
+///
+/// let config = cx.config();
+/// assert_eq!(config.get::<String>("database.url").unwrap(), "postgres://localhost");
+/// assert_eq!(config.get::<u16>("database.port").unwrap(), 5432);
+/// assert!(config.get::<u16>("database.timeout").is_err());
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Config {
+    root: Value,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            root: Value::Object(Map::new()),
+        }
+    }
+}
+
+impl Config {
+    pub(crate) fn load(sources: Vec<ConfigSource>) -> Result<Config, ConfigError> {
+        let mut config = Config::default();
+
+        for source in sources {
+            match source {
+                ConfigSource::File(path) => {
+                    let value = read_file(&path)?;
+                    merge(&mut config.root, value);
+                }
+                ConfigSource::Env(prefix) => {
+                    let prefix = format!("{}_", prefix);
+
+                    for (name, value) in env::vars() {
+                        let Some(key) = name.strip_prefix(&prefix) else {
+                            continue;
+                        };
+
+                        let segments = key.split("__").map(str::to_lowercase).collect::<Vec<_>>();
+                        config.insert(&segments, Value::String(value));
+                    }
+                }
+                ConfigSource::Values(values) => {
+                    for (key, value) in values {
+                        let segments = key.split('.').map(str::to_owned).collect::<Vec<_>>();
+                        config.insert(&segments, value);
+                    }
+                }
+            }
+        }
+
+        Ok(config)
+    }
+
+    fn insert(&mut self, segments: &[String], value: Value) {
+        let mut nested = value;
+
+        for segment in segments.iter().rev() {
+            let mut table = Map::new();
+            table.insert(segment.clone(), nested);
+            nested = Value::Object(table);
+        }
+
+        merge(&mut self.root, nested);
+    }
+
+    fn find(&self, key: &str) -> Option<&Value> {
+        key.split('.')
+            .try_fold(&self.root, |value, segment| match value {
+                Value::Object(table) => table.get(segment),
+                Value::Array(array) => array.get(segment.parse::<usize>().ok()?),
+                _ => None,
+            })
+    }
+
+    /// Returns whether there is a value for the key.
+    pub fn contains(&self, key: &str) -> bool {
+        self.find(key).is_some()
+    }
+
+    /// Returns the value for the key, deserialized into `T`.
+    ///
+    /// # Errors
+    ///
+    /// - Returns [`ConfigError::Missing`] if there is no value for the key.
+    /// - Returns [`ConfigError::Invalid`] if the value cannot be deserialized into `T`.
+    pub fn get<T: DeserializeOwned>(&self, key: &str) -> Result<T, ConfigError> {
+        match self.get_option(key)? {
+            Some(value) => Ok(value),
+            None => Err(ConfigError::Missing(key.to_owned())),
+        }
+    }
+
+    /// Returns the value for the key deserialized into `T`, or `None` if there is no value for the key.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`ConfigError::Invalid`] if the value cannot be deserialized into `T`.
+    pub fn get_option<T: DeserializeOwned>(&self, key: &str) -> Result<Option<T>, ConfigError> {
+        let Some(value) = self.find(key) else {
+            return Ok(None);
+        };
+
+        match T::deserialize(value) {
+            Ok(o) => Ok(Some(o)),
+            Err(e) => match T::deserialize(&parse_strings(value)) {
+                Ok(o) => Ok(Some(o)),
+                Err(_) => Err(ConfigError::Invalid(key.to_owned(), e)),
+            },
+        }
+    }
+}
+
+pub(crate) enum ConfigSource {
+    File(PathBuf),
+    Env(String),
+    Values(Vec<(String, Value)>),
+}
+
+fn read_file(path: &Path) -> Result<Value, ConfigError> {
+    let content =
+        fs::read_to_string(path).map_err(|e| ConfigError::ReadFile(path.to_owned(), e))?;
+
+    let parse_error = |e: &dyn Display| ConfigError::ParseFile(path.to_owned(), e.to_string());
+
+    match path.extension().and_then(|extension| extension.to_str()) {
+        Some("json") => serde_json::from_str(&content).map_err(|e| parse_error(&e)),
+        Some("toml") => toml::from_str(&content).map_err(|e| parse_error(&e)),
+        _ => Err(parse_error(
+            &"unsupported format, expected a `.json` or `.toml` file",
+        )),
+    }
+}
+
+/// Merges `value` into `target`, tables are merged key by key, other values are replaced.
+fn merge(target: &mut Value, value: Value) {
+    match (target, value) {
+        (Value::Object(target), Value::Object(table)) => {
+            for (key, value) in table {
+                match target.get_mut(&key) {
+                    Some(existing) => merge(existing, value),
+                    None => {
+                        target.insert(key, value);
+                    }
+                }
+            }
+        }
+        (target, value) => *target = value,
+    }
+}
+
+/// Returns a copy of the value, in which the strings that are valid JSON are replaced by the parsed values.
+fn parse_strings(value: &Value) -> Value {
+    match value {
+        Value::String(s) => serde_json::from_str(s).unwrap_or_else(|_| value.clone()),
+        Value::Array(array) => Value::Array(array.iter().map(parse_strings).collect()),
+        Value::Object(table) => Value::Object(
+            table
+                .iter()
+                .map(|(key, value)| (key.clone(), parse_strings(value)))
+                .collect(),
+        ),
+        _ => value.clone(),
+    }
+}
+
+/// Represents an error that occurred while loading or looking up configuration values.
+///
+/// When returned from the constructor of a provider, e.g. one generated by `#[di(config = "...")]`,
+/// it is reported as [`ResolveErrorKind::ConstructorFailed`](crate::ResolveErrorKind::ConstructorFailed)
+/// with the definition of the provider.
+#[derive(Debug)]
+pub enum ConfigError {
+    /// The configuration file could not be read.
+    ReadFile(PathBuf, io::Error),
+    /// The configuration file could not be parsed, or its format is not supported.
+    ParseFile(PathBuf, String),
+    /// There is no value for the key.
+    Missing(String),
+    /// The value for the key cannot be deserialized into the requested type.
+    Invalid(String, serde_json::Error),
+}
+
+impl Display for ConfigError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::ReadFile(path, e) => write!(
+                f,
+                "failed to read the configuration file `{}`: {}",
+                path.display(),
+                e
+            ),
+            ConfigError::ParseFile(path, e) => write!(
+                f,
+                "failed to parse the configuration file `{}`: {}",
+                path.display(),
+                e
+            ),
+            ConfigError::Missing(key) => write!(f, "missing configuration value for: `{}`", key),
+            ConfigError::Invalid(key, e) => {
+                write!(f, "invalid configuration value for: `{}`, {}", key, e)
+            }
+        }
+    }
+}
+
+impl Error for ConfigError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ConfigError::ReadFile(_, e) => Some(e),
+            ConfigError::Invalid(_, e) => Some(e),
+            ConfigError::ParseFile(..) | ConfigError::Missing(_) => None,
+        }
+    }
+}
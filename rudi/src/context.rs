@@ -1,10 +1,31 @@
-use std::{any::TypeId, borrow::Cow, collections::HashMap, rc::Rc};
+use std::{
+    any::TypeId,
+    borrow::Cow,
+    cell::RefCell,
+    collections::{HashMap, HashSet},
+    iter, mem,
+    time::Instant,
+};
 
-use crate::{
-    BoxFuture, Constructor, Definition, DynProvider, DynSingle, EagerCreateFunction, Key, Provider,
-    ProviderRegistry, ResolveModule, Scope, Single, SingleRegistry, Type,
+use super::{
+    find_cycles, AsyncConstructor, AsyncFactory, BoxError, BoxFuture, Color, ConditionContext,
+    ConditionReads, Constructor, ContextHandle, ContextObserver, CustomScope, Definition,
+    Dependency, DependencyGraph, DependencyKind, Dispose, DynDecorator, DynProvider, DynSingle,
+    EagerCreateFunction, Factory, JoinAll, Key, Lazy, MaybeSendSync, PostConstruct,
+    PresenceCondition, Provider, ProviderRegistry, ResolveError, ResolveErrorKind, ResolveModule,
+    Scope, ScopeDecision, ScopedRegistry, Shared, Single, SingleRegistry, StartupRecorder,
+    StartupReport, SyncConstructor, Type, ValidationError, ValidationErrorKind,
 };
 
+#[cfg(feature = "config")]
+use std::path::PathBuf;
+
+#[cfg(feature = "config")]
+use super::{Config, ConfigSource};
+
+type AsyncEagerCreateFunction =
+    for<'a> fn(&'a mut Context, Key) -> BoxFuture<'a, Result<(), ResolveError>>;
+
 /// A context is a container for all the providers and instances.
 ///
 /// It is the main entry point for the dependency injection.
@@ -111,15 +132,44 @@ pub struct Context {
     allow_only_single_eager_create: bool,
 
     eager_create: bool,
+    concurrent_eager_create: bool,
+
+    profiles: Vec<Cow<'static, str>>,
+
+    // the registries are shared with the views of the context, see `Context::view`
+    #[cfg(feature = "config")]
+    config: Shared<Config>,
+
+    single_registry: Shared<SingleRegistry>,
+    provider_registry: Shared<ProviderRegistry>,
+
+    custom_scopes: Shared<HashMap<TypeId, Box<dyn CustomScope>>>,
+    scoped_registry: ScopedRegistry,
 
-    single_registry: SingleRegistry,
-    provider_registry: ProviderRegistry,
+    observers: Shared<Vec<Box<dyn ContextObserver>>>,
+
+    // the decorators with the modules that registered them, `None` for the ones registered by `decorate`
+    decorators: Shared<Vec<(Option<Type>, DynDecorator)>>,
 
     loaded_modules: Vec<Type>,
     conditional_providers: Vec<(bool, DynProvider)>,
     eager_create_functions: Vec<(Definition, EagerCreateFunction)>,
 
     dependency_chain: DependencyChain,
+    // whether providers have been loaded since the declared dependencies were last checked for cycles
+    unchecked_dependencies: bool,
+    startup: StartupRecorder,
+
+    parent: Option<Shared<Context>>,
+    handle: Option<Handle>,
+}
+
+/// The [`ContextHandle`] captured by the handles obtained from a context.
+enum Handle {
+    /// The handle belongs to the context, which publishes its registries to it.
+    Own(Shared<ContextHandle>),
+    /// The context resolves an instance for the handle of another context.
+    Borrowed(Shared<ContextHandle>),
 }
 
 impl Default for Context {
@@ -128,12 +178,24 @@ impl Default for Context {
             allow_override: true,
             allow_only_single_eager_create: true,
             eager_create: Default::default(),
+            concurrent_eager_create: Default::default(),
+            profiles: Default::default(),
+            #[cfg(feature = "config")]
+            config: Default::default(),
             single_registry: Default::default(),
             provider_registry: Default::default(),
+            custom_scopes: Default::default(),
+            scoped_registry: Default::default(),
+            observers: Default::default(),
+            decorators: Default::default(),
             loaded_modules: Default::default(),
             conditional_providers: Default::default(),
             eager_create_functions: Default::default(),
             dependency_chain: Default::default(),
+            unchecked_dependencies: Default::default(),
+            startup: Default::default(),
+            parent: Default::default(),
+            handle: Default::default(),
         }
     }
 }
@@ -144,8 +206,12 @@ impl Context {
     /// # Panics
     ///
     /// - Panics if there are multiple providers with the same key and the context's [`allow_override`](Context::allow_override) is false.
+    /// - Panics if several providers of the same type are [`primary`](crate::Definition::primary).
+    /// - Panics if the [`dependencies`](crate::Provider::dependencies) declared by the providers form a cycle.
+    /// - Panics if the [`condition`](crate::Provider::condition)s of the providers contradict each other or are ambiguous.
     /// - Panics if there is a provider whose constructor is async and the provider will be eagerly created.
     /// - Panics if there is a provider that panics on construction.
+    /// - Panics if there is a provider whose constructor returns an error.
     ///
     /// # Example
     ///
@@ -184,6 +250,7 @@ impl Context {
     /// - Panics if there are multiple providers with the same key and the context's [`allow_override`](Context::allow_override) is false.
     /// - Panics if there is a provider whose constructor is async and the provider will be eagerly created.
     /// - Panics if there is a provider that panics on construction.
+    /// - Panics if there is a provider whose constructor returns an error.
     ///
     /// [`AutoRegisterModule`]: crate::AutoRegisterModule
     #[cfg_attr(docsrs, doc(cfg(feature = "auto-register")))]
@@ -204,6 +271,7 @@ impl Context {
     ///
     /// - Panics if there are multiple providers with the same key and the context's [`allow_override`](Context::allow_override) is false.
     /// - Panics if there is a provider that panics on construction.
+    /// - Panics if there is a provider whose constructor returns an error.
     pub async fn create_async(modules: Vec<ResolveModule>) -> Context {
         ContextOptions::default().create_async(modules).await
     }
@@ -219,12 +287,226 @@ impl Context {
     ///
     /// - Panics if there are multiple providers with the same key and the context's [`allow_override`](Context::allow_override) is false.
     /// - Panics if there is a provider that panics on construction.
+    /// - Panics if there is a provider whose constructor returns an error.
     #[cfg_attr(docsrs, doc(cfg(feature = "auto-register")))]
     #[cfg(feature = "auto-register")]
     pub async fn auto_register_async() -> Context {
         ContextOptions::default().auto_register_async().await
     }
 
+    /// Creates a new context with the given modules,
+    /// or returns a [`ResolveError`] if a provider that is eagerly created cannot be resolved,
+    /// or several providers of the same type are primary, see [`ResolveErrorKind::MultiplePrimaryProviders`](crate::ResolveErrorKind::MultiplePrimaryProviders),
+    /// or the declared dependencies form a cycle, see [`ResolveErrorKind::CircularDependencies`](crate::ResolveErrorKind::CircularDependencies),
+    /// or the conditions contradict each other, see [`ResolveErrorKind::ContradictoryConditions`](crate::ResolveErrorKind::ContradictoryConditions),
+    /// or have several stable results, see [`ResolveErrorKind::AmbiguousConditions`](crate::ResolveErrorKind::AmbiguousConditions).
+    ///
+    /// See [`Context::create`] for more details.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if there are multiple providers with the same key and the context's [`allow_override`](Context::allow_override) is false.
+    /// - Panics if there is a provider whose constructor is async and the provider will be eagerly created.
+    /// - Panics if there is a provider that panics on construction.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rudi::{modules, providers, try_singleton, Context, DynProvider, Module, ResolveErrorKind};
+    ///
+    /// struct MyModule;
+    ///
+    /// impl Module for MyModule {
+    ///     fn eager_create() -> bool {
+    ///         true
+    ///     }
+    ///
+    ///     fn providers() -> Vec<DynProvider> {
+    ///         providers![try_singleton(|_| "forty-two".parse::<i32>())]
+    ///     }
+    /// }
+    ///
+    /// # fn main() {
+    /// let err = Context::try_create(modules![MyModule]).err().unwrap();
+    /// assert!(matches!(err.kind(), ResolveErrorKind::ConstructorFailed(..)));
+    /// # }
+    /// ```
+    #[track_caller]
+    pub fn try_create(modules: Vec<ResolveModule>) -> Result<Context, ResolveError> {
+        ContextOptions::default().try_create(modules)
+    }
+
+    /// Creates a new context with the [`AutoRegisterModule`],
+    /// or returns a [`ResolveError`] if a provider that is eagerly created cannot be resolved.
+    ///
+    /// Same as `Context::try_create(modules![AutoRegisterModule])`.
+    ///
+    /// See [`Context::try_create`] for more details.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if there are multiple providers with the same key and the context's [`allow_override`](Context::allow_override) is false.
+    /// - Panics if there is a provider whose constructor is async and the provider will be eagerly created.
+    /// - Panics if there is a provider that panics on construction.
+    ///
+    /// [`AutoRegisterModule`]: crate::AutoRegisterModule
+    #[cfg_attr(docsrs, doc(cfg(feature = "auto-register")))]
+    #[cfg(feature = "auto-register")]
+    #[track_caller]
+    pub fn try_auto_register() -> Result<Context, ResolveError> {
+        ContextOptions::default().try_auto_register()
+    }
+
+    /// Async version of [`Context::try_create`].
+    ///
+    /// See [`Context::try_create`] for more details.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if there are multiple providers with the same key and the context's [`allow_override`](Context::allow_override) is false.
+    /// - Panics if there is a provider that panics on construction.
+    pub async fn try_create_async(modules: Vec<ResolveModule>) -> Result<Context, ResolveError> {
+        ContextOptions::default().try_create_async(modules).await
+    }
+
+    /// Async version of [`Context::try_auto_register`].
+    ///
+    /// See [`Context::try_auto_register`] for more details.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if there are multiple providers with the same key and the context's [`allow_override`](Context::allow_override) is false.
+    /// - Panics if there is a provider that panics on construction.
+    #[cfg_attr(docsrs, doc(cfg(feature = "auto-register")))]
+    #[cfg(feature = "auto-register")]
+    pub async fn try_auto_register_async() -> Result<Context, ResolveError> {
+        ContextOptions::default().try_auto_register_async().await
+    }
+
+    /// Creates a child context of the given context with the given modules.
+    ///
+    /// The child context can resolve the providers and instances of its parent and all ancestors,
+    /// and the providers registered in the child context override the providers with the same key
+    /// of the ancestors, only for the child context.
+    /// The options of the child context, e.g. [`allow_override`](Context::allow_override), are inherited from the parent.
+    ///
+    /// Since the parent is shared by its children, it must be wrapped in an `Rc`,
+    /// or an `Arc` for the context of the `sync` module, and can no longer be mutated.
+    ///
+    /// When the child context resolves a provider registered in an ancestor:
+    /// - If the ancestor already stores the [`Singleton`](crate::Scope::Singleton) or [`SingleOwner`](crate::Scope::SingleOwner) instance, the instance is used.
+    /// - Otherwise, the instance is created with the child context, so the overrides of its dependencies are applied,
+    ///   and is stored in the child context. It is not visible to the parent or the other children,
+    ///   and is dropped when the child context is dropped.
+    ///
+    /// To share a [`Singleton`](crate::Scope::Singleton) instance between children, create it in the parent before creating the children,
+    /// e.g. with `eager_create`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if there are multiple providers with the same key in the given modules and the context's [`allow_override`](Context::allow_override) is false.
+    /// - Panics if there is a provider whose constructor is async and the provider will be eagerly created.
+    /// - Panics if there is a provider that panics on construction.
+    /// - Panics if there is a provider whose constructor returns an error.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rudi::{modules, providers, singleton, transient, Context, DynProvider, Module, Shared};
+    ///
+    /// #[derive(Clone)]
+    /// struct Greeting(String);
+    ///
+    /// struct ParentModule;
+    ///
+    /// impl Module for ParentModule {
+    ///     fn providers() -> Vec<DynProvider> {
+    ///         providers![
+    ///             singleton(|_| "parent".to_string()).eager_create(true),
+    ///             transient(|cx| Greeting(cx.resolve())),
+    ///         ]
+    ///     }
+    /// }
+    ///
+    /// struct ChildModule;
+    ///
+    /// impl Module for ChildModule {
+    ///     fn providers() -> Vec<DynProvider> {
+    ///         providers![singleton(|_| "child".to_string())]
+    ///     }
+    /// }
+    ///
+    /// # fn main() {
+    /// let parent = Shared::new(Context::create(modules![ParentModule]));
+    ///
+    /// let mut child = parent.child(modules![ChildModule]);
+    /// assert_eq!(child.resolve::<Greeting>().0, "child");
+    ///
+    /// let mut other = parent.child(modules![]);
+    /// assert_eq!(other.resolve::<Greeting>().0, "parent");
+    ///
+    /// drop(child);
+    /// assert_eq!(parent.get_single::<String>(), "parent");
+    /// # }
+    /// ```
+    #[track_caller]
+    pub fn child(self: &Shared<Self>, modules: Vec<ResolveModule>) -> Context {
+        unwrap_or_panic(self.try_child(modules))
+    }
+
+    /// Async version of [`Context::child`].
+    ///
+    /// If no provider in the given modules has an async constructor and that provider needs to be eagerly created,
+    /// this method is the same as [`Context::child`].
+    ///
+    /// See [`Context::child`] for more details.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if there are multiple providers with the same key in the given modules and the context's [`allow_override`](Context::allow_override) is false.
+    /// - Panics if there is a provider that panics on construction.
+    /// - Panics if there is a provider whose constructor returns an error.
+    pub async fn child_async(self: &Shared<Self>, modules: Vec<ResolveModule>) -> Context {
+        unwrap_or_panic(self.try_child_async(modules).await)
+    }
+
+    /// Creates a child context of the given context with the given modules,
+    /// or returns a [`ResolveError`] if a provider that is eagerly created cannot be resolved.
+    ///
+    /// See [`Context::child`] for more details.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if there are multiple providers with the same key in the given modules and the context's [`allow_override`](Context::allow_override) is false.
+    /// - Panics if there is a provider whose constructor is async and the provider will be eagerly created.
+    /// - Panics if there is a provider that panics on construction.
+    #[track_caller]
+    pub fn try_child(
+        self: &Shared<Self>,
+        modules: Vec<ResolveModule>,
+    ) -> Result<Context, ResolveError> {
+        let mut cx = self.inner_child(modules);
+        cx.try_flush()?;
+        Ok(cx)
+    }
+
+    /// Async version of [`Context::try_child`].
+    ///
+    /// See [`Context::try_child`] for more details.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if there are multiple providers with the same key in the given modules and the context's [`allow_override`](Context::allow_override) is false.
+    /// - Panics if there is a provider that panics on construction.
+    pub async fn try_child_async(
+        self: &Shared<Self>,
+        modules: Vec<ResolveModule>,
+    ) -> Result<Context, ResolveError> {
+        let mut cx = self.inner_child(modules);
+        cx.try_flush_async().await?;
+        Ok(cx)
+    }
+
     /// Returns a new ContextOptions object.
     ///
     /// This function return a new ContextOptions object that you can use to create a context with specific options
@@ -271,16 +553,90 @@ impl Context {
         self.eager_create
     }
 
+    /// Returns whether the context should run the async constructors of independent eagerly created instances concurrently,
+    /// see [`ContextOptions::concurrent_eager_create`] for more details.
+    pub fn concurrent_eager_create(&self) -> bool {
+        self.concurrent_eager_create
+    }
+
+    /// Returns the active profiles of the context, see [`ContextOptions::profiles`] for more details.
+    pub fn profiles(&self) -> &[Cow<'static, str>] {
+        &self.profiles
+    }
+
+    /// Returns whether the given profile is active in the context.
+    ///
+    /// This method can be used in the condition of a provider to choose a provider by profile.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rudi::{Context, Singleton};
+    ///
+    /// #[Singleton(condition = |cx| cx.is_profile_active("test"))]
+    /// fn Url() -> String {
+    ///     "sqlite::memory:".to_string()
+    /// }
+    ///
+    /// # fn main() {
+    /// let cx = Context::options().profiles(["test"]).auto_register();
+    ///
+    /// assert!(cx.is_profile_active("test"));
+    /// assert!(cx.contains_provider::<String>());
+    /// # }
+    /// ```
+    pub fn is_profile_active(&self, profile: &str) -> bool {
+        self.profiles.iter().any(|active| active == profile)
+    }
+
+    /// Returns the configuration values of the context, see [`Config`] for more details.
+    ///
+    /// A child context shares the configuration values of its root context.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rudi::{Context, Singleton};
+    ///
+    /// #[Singleton(condition = |cx| cx.config().contains("cache.url"))]
+    /// fn Cache() -> String {
+    ///     "redis".to_string()
+    /// }
+    ///
+    /// # fn main() {
+    /// let cx = Context::options()
+    ///     .config_values([("cache.url", "redis://localhost")])
+    ///     .auto_register();
+    ///
+    /// assert_eq!(cx.config().get::<String>("cache.url").unwrap(), "redis://localhost");
+    /// assert!(cx.contains_provider::<String>());
+    /// # }
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "config")))]
+    #[cfg(feature = "config")]
+    pub fn config(&self) -> &Config {
+        &self.ancestors().last().unwrap_or(self).config
+    }
+
     /// Returns a reference to the single registry.
+    ///
+    /// Only the instances stored in the current context are included, not those of the parent.
     pub fn single_registry(&self) -> &HashMap<Key, DynSingle> {
         self.single_registry.inner()
     }
 
     /// Returns a reference to the provider registry.
+    ///
+    /// Only the providers registered in the current context are included, not those of the parent.
     pub fn provider_registry(&self) -> &HashMap<Key, DynProvider> {
         self.provider_registry.inner()
     }
 
+    /// Returns a reference to the parent context, if the context is created by [`Context::child`].
+    pub fn parent(&self) -> Option<&Context> {
+        self.parent.as_deref()
+    }
+
     /// Returns a reference to the loaded modules.
     pub fn loaded_modules(&self) -> &Vec<Type> {
         &self.loaded_modules
@@ -301,6 +657,178 @@ impl Context {
         &self.dependency_chain.stack
     }
 
+    /// Returns a [`DependencyGraph`] of the providers registered in the context and its ancestors,
+    /// and the dependencies between them observed while resolving instances.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rudi::{Context, Singleton, Transient};
+    ///
+    /// #[derive(Clone)]
+    /// #[Transient]
+    /// struct A;
+    ///
+    /// #[derive(Clone)]
+    /// #[Singleton]
+    /// struct B(A);
+    ///
+    /// # fn main() {
+    /// let mut cx = Context::auto_register();
+    /// assert!(cx.dependency_graph().edges().is_empty());
+    ///
+    /// cx.resolve::<B>();
+    ///
+    /// let graph = cx.dependency_graph();
+    /// let (dependent, dependency) = &graph.edges()[0];
+    /// assert_eq!(dependent.ty.name, std::any::type_name::<B>());
+    /// assert_eq!(dependency.ty.name, std::any::type_name::<A>());
+    /// # }
+    /// ```
+    pub fn dependency_graph(&self) -> DependencyGraph {
+        let nodes = self
+            .visible_providers()
+            .into_values()
+            .map(|provider| provider.definition().clone())
+            .collect();
+
+        let edges = self
+            .ancestors()
+            .flat_map(|cx| cx.dependency_chain.edges.iter().cloned())
+            .collect::<HashSet<_>>();
+
+        DependencyGraph::new(nodes, edges.into_iter().collect())
+    }
+
+    /// Returns a [`StartupReport`] of the instances created while the context was created or flushed,
+    /// e.g. by [`Context::create`] or [`Context::flush`], with their construction times, creation order, scope and color,
+    /// and the providers registered in the context and its ancestors that have never been resolved.
+    ///
+    /// The instances created by resolving after the flush, e.g. by [`Context::resolve`], are not included.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rudi::{Context, Singleton, Transient};
+    ///
+    /// #[derive(Clone)]
+    /// #[Singleton(eager_create)]
+    /// struct A;
+    ///
+    /// #[Transient]
+    /// struct B;
+    ///
+    /// # fn main() {
+    /// let mut cx = Context::auto_register();
+    ///
+    /// let report = cx.startup_report();
+    /// assert_eq!(report.created()[0].definition.key.ty.name, std::any::type_name::<A>());
+    /// assert_eq!(report.never_resolved()[0].key.ty.name, std::any::type_name::<B>());
+    ///
+    /// cx.resolve::<B>();
+    /// assert!(cx.startup_report().never_resolved().is_empty());
+    /// # }
+    /// ```
+    pub fn startup_report(&self) -> StartupReport {
+        let mut never_resolved = self
+            .visible_providers()
+            .into_iter()
+            .filter(|(key, _)| !self.ancestors().any(|cx| cx.startup.is_resolved(key)))
+            .map(|(_, provider)| provider.definition().clone())
+            .collect::<Vec<_>>();
+
+        never_resolved.sort_by(|a, b| {
+            let sort_key = |key: &Key| {
+                (
+                    key.ty.name,
+                    key.name.clone(),
+                    key.qualifier.map(|ty| ty.name),
+                )
+            };
+            sort_key(&a.key).cmp(&sort_key(&b.key))
+        });
+
+        self.startup.report(never_resolved)
+    }
+
+    /// Checks the dependencies declared by the providers registered in the context and its ancestors,
+    /// without constructing any instance.
+    ///
+    /// The dependencies are declared by the attribute macros, e.g. [`#[Singleton]`](crate::Singleton),
+    /// or set by the `dependencies` method of the providers, e.g. [`SingletonProvider::dependencies`](crate::SingletonProvider::dependencies).
+    ///
+    /// Returns all the problems found, see [`ValidationErrorKind`] for the kinds of problems.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rudi::{Context, Singleton, Transient, ValidationErrorKind};
+    ///
+    /// #[derive(Clone)]
+    /// #[Transient]
+    /// struct A;
+    ///
+    /// #[Singleton]
+    /// fn B(#[di(ref)] _a: &A) -> i32 {
+    ///     1
+    /// }
+    ///
+    /// #[derive(Clone)]
+    /// #[Singleton]
+    /// struct C(#[di(name = "c")] A);
+    ///
+    /// # fn main() {
+    /// let cx = Context::auto_register();
+    ///
+    /// let errors = cx.validate().unwrap_err();
+    /// assert_eq!(errors.len(), 2);
+    /// assert!(errors.iter().any(|e| matches!(e.kind(), ValidationErrorKind::ScopeMismatch(_))));
+    /// assert!(errors.iter().any(|e| matches!(e.kind(), ValidationErrorKind::NotFoundProvider)));
+    /// # }
+    /// ```
+    pub fn validate(&self) -> Result<(), Vec<ValidationError>> {
+        let providers = self.visible_providers();
+
+        let mut dependents = providers.values().collect::<Vec<_>>();
+        dependents.sort_by_key(|provider| {
+            let key = provider.key();
+            (
+                key.ty.name,
+                key.name.clone(),
+                key.qualifier.map(|ty| ty.name),
+            )
+        });
+
+        let mut errors = Vec::new();
+
+        for provider in dependents {
+            let dependent = provider.definition();
+
+            for dependency in provider.dependencies() {
+                let primary =
+                    if dependency.key.name.is_empty() && dependency.key.qualifier.is_none() {
+                        self.find_primary(&dependency.key.ty)
+                    } else {
+                        None
+                    };
+
+                errors.extend(
+                    validate_dependency(&providers, primary, dependent, dependency)
+                        .into_iter()
+                        .map(|kind| {
+                            ValidationError::new(dependent.clone(), dependency.clone(), kind)
+                        }),
+                );
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+
     /// Appends a standalone [`Singleton`](crate::Scope::Singleton) instance to the context with default name `""`.
     ///
     /// # Panics
@@ -321,7 +849,7 @@ impl Context {
     #[track_caller]
     pub fn insert_singleton<T>(&mut self, instance: T)
     where
-        T: 'static + Clone,
+        T: 'static + Clone + MaybeSendSync,
     {
         self.insert_singleton_with_name(instance, "");
     }
@@ -350,7 +878,7 @@ impl Context {
     #[track_caller]
     pub fn insert_singleton_with_name<T, N>(&mut self, instance: T, name: N)
     where
-        T: 'static + Clone,
+        T: 'static + Clone + MaybeSendSync,
         N: Into<Cow<'static, str>>,
     {
         let provider: DynProvider =
@@ -358,8 +886,8 @@ impl Context {
         let single = Single::new(instance, Some(Clone::clone)).into();
 
         let key = provider.key().clone();
-        self.provider_registry.insert(provider, self.allow_override);
-        self.single_registry.insert(key, single);
+        self.insert_provider(provider);
+        self.insert_single(key, single);
     }
 
     /// Appends a standalone [`SingleOwner`](crate::Scope::SingleOwner) instance to the context with default name `""`.
@@ -385,7 +913,7 @@ impl Context {
     #[track_caller]
     pub fn insert_single_owner<T>(&mut self, instance: T)
     where
-        T: 'static,
+        T: 'static + MaybeSendSync,
     {
         self.insert_single_owner_with_name(instance, "");
     }
@@ -417,7 +945,7 @@ impl Context {
     #[track_caller]
     pub fn insert_single_owner_with_name<T, N>(&mut self, instance: T, name: N)
     where
-        T: 'static,
+        T: 'static + MaybeSendSync,
         N: Into<Cow<'static, str>>,
     {
         let provider: DynProvider =
@@ -425,8 +953,8 @@ impl Context {
         let single = Single::new(instance, None).into();
 
         let key = provider.key().clone();
-        self.provider_registry.insert(provider, self.allow_override);
-        self.single_registry.insert(key, single);
+        self.insert_provider(provider);
+        self.insert_single(key, single);
     }
 
     /// Load the given modules.
@@ -437,6 +965,10 @@ impl Context {
     /// bound providers into a collection of providers without bound providers, and finally
     /// deposits the providers one by one into context.
     ///
+    /// The [`dependencies`](crate::Provider::dependencies) declared by the providers are checked for cycles
+    /// by the next call to [`Context::flush`], use [`Context::try_load_modules`] to check them immediately.
+    /// The conditional providers are only checked by [`Context::flush`], once their conditions are met.
+    ///
     /// # Panics
     ///
     /// - Panics if there are multiple providers with the same key and the context's [`allow_override`](Context::allow_override) is false.
@@ -464,12 +996,117 @@ impl Context {
             return;
         }
 
-        let modules = flatten(modules, ResolveModule::submodules);
+        self.inner_load_modules(modules);
+    }
 
-        modules.into_iter().for_each(|module| {
-            self.loaded_modules.push(module.ty());
-            self.load_providers(module.eager_create(), module.providers());
-        });
+    /// Same as [`Context::load_modules`], but returns a [`ResolveError`] if several providers of the same type
+    /// in the context are primary, see [`ResolveErrorKind::MultiplePrimaryProviders`](crate::ResolveErrorKind::MultiplePrimaryProviders),
+    /// or the [`dependencies`](crate::Provider::dependencies) declared by the providers in the context form a cycle,
+    /// a cycle is reported for each group of providers that depend on each other,
+    /// see [`ResolveErrorKind::CircularDependencies`](crate::ResolveErrorKind::CircularDependencies).
+    ///
+    /// The modules are loaded even if an error is returned.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if there are multiple providers with the same key and the context's [`allow_override`](Context::allow_override) is false.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rudi::{components, modules, Context, DynProvider, Module, ResolveErrorKind, Transient};
+    ///
+    /// #[derive(Clone)]
+    /// struct A;
+    ///
+    /// #[Transient(auto_register = false)]
+    /// fn NewA(a: A) -> A {
+    ///     a
+    /// }
+    ///
+    /// struct MyModule;
+    ///
+    /// impl Module for MyModule {
+    ///     fn providers() -> Vec<DynProvider> {
+    ///         components![NewA]
+    ///     }
+    /// }
+    ///
+    /// # fn main() {
+    /// let mut cx = Context::default();
+    ///
+    /// let err = cx.try_load_modules(modules![MyModule]).unwrap_err();
+    /// assert!(matches!(err.kind(), ResolveErrorKind::CircularDependencies(_)));
+    /// # }
+    /// ```
+    #[track_caller]
+    pub fn try_load_modules(&mut self, modules: Vec<ResolveModule>) -> Result<(), ResolveError> {
+        self.load_modules(modules);
+        self.check_primary_providers()?;
+        self.check_circular_dependencies()
+    }
+
+    /// Register a decorator that wraps or modifies the instances of type `T` with the given name.
+    ///
+    /// The decorator is applied every time a provider of that key constructs an instance,
+    /// including the providers produced by [`bind`](crate::SingletonProvider::bind) and the [`Factory`] handles,
+    /// after the [`post_construct`](crate::SingletonProvider::post_construct) hook and before the instance is cached.
+    /// Multiple decorators of the same key are applied in registration order,
+    /// the decorators registered in the ancestors of the context are applied first.
+    ///
+    /// See [`DynDecorator`] for more details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::rc::Rc;
+    ///
+    /// use rudi::{Context, Transient};
+    ///
+    /// trait Service {
+    ///     fn call(&self) -> String;
+    /// }
+    ///
+    /// #[Transient(binds = [Self::into_service])]
+    /// struct ServiceImpl;
+    ///
+    /// impl Service for ServiceImpl {
+    ///     fn call(&self) -> String {
+    ///         "call".to_string()
+    ///     }
+    /// }
+    ///
+    /// impl ServiceImpl {
+    ///     fn into_service(self) -> Rc<dyn Service> {
+    ///         Rc::new(self)
+    ///     }
+    /// }
+    ///
+    /// struct Logging(Rc<dyn Service>);
+    ///
+    /// impl Service for Logging {
+    ///     fn call(&self) -> String {
+    ///         format!("logged {}", self.0.call())
+    ///     }
+    /// }
+    ///
+    /// # fn main() {
+    /// let mut cx = Context::auto_register();
+    /// cx.decorate("", |service: Rc<dyn Service>, _cx| -> Rc<dyn Service> {
+    ///     Rc::new(Logging(service))
+    /// });
+    ///
+    /// assert_eq!(cx.resolve::<Rc<dyn Service>>().call(), "logged call");
+    /// # }
+    /// ```
+    pub fn decorate<T, F>(&mut self, name: impl Into<Cow<'static, str>>, decorate: F)
+    where
+        T: 'static,
+        F: Fn(T, &mut Context) -> T + 'static + MaybeSendSync,
+    {
+        let decorator = DynDecorator::new(name, decorate);
+
+        self.update_decorators(|decorators| decorators.push((None, decorator)));
     }
 
     /// Unload the given modules.
@@ -478,6 +1115,13 @@ impl Context {
     /// the [`Context::load_modules`] method, and then remove all providers in the context
     /// that are equal to the providers in the collection and their possible instances.
     ///
+    /// The dispose hooks of the removed instances are called in reverse creation order,
+    /// see [`Context::shutdown`] for more details.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if there is a removed instance whose dispose hook is async.
+    ///
     /// # Example
     ///
     /// ```rust
@@ -498,17 +1142,91 @@ impl Context {
     /// assert!(cx.get_provider::<A>().is_none());
     /// # }
     /// ```
+    #[track_caller]
     pub fn unload_modules(&mut self, modules: Vec<ResolveModule>) {
-        if modules.is_empty() {
-            return;
-        }
+        let singles = self.inner_unload_modules(modules);
+        dispose_singles(singles);
+    }
 
-        let modules = flatten(modules, ResolveModule::submodules);
+    /// Async version of [`Context::unload_modules`].
+    ///
+    /// See [`Context::unload_modules`] for more details.
+    pub async fn unload_modules_async(&mut self, modules: Vec<ResolveModule>) {
+        let singles = self.inner_unload_modules(modules);
+        dispose_singles_async(singles).await;
+    }
 
-        modules.into_iter().for_each(|module| {
-            self.loaded_modules.retain(|ty| ty != &module.ty());
-            self.unload_providers(module.providers());
-        });
+    /// Remove all the [`Singleton`](crate::Scope::Singleton) and [`SingleOwner`](crate::Scope::SingleOwner) instances
+    /// of the current context, and call their dispose hooks in reverse creation order,
+    /// so that an instance is disposed before the instances it depends on.
+    ///
+    /// Dispose hooks are set by [`SingletonProvider::on_dispose`](crate::SingletonProvider::on_dispose),
+    /// [`SingleOwnerProvider::on_dispose`](crate::SingleOwnerProvider::on_dispose),
+    /// or the `dispose` argument of the attribute macros, e.g. `#[Singleton(dispose = Self::close)]`.
+    /// The instances cached by a [`CustomScope`] are disposed before the other instances.
+    ///
+    /// The providers are not removed, so the instances can be created again after shutdown.
+    /// The instances of the parent context are not removed.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if there is an instance whose dispose hook is async.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::cell::RefCell;
+    ///
+    /// use rudi::{Context, Singleton};
+    ///
+    /// thread_local! {
+    ///     static CLOSED: RefCell<Vec<&'static str>> = const { RefCell::new(Vec::new()) };
+    /// }
+    ///
+    /// #[derive(Clone)]
+    /// #[Singleton(dispose = Self::close)]
+    /// struct Pool;
+    ///
+    /// impl Pool {
+    ///     fn close(self) {
+    ///         CLOSED.with(|closed| closed.borrow_mut().push("pool"));
+    ///     }
+    /// }
+    ///
+    /// #[derive(Clone)]
+    /// #[Singleton(dispose = Self::close)]
+    /// struct Repository(Pool);
+    ///
+    /// impl Repository {
+    ///     fn close(self) {
+    ///         CLOSED.with(|closed| closed.borrow_mut().push("repository"));
+    ///     }
+    /// }
+    ///
+    /// # fn main() {
+    /// let mut cx = Context::auto_register();
+    /// cx.resolve::<Repository>();
+    ///
+    /// cx.shutdown();
+    ///
+    /// assert!(!cx.contains_single::<Pool>());
+    /// CLOSED.with(|closed| assert_eq!(*closed.borrow(), ["repository", "pool"]));
+    /// # }
+    /// ```
+    #[track_caller]
+    pub fn shutdown(&mut self) {
+        let singles = self.remove_singles(&mut |_| true);
+        dispose_singles(singles);
+    }
+
+    /// Async version of [`Context::shutdown`].
+    ///
+    /// Dispose hooks can be async, e.g. set by [`SingletonProvider::on_dispose_async`](crate::SingletonProvider::on_dispose_async).
+    ///
+    /// See [`Context::shutdown`] for more details.
+    pub async fn shutdown_async(&mut self) {
+        let singles = self.remove_singles(&mut |_| true);
+        dispose_singles_async(singles).await;
     }
 
     /// Flush the context.
@@ -520,6 +1238,17 @@ impl Context {
     ///    If the evaluation result is `true`, the provider will be loaded into the context,
     ///    otherwise it will be removed from the context.
     ///
+    ///    The conditions are evaluated repeatedly, taking into account the providers whose conditions were met in the previous round,
+    ///    until the result no longer changes, so a condition that queries the context, e.g. by [`Context::contains_provider`],
+    ///    gives the same result whatever the order in which the modules are loaded.
+    ///    A condition does not see the provider it belongs to, nor the providers bound from it.
+    ///    Only the conditions that queried a provider whose result changed are evaluated again.
+    ///    If the results keep changing, the conditions either have several stable results,
+    ///    see [`ResolveErrorKind::AmbiguousConditions`](crate::ResolveErrorKind::AmbiguousConditions),
+    ///    or contradict each other, see [`ResolveErrorKind::ContradictoryConditions`](crate::ResolveErrorKind::ContradictoryConditions).
+    ///
+    ///    The [`presence`](crate::Definition::presence) conditions are then checked in load order.
+    ///
     /// 2. Construct instances that will be eagerly created.
     ///
     ///    Whether an instance need to be created eagerly depends on
@@ -539,8 +1268,12 @@ impl Context {
     /// # Panics
     ///
     /// - Panics if there are multiple providers with the same key and the context's [`allow_override`](Context::allow_override) is false.
+    /// - Panics if several providers of the same type are [`primary`](crate::Definition::primary).
+    /// - Panics if the [`dependencies`](crate::Provider::dependencies) declared by the providers form a cycle.
+    /// - Panics if the [`condition`](crate::Provider::condition)s of the providers contradict each other or are ambiguous.
     /// - Panics if there is a provider whose constructor is async and the provider will be eagerly created.
     /// - Panics if there is a provider that panics on construction.
+    /// - Panics if there is a provider whose constructor returns an error.
     ///
     /// # Example
     ///
@@ -630,10 +1363,64 @@ impl Context {
     /// ```
     #[track_caller]
     pub fn flush(&mut self) {
-        self.create_eager_instances();
+        unwrap_or_panic(self.try_flush())
+    }
+
+    /// Same as [`Context::flush`], but returns a [`ResolveError`] if a provider that is eagerly created cannot be resolved,
+    /// several providers of the same type are primary, the declared dependencies form a cycle,
+    /// or the conditions contradict each other or are ambiguous.
+    ///
+    /// If an error is returned, the remaining eagerly created providers are not created,
+    /// they will be created by the next call to this method or [`Context::flush`].
+    ///
+    /// # Panics
+    ///
+    /// - Panics if there are multiple providers with the same key and the context's [`allow_override`](Context::allow_override) is false.
+    /// - Panics if there is a provider whose constructor is async and the provider will be eagerly created.
+    /// - Panics if there is a provider that panics on construction.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rudi::{modules, providers, try_singleton, Context, DynProvider, Module};
+    ///
+    /// struct MyModule;
+    ///
+    /// impl Module for MyModule {
+    ///     fn eager_create() -> bool {
+    ///         true
+    ///     }
+    ///
+    ///     fn providers() -> Vec<DynProvider> {
+    ///         providers![try_singleton(|_| "42".parse::<i32>())]
+    ///     }
+    /// }
+    ///
+    /// # fn main() {
+    /// let mut cx = Context::default();
+    /// cx.load_modules(modules![MyModule]);
+    ///
+    /// assert!(cx.try_flush().is_ok());
+    /// assert_eq!(cx.get_single::<i32>(), &42);
+    /// # }
+    /// ```
+    #[track_caller]
+    pub fn try_flush(&mut self) -> Result<(), ResolveError> {
+        self.check_primary_providers()?;
+        self.check_circular_dependencies()?;
+
+        self.startup.recording = true;
+
+        let result = self.create_eager_instances().and_then(|()| {
+            self.evaluate_providers()?;
+            self.check_primary_providers()?;
+            self.check_circular_dependencies()?;
+            self.create_eager_instances()
+        });
 
-        self.evaluate_providers();
-        self.create_eager_instances();
+        self.startup.recording = false;
+
+        result
     }
 
     /// Async version of [`Context::flush`].
@@ -647,11 +1434,38 @@ impl Context {
     ///
     /// - Panics if there are multiple providers with the same key and the context's [`allow_override`](Context::allow_override) is false.
     /// - Panics if there is a provider that panics on construction.
+    /// - Panics if there is a provider whose constructor returns an error.
     pub async fn flush_async(&mut self) {
-        self.create_eager_instances_async().await;
+        unwrap_or_panic(self.try_flush_async().await)
+    }
+
+    /// Async version of [`Context::try_flush`].
+    ///
+    /// See [`Context::try_flush`] for more details.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if there are multiple providers with the same key and the context's [`allow_override`](Context::allow_override) is false.
+    /// - Panics if there is a provider that panics on construction.
+    pub async fn try_flush_async(&mut self) -> Result<(), ResolveError> {
+        self.check_primary_providers()?;
+        self.check_circular_dependencies()?;
+
+        self.startup.recording = true;
+
+        let result = async {
+            self.create_eager_instances_async().await?;
+
+            self.evaluate_providers()?;
+            self.check_primary_providers()?;
+            self.check_circular_dependencies()?;
+            self.create_eager_instances_async().await
+        }
+        .await;
+
+        self.startup.recording = false;
 
-        self.evaluate_providers();
-        self.create_eager_instances_async().await;
+        result
     }
 
     /// Returns a [`Singleton`](crate::Scope::Singleton) or [`Transient`](crate::Scope::Transient) instance based on the given type and default name `""`.
@@ -661,6 +1475,7 @@ impl Context {
     /// - Panics if no provider is registered for the given type and default name `""`.
     /// - Panics if there is a provider whose constructor is async.
     /// - Panics if there is a provider that panics on construction.
+    /// - Panics if there is a provider whose constructor returns an error.
     /// - Panics if the provider is not a [`Singleton`](crate::Scope::Singleton) or [`Transient`](crate::Scope::Transient).
     ///
     /// # Example
@@ -690,6 +1505,7 @@ impl Context {
     /// - Panics if no provider is registered for the given type and name.
     /// - Panics if there is a provider whose constructor is async.
     /// - Panics if there is a provider that panics on construction.
+    /// - Panics if there is a provider whose constructor returns an error.
     /// - Panics if the provider is not a [`Singleton`](crate::Scope::Singleton) or [`Transient`](crate::Scope::Transient).
     ///
     /// # Example
@@ -709,14 +1525,81 @@ impl Context {
     /// ```
     #[track_caller]
     pub fn resolve_with_name<T: 'static>(&mut self, name: impl Into<Cow<'static, str>>) -> T {
-        match self.inner_resolve(name.into(), Behaviour::CreateThenReturnSingletonOrTransient) {
-            Resolved::SingletonOrTransient(instance) => instance,
-            Resolved::NotFoundProvider(key) => no_provider_panic(key),
-            Resolved::NotSingletonOrTransient(definition) => {
-                not_singleton_or_transient_panic(definition)
-            }
-            Resolved::NotSingletonOrSingleOwner(_) | Resolved::NoReturn => unreachable!(),
-        }
+        unwrap_or_panic(self.try_resolve_with_name(name))
+    }
+
+    /// Returns a [`Singleton`](crate::Scope::Singleton) or [`Transient`](crate::Scope::Transient) instance based on the given type and default name `""`,
+    /// or a [`ResolveError`] if it cannot be resolved.
+    ///
+    /// # Note
+    ///
+    /// Unlike [`Context::resolve`], this method does not panic when no provider is registered,
+    /// when the provider is not a [`Singleton`](crate::Scope::Singleton) or [`Transient`](crate::Scope::Transient),
+    /// when the constructor of the provider is async, or when a circular dependency is detected.
+    ///
+    /// If the constructor of the provider returns an error, see [`try_singleton`](crate::try_singleton),
+    /// this method returns a [`ResolveErrorKind::ConstructorFailed`] error,
+    /// or the [`ResolveError`] returned by the constructor as is.
+    ///
+    /// The constructors generated by the attribute macros, e.g. [`#[Singleton]`](crate::Singleton), propagate the errors of their dependencies,
+    /// but a constructor that calls a panicking method, e.g. [`Context::resolve`], still panics if one of its dependencies cannot be resolved.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rudi::{Context, ResolveErrorKind, Singleton, Transient};
+    ///
+    /// #[derive(Clone, Debug)]
+    /// #[Singleton]
+    /// struct A;
+    ///
+    /// #[derive(Debug)]
+    /// #[Transient(async)]
+    /// struct B;
+    ///
+    /// # fn main() {
+    /// let mut cx = Context::auto_register();
+    ///
+    /// assert!(cx.try_resolve::<A>().is_ok());
+    ///
+    /// let err = cx.try_resolve::<B>().unwrap_err();
+    /// assert!(matches!(err.kind(), ResolveErrorKind::AsyncConstructorInSync(_)));
+    ///
+    /// let err = cx.try_resolve::<i32>().unwrap_err();
+    /// assert!(matches!(err.kind(), ResolveErrorKind::NotFoundProvider(_)));
+    /// # }
+    /// ```
+    pub fn try_resolve<T: 'static>(&mut self) -> Result<T, ResolveError> {
+        self.try_resolve_with_name("")
+    }
+
+    /// Returns a [`Singleton`](crate::Scope::Singleton) or [`Transient`](crate::Scope::Transient) instance based on the given type and name,
+    /// or a [`ResolveError`] if it cannot be resolved.
+    ///
+    /// See [`Context::try_resolve`] for more details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rudi::{Context, ResolveErrorKind, SingleOwner};
+    ///
+    /// #[derive(Debug)]
+    /// #[SingleOwner(name = "a")]
+    /// struct A;
+    ///
+    /// # fn main() {
+    /// let mut cx = Context::auto_register();
+    ///
+    /// let err = cx.try_resolve_with_name::<A>("a").unwrap_err();
+    /// assert!(matches!(err.kind(), ResolveErrorKind::NotSingletonOrTransient(_)));
+    /// assert_eq!(err.key().name, "a");
+    /// # }
+    /// ```
+    pub fn try_resolve_with_name<T: 'static>(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+    ) -> Result<T, ResolveError> {
+        self.try_resolve_by_key(Key::new::<T>(name.into()))
     }
 
     /// Returns an optional [`Singleton`](crate::Scope::Singleton) or [`Transient`](crate::Scope::Transient) instance based on the given type and default name `""`.
@@ -730,6 +1613,7 @@ impl Context {
     ///
     /// - Panics if there is a provider whose constructor is async.
     /// - Panics if there is a provider that panics on construction.
+    /// - Panics if there is a provider whose constructor returns an error.
     ///
     /// # Example
     ///
@@ -761,6 +1645,7 @@ impl Context {
     ///
     /// - Panics if there is a provider whose constructor is async.
     /// - Panics if there is a provider that panics on construction.
+    /// - Panics if there is a provider whose constructor returns an error.
     ///
     /// # Example
     ///
@@ -781,83 +1666,348 @@ impl Context {
         &mut self,
         name: impl Into<Cow<'static, str>>,
     ) -> Option<T> {
-        match self.inner_resolve(name.into(), Behaviour::CreateThenReturnSingletonOrTransient) {
-            Resolved::SingletonOrTransient(instance) => Some(instance),
-            Resolved::NotFoundProvider(_) | Resolved::NotSingletonOrTransient(_) => None,
-            Resolved::NotSingletonOrSingleOwner(_) | Resolved::NoReturn => unreachable!(),
-        }
+        unwrap_or_panic(self.try_resolve_option_with_name(name))
     }
 
-    /// Returns a collection of [`Singleton`](crate::Scope::Singleton) and [`Transient`](crate::Scope::Transient) instances of the given type.
+    /// Returns an optional [`Singleton`](crate::Scope::Singleton) or [`Transient`](crate::Scope::Transient) instance based on the given type and default name `""`,
+    /// or a [`ResolveError`] if it cannot be resolved.
     ///
     /// # Note
     ///
-    /// This method will return a collection of [`Singleton`](crate::Scope::Singleton) and [`Transient`](crate::Scope::Transient),
-    /// if some providers are [`SingleOwner`](crate::Scope::SingleOwner), they will not be contained in the collection.
-    ///
-    /// # Panics
+    /// Same as [`Context::resolve_option`], if no provider is registered for the given type and default name `""`,
+    /// or the provider is not a [`Singleton`](crate::Scope::Singleton) or [`Transient`](crate::Scope::Transient),
+    /// this method will return `Ok(None)`.
     ///
-    /// - Panics if there is a provider whose constructor is async.
-    /// - Panics if there is a provider that panics on construction.
+    /// See [`Context::try_resolve`] for the errors that can be returned.
     ///
     /// # Example
     ///
     /// ```rust
-    /// use rudi::{Context, Transient};
-    ///
-    /// #[Transient(name = "a")]
-    /// fn A() -> i32 {
-    ///     1
-    /// }
+    /// use rudi::{Context, Singleton};
     ///
-    /// #[Transient(name = "b")]
-    /// fn B() -> i32 {
-    ///     2
-    /// }
+    /// #[derive(Clone, Debug)]
+    /// #[Singleton]
+    /// struct A;
     ///
     /// # fn main() {
     /// let mut cx = Context::auto_register();
-    /// assert_eq!(cx.resolve_by_type::<i32>().into_iter().sum::<i32>(), 3);
+    /// assert!(cx.try_resolve_option::<A>().unwrap().is_some());
+    /// assert!(cx.try_resolve_option::<i32>().unwrap().is_none());
     /// # }
     /// ```
-    #[track_caller]
-    pub fn resolve_by_type<T: 'static>(&mut self) -> Vec<T> {
-        self.names::<T>()
-            .into_iter()
-            .filter_map(|name| self.resolve_option_with_name(name))
-            .collect()
-    }
-
-    #[doc(hidden)]
-    #[track_caller]
-    pub fn just_create<T: 'static>(&mut self, name: Cow<'static, str>) {
-        match self.inner_resolve::<T>(name, Behaviour::JustCreateAllScopeForEagerCreate) {
-            Resolved::NoReturn => {}
-            Resolved::NotFoundProvider(key) => no_provider_panic(key),
-            Resolved::SingletonOrTransient(_)
-            | Resolved::NotSingletonOrTransient(_)
-            | Resolved::NotSingletonOrSingleOwner(_) => {
-                unreachable!()
-            }
-        }
+    pub fn try_resolve_option<T: 'static>(&mut self) -> Result<Option<T>, ResolveError> {
+        self.try_resolve_option_with_name("")
     }
 
-    /// Creates a [`Singleton`](crate::Scope::Singleton) or [`SingleOwner`](crate::Scope::SingleOwner) instance based on the given type and default name `""` but does not return it.
+    /// Returns an optional [`Singleton`](crate::Scope::Singleton) or [`Transient`](crate::Scope::Transient) instance based on the given type and name,
+    /// or a [`ResolveError`] if it cannot be resolved.
     ///
-    /// # Panics
-    ///
-    /// - Panics if no provider is registered for the given type and default name `""`.
-    /// - Panics if there is a provider whose constructor is async.
-    /// - Panics if there is a provider that panics on construction.
-    /// - Panics if the provider is not a [`Singleton`](crate::Scope::Singleton) or [`SingleOwner`](crate::Scope::SingleOwner).
+    /// See [`Context::try_resolve_option`] for more details.
     ///
     /// # Example
     ///
     /// ```rust
     /// use rudi::{Context, Singleton};
     ///
-    /// #[derive(Clone)]
-    /// #[Singleton]
+    /// #[derive(Clone, Debug)]
+    /// #[Singleton(name = "a")]
+    /// struct A;
+    ///
+    /// # fn main() {
+    /// let mut cx = Context::auto_register();
+    /// assert!(cx.try_resolve_option_with_name::<A>("a").unwrap().is_some());
+    /// assert!(cx.try_resolve_option_with_name::<A>("b").unwrap().is_none());
+    /// # }
+    /// ```
+    pub fn try_resolve_option_with_name<T: 'static>(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+    ) -> Result<Option<T>, ResolveError> {
+        self.try_resolve_option_by_key(Key::new::<T>(name.into()))
+    }
+
+    /// Returns a [`Singleton`](crate::Scope::Singleton) or [`Transient`](crate::Scope::Transient) instance based on the given type and qualifier `Q`.
+    ///
+    /// A qualifier is a marker type set by the `qualifier` method of the providers, e.g. [`SingletonProvider::qualifier`](crate::SingletonProvider::qualifier),
+    /// or by the `qualifier` argument of the attribute macros, e.g. [`#[Singleton(qualifier = Q)]`](crate::Singleton).
+    /// Unlike a name, a typo in a qualifier is a compile error.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if no provider is registered for the given type and qualifier.
+    /// - Panics if there is a provider whose constructor is async.
+    /// - Panics if there is a provider that panics on construction.
+    /// - Panics if there is a provider whose constructor returns an error.
+    /// - Panics if the provider is not a [`Singleton`](crate::Scope::Singleton) or [`Transient`](crate::Scope::Transient).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rudi::{Context, Singleton};
+    ///
+    /// struct Primary;
+    ///
+    /// struct Replica;
+    ///
+    /// #[Singleton(qualifier = Primary)]
+    /// fn PrimaryUrl() -> String {
+    ///     "postgres://primary".to_string()
+    /// }
+    ///
+    /// #[Singleton(qualifier = Replica)]
+    /// fn ReplicaUrl() -> String {
+    ///     "postgres://replica".to_string()
+    /// }
+    ///
+    /// # fn main() {
+    /// let mut cx = Context::auto_register();
+    /// assert_eq!(cx.resolve_qualified::<String, Primary>(), "postgres://primary");
+    /// assert_eq!(cx.resolve_qualified::<String, Replica>(), "postgres://replica");
+    /// # }
+    /// ```
+    #[track_caller]
+    pub fn resolve_qualified<T: 'static, Q: 'static>(&mut self) -> T {
+        unwrap_or_panic(self.try_resolve_qualified::<T, Q>())
+    }
+
+    /// Returns a [`Singleton`](crate::Scope::Singleton) or [`Transient`](crate::Scope::Transient) instance based on the given type and qualifier `Q`,
+    /// or a [`ResolveError`] if it cannot be resolved.
+    ///
+    /// See [`Context::resolve_qualified`] and [`Context::try_resolve`] for more details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rudi::{modules, providers, singleton, Context, DynProvider, Module};
+    ///
+    /// struct Primary;
+    ///
+    /// struct Replica;
+    ///
+    /// struct MyModule;
+    ///
+    /// impl Module for MyModule {
+    ///     fn providers() -> Vec<DynProvider> {
+    ///         providers![singleton(|_| 5432).qualifier::<Primary>()]
+    ///     }
+    /// }
+    ///
+    /// # fn main() {
+    /// let mut cx = Context::create(modules![MyModule]);
+    /// assert_eq!(cx.try_resolve_qualified::<i32, Primary>().unwrap(), 5432);
+    /// assert!(cx.try_resolve_qualified::<i32, Replica>().is_err());
+    /// assert!(cx.try_resolve::<i32>().is_err());
+    /// # }
+    /// ```
+    pub fn try_resolve_qualified<T: 'static, Q: 'static>(&mut self) -> Result<T, ResolveError> {
+        self.try_resolve_by_key(Key::qualified::<T, Q>())
+    }
+
+    /// Returns an optional [`Singleton`](crate::Scope::Singleton) or [`Transient`](crate::Scope::Transient) instance based on the given type and qualifier `Q`.
+    ///
+    /// See [`Context::resolve_option`] and [`Context::resolve_qualified`] for more details.
+    #[track_caller]
+    pub fn resolve_option_qualified<T: 'static, Q: 'static>(&mut self) -> Option<T> {
+        unwrap_or_panic(self.try_resolve_option_qualified::<T, Q>())
+    }
+
+    /// Returns an optional [`Singleton`](crate::Scope::Singleton) or [`Transient`](crate::Scope::Transient) instance based on the given type and qualifier `Q`,
+    /// or a [`ResolveError`] if it cannot be resolved.
+    ///
+    /// See [`Context::try_resolve_option`] and [`Context::resolve_qualified`] for more details.
+    pub fn try_resolve_option_qualified<T: 'static, Q: 'static>(
+        &mut self,
+    ) -> Result<Option<T>, ResolveError> {
+        self.try_resolve_option_by_key(Key::qualified::<T, Q>())
+    }
+
+    /// Returns a collection of [`Singleton`](crate::Scope::Singleton) and [`Transient`](crate::Scope::Transient) instances of the given type.
+    ///
+    /// # Note
+    ///
+    /// This method will return a collection of [`Singleton`](crate::Scope::Singleton) and [`Transient`](crate::Scope::Transient),
+    /// if some providers are [`SingleOwner`](crate::Scope::SingleOwner), they will not be contained in the collection.
+    ///
+    /// The instances are sorted by the [`order`](crate::SingletonProvider::order) of their providers,
+    /// then by the registration order of the providers, the providers of the ancestors first.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if there is a provider whose constructor is async.
+    /// - Panics if there is a provider that panics on construction.
+    /// - Panics if there is a provider whose constructor returns an error.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rudi::{Context, Transient};
+    ///
+    /// #[Transient(name = "a")]
+    /// fn A() -> i32 {
+    ///     1
+    /// }
+    ///
+    /// #[Transient(name = "b")]
+    /// fn B() -> i32 {
+    ///     2
+    /// }
+    ///
+    /// # fn main() {
+    /// let mut cx = Context::auto_register();
+    /// assert_eq!(cx.resolve_by_type::<i32>().into_iter().sum::<i32>(), 3);
+    /// # }
+    /// ```
+    #[track_caller]
+    pub fn resolve_by_type<T: 'static>(&mut self) -> Vec<T> {
+        unwrap_or_panic(self.try_resolve_by_type())
+    }
+
+    /// Returns a collection of [`Singleton`](crate::Scope::Singleton) and [`Transient`](crate::Scope::Transient) instances of the given type,
+    /// or a [`ResolveError`] if one of them cannot be resolved.
+    ///
+    /// See [`Context::resolve_by_type`] and [`Context::try_resolve`] for more details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rudi::{Context, Transient};
+    ///
+    /// #[Transient(name = "a")]
+    /// fn A() -> i32 {
+    ///     1
+    /// }
+    ///
+    /// #[Transient(name = "b")]
+    /// fn B() -> i32 {
+    ///     2
+    /// }
+    ///
+    /// # fn main() {
+    /// let mut cx = Context::auto_register();
+    /// assert_eq!(cx.try_resolve_by_type::<i32>().unwrap().into_iter().sum::<i32>(), 3);
+    /// # }
+    /// ```
+    pub fn try_resolve_by_type<T: 'static>(&mut self) -> Result<Vec<T>, ResolveError> {
+        let keys = self.keys::<T>();
+
+        let mut instances = Vec::with_capacity(keys.len());
+
+        for key in keys {
+            if let Some(instance) = self.try_resolve_option_by_key(key)? {
+                instances.push(instance);
+            }
+        }
+
+        Ok(instances)
+    }
+
+    /// Returns a map from the names of the providers to [`Singleton`](crate::Scope::Singleton) and [`Transient`](crate::Scope::Transient) instances of the given type.
+    ///
+    /// Same as [`Context::resolve_by_type`], but keeps the name of the provider of each instance,
+    /// the instances of the [qualified](Context::resolve_qualified) providers are keyed by the type name of their qualifier.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if there is a provider whose constructor is async.
+    /// - Panics if there is a provider that panics on construction.
+    /// - Panics if there is a provider whose constructor returns an error.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rudi::{Context, Transient};
+    ///
+    /// #[Transient(name = "a")]
+    /// fn A() -> i32 {
+    ///     1
+    /// }
+    ///
+    /// #[Transient(name = "b")]
+    /// fn B() -> i32 {
+    ///     2
+    /// }
+    ///
+    /// # fn main() {
+    /// let mut cx = Context::auto_register();
+    ///
+    /// let map = cx.resolve_map_by_type::<i32>();
+    /// assert_eq!(map["a"], 1);
+    /// assert_eq!(map["b"], 2);
+    /// # }
+    /// ```
+    #[track_caller]
+    pub fn resolve_map_by_type<T: 'static>(&mut self) -> HashMap<Cow<'static, str>, T> {
+        unwrap_or_panic(self.try_resolve_map_by_type())
+    }
+
+    /// Returns a map from the names of the providers to [`Singleton`](crate::Scope::Singleton) and [`Transient`](crate::Scope::Transient) instances of the given type,
+    /// or a [`ResolveError`] if one of them cannot be resolved.
+    ///
+    /// See [`Context::resolve_map_by_type`] and [`Context::try_resolve`] for more details.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rudi::{Context, Transient};
+    ///
+    /// #[Transient(name = "a")]
+    /// fn A() -> i32 {
+    ///     1
+    /// }
+    ///
+    /// # fn main() {
+    /// let mut cx = Context::auto_register();
+    /// assert_eq!(cx.try_resolve_map_by_type::<i32>().unwrap()["a"], 1);
+    /// # }
+    /// ```
+    pub fn try_resolve_map_by_type<T: 'static>(
+        &mut self,
+    ) -> Result<HashMap<Cow<'static, str>, T>, ResolveError> {
+        let keys = self.map_keys::<T>();
+
+        let mut instances = HashMap::with_capacity(keys.len());
+
+        for (name, key) in keys {
+            if let Some(instance) = self.try_resolve_option_by_key(key)? {
+                instances.insert(name, instance);
+            }
+        }
+
+        Ok(instances)
+    }
+
+    #[doc(hidden)]
+    pub fn just_create<T: 'static>(&mut self, key: Key) -> Result<(), ResolveError> {
+        match self.inner_resolve::<T>(key, Behaviour::JustCreateAllScopeForEagerCreate)? {
+            Resolved::NoReturn => Ok(()),
+            Resolved::NotFoundProvider(key) => {
+                Err(self.error(ResolveErrorKind::NotFoundProvider(key)))
+            }
+            Resolved::SingletonOrTransient(_)
+            | Resolved::NotSingletonOrTransient(_)
+            | Resolved::NotSingletonOrSingleOwner(_) => {
+                unreachable!()
+            }
+        }
+    }
+
+    /// Creates a [`Singleton`](crate::Scope::Singleton) or [`SingleOwner`](crate::Scope::SingleOwner) instance based on the given type and default name `""` but does not return it.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if no provider is registered for the given type and default name `""`.
+    /// - Panics if there is a provider whose constructor is async.
+    /// - Panics if there is a provider that panics on construction.
+    /// - Panics if there is a provider whose constructor returns an error.
+    /// - Panics if the provider is not a [`Singleton`](crate::Scope::Singleton) or [`SingleOwner`](crate::Scope::SingleOwner).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rudi::{Context, Singleton};
+    ///
+    /// #[derive(Clone)]
+    /// #[Singleton]
     /// struct A;
     ///
     /// # fn main() {
@@ -879,6 +2029,7 @@ impl Context {
     /// - Panics if no provider is registered for the given type and name.
     /// - Panics if there is a provider whose constructor is async.
     /// - Panics if there is a provider that panics on construction.
+    /// - Panics if there is a provider whose constructor returns an error.
     /// - Panics if the provider is not a [`Singleton`](crate::Scope::Singleton) or [`SingleOwner`](crate::Scope::SingleOwner).
     ///
     /// # Example
@@ -899,16 +2050,15 @@ impl Context {
     /// ```
     #[track_caller]
     pub fn just_create_single_with_name<T: 'static>(&mut self, name: impl Into<Cow<'static, str>>) {
-        match self.inner_resolve::<T>(name.into(), Behaviour::JustCreateSingletonOrSingleOwner) {
-            Resolved::NoReturn => {}
-            Resolved::NotFoundProvider(key) => no_provider_panic(key),
-            Resolved::NotSingletonOrSingleOwner(definition) => {
-                not_singleton_or_single_owner_panic(definition)
-            }
-            Resolved::SingletonOrTransient(_) | Resolved::NotSingletonOrTransient(_) => {
-                unreachable!()
-            }
-        }
+        unwrap_or_panic(self.just_create_single_with_name_checked::<T>(name))
+    }
+
+    #[doc(hidden)]
+    pub fn just_create_single_with_name_checked<T: 'static>(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+    ) -> Result<(), ResolveError> {
+        self.just_create_single_by_key_checked::<T>(Key::new::<T>(name.into()))
     }
 
     /// Try to create a [`Singleton`](crate::Scope::Singleton) or [`SingleOwner`](crate::Scope::SingleOwner) instance based on the given type and default name `""` but does not return it.
@@ -922,6 +2072,7 @@ impl Context {
     ///
     /// - Panics if there is a provider whose constructor is async.
     /// - Panics if there is a provider that panics on construction.
+    /// - Panics if there is a provider whose constructor returns an error.
     ///
     /// # Example
     ///
@@ -964,6 +2115,7 @@ impl Context {
     ///
     /// - Panics if there is a provider whose constructor is async.
     /// - Panics if there is a provider that panics on construction.
+    /// - Panics if there is a provider whose constructor returns an error.
     ///
     /// # Example
     ///
@@ -995,13 +2147,29 @@ impl Context {
         &mut self,
         name: impl Into<Cow<'static, str>>,
     ) -> bool {
-        match self.inner_resolve::<T>(name.into(), Behaviour::JustCreateSingletonOrSingleOwner) {
-            Resolved::NoReturn => true,
-            Resolved::NotFoundProvider(_) | Resolved::NotSingletonOrSingleOwner(_) => false,
-            Resolved::SingletonOrTransient(_) | Resolved::NotSingletonOrTransient(_) => {
-                unreachable!()
-            }
-        }
+        unwrap_or_panic(self.try_just_create_single_with_name_checked::<T>(name))
+    }
+
+    #[doc(hidden)]
+    pub fn try_just_create_single_with_name_checked<T: 'static>(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+    ) -> Result<bool, ResolveError> {
+        self.try_just_create_single_by_key_checked::<T>(Key::new::<T>(name.into()))
+    }
+
+    #[doc(hidden)]
+    pub fn just_create_single_qualified_checked<T: 'static, Q: 'static>(
+        &mut self,
+    ) -> Result<(), ResolveError> {
+        self.just_create_single_by_key_checked::<T>(Key::qualified::<T, Q>())
+    }
+
+    #[doc(hidden)]
+    pub fn try_just_create_single_qualified_checked<T: 'static, Q: 'static>(
+        &mut self,
+    ) -> Result<bool, ResolveError> {
+        self.try_just_create_single_by_key_checked::<T>(Key::qualified::<T, Q>())
     }
 
     /// Try to create [`Singleton`](crate::Scope::Singleton) and [`SingleOwner`](crate::Scope::SingleOwner) instances based on the given type but does not return them.
@@ -1015,6 +2183,7 @@ impl Context {
     ///
     /// - Panics if there is a provider whose constructor is async.
     /// - Panics if there is a provider that panics on construction.
+    /// - Panics if there is a provider whose constructor returns an error.
     ///
     /// # Example
     ///
@@ -1046,9 +2215,16 @@ impl Context {
     /// ```
     #[track_caller]
     pub fn try_just_create_singles_by_type<T: 'static>(&mut self) -> Vec<bool> {
-        self.names::<T>()
+        unwrap_or_panic(self.try_just_create_singles_by_type_checked::<T>())
+    }
+
+    #[doc(hidden)]
+    pub fn try_just_create_singles_by_type_checked<T: 'static>(
+        &mut self,
+    ) -> Result<Vec<bool>, ResolveError> {
+        self.keys::<T>()
             .into_iter()
-            .map(|name| self.try_just_create_single_with_name::<T>(name))
+            .map(|key| self.try_just_create_single_by_key_checked::<T>(key))
             .collect()
     }
 
@@ -1058,6 +2234,7 @@ impl Context {
     ///
     /// - Panics if no provider is registered for the given type and default name `""`.
     /// - Panics if there is a provider that panics on construction.
+    /// - Panics if there is a provider whose constructor returns an error.
     /// - Panics if the provider is not a [`Singleton`](crate::Scope::Singleton) or [`Transient`](crate::Scope::Transient).
     ///
     /// # Example
@@ -1090,6 +2267,7 @@ impl Context {
     ///
     /// - Panics if no provider is registered for the given type and name.
     /// - Panics if there is a provider that panics on construction.
+    /// - Panics if there is a provider whose constructor returns an error.
     /// - Panics if the provider is not a [`Singleton`](crate::Scope::Singleton) or [`Transient`](crate::Scope::Transient).
     ///
     /// # Example
@@ -1116,24 +2294,69 @@ impl Context {
         &mut self,
         name: impl Into<Cow<'static, str>>,
     ) -> T {
-        match self
-            .inner_resolve_async(name.into(), Behaviour::CreateThenReturnSingletonOrTransient)
-            .await
-        {
-            Resolved::SingletonOrTransient(instance) => instance,
-            Resolved::NotFoundProvider(key) => no_provider_panic(key),
-            Resolved::NotSingletonOrTransient(definition) => {
-                not_singleton_or_transient_panic(definition)
-            }
-            Resolved::NotSingletonOrSingleOwner(_) | Resolved::NoReturn => unreachable!(),
-        }
+        unwrap_or_panic(self.try_resolve_with_name_async(name).await)
     }
 
-    /// Async version of [`Context::resolve_option`].
+    /// Async version of [`Context::try_resolve`].
     ///
-    /// # Panics
+    /// # Example
     ///
-    /// - Panics if there is a provider that panics on construction.
+    /// ```rust
+    /// use rudi::{Context, ResolveErrorKind, Transient};
+    ///
+    /// #[Transient]
+    /// async fn Number() -> i32 {
+    ///     1
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut cx = Context::auto_register();
+    ///
+    ///     assert_eq!(cx.try_resolve_async::<i32>().await.unwrap(), 1);
+    ///
+    ///     let err = cx.try_resolve_async::<i64>().await.unwrap_err();
+    ///     assert!(matches!(err.kind(), ResolveErrorKind::NotFoundProvider(_)));
+    /// }
+    /// ```
+    pub async fn try_resolve_async<T: 'static>(&mut self) -> Result<T, ResolveError> {
+        self.try_resolve_with_name_async("").await
+    }
+
+    /// Async version of [`Context::try_resolve_with_name`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rudi::{Context, Transient};
+    ///
+    /// #[Transient(name = "a")]
+    /// async fn Number() -> i32 {
+    ///     1
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut cx = Context::auto_register();
+    ///
+    ///     assert_eq!(cx.try_resolve_with_name_async::<i32>("a").await.unwrap(), 1);
+    ///     assert!(cx.try_resolve_with_name_async::<i32>("b").await.is_err());
+    /// }
+    /// ```
+    pub async fn try_resolve_with_name_async<T: 'static>(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+    ) -> Result<T, ResolveError> {
+        self.try_resolve_by_key_async(Key::new::<T>(name.into()))
+            .await
+    }
+
+    /// Async version of [`Context::resolve_option`].
+    ///
+    /// # Panics
+    ///
+    /// - Panics if there is a provider that panics on construction.
+    /// - Panics if there is a provider whose constructor returns an error.
     ///
     /// # Example
     ///
@@ -1164,6 +2387,7 @@ impl Context {
     /// # Panics
     ///
     /// - Panics if there is a provider that panics on construction.
+    /// - Panics if there is a provider whose constructor returns an error.
     ///
     /// # Example
     ///
@@ -1189,14 +2413,89 @@ impl Context {
         &mut self,
         name: impl Into<Cow<'static, str>>,
     ) -> Option<T> {
-        match self
-            .inner_resolve_async(name.into(), Behaviour::CreateThenReturnSingletonOrTransient)
+        unwrap_or_panic(self.try_resolve_option_with_name_async(name).await)
+    }
+
+    /// Async version of [`Context::try_resolve_option`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rudi::{Context, Transient};
+    ///
+    /// #[Transient]
+    /// async fn Number() -> i32 {
+    ///     1
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut cx = Context::auto_register();
+    ///     assert_eq!(cx.try_resolve_option_async::<i32>().await.unwrap(), Some(1));
+    ///     assert_eq!(cx.try_resolve_option_async::<i64>().await.unwrap(), None);
+    /// }
+    /// ```
+    pub async fn try_resolve_option_async<T: 'static>(
+        &mut self,
+    ) -> Result<Option<T>, ResolveError> {
+        self.try_resolve_option_with_name_async("").await
+    }
+
+    /// Async version of [`Context::try_resolve_option_with_name`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rudi::{Context, Transient};
+    ///
+    /// #[Transient(name = "a")]
+    /// async fn Number() -> i32 {
+    ///     1
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut cx = Context::auto_register();
+    ///     assert_eq!(
+    ///         cx.try_resolve_option_with_name_async::<i32>("a")
+    ///             .await
+    ///             .unwrap(),
+    ///         Some(1)
+    ///     );
+    /// }
+    /// ```
+    pub async fn try_resolve_option_with_name_async<T: 'static>(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+    ) -> Result<Option<T>, ResolveError> {
+        self.try_resolve_option_by_key_async(Key::new::<T>(name.into()))
+            .await
+    }
+
+    /// Async version of [`Context::resolve_qualified`].
+    pub async fn resolve_qualified_async<T: 'static, Q: 'static>(&mut self) -> T {
+        unwrap_or_panic(self.try_resolve_qualified_async::<T, Q>().await)
+    }
+
+    /// Async version of [`Context::try_resolve_qualified`].
+    pub async fn try_resolve_qualified_async<T: 'static, Q: 'static>(
+        &mut self,
+    ) -> Result<T, ResolveError> {
+        self.try_resolve_by_key_async(Key::qualified::<T, Q>())
+            .await
+    }
+
+    /// Async version of [`Context::resolve_option_qualified`].
+    pub async fn resolve_option_qualified_async<T: 'static, Q: 'static>(&mut self) -> Option<T> {
+        unwrap_or_panic(self.try_resolve_option_qualified_async::<T, Q>().await)
+    }
+
+    /// Async version of [`Context::try_resolve_option_qualified`].
+    pub async fn try_resolve_option_qualified_async<T: 'static, Q: 'static>(
+        &mut self,
+    ) -> Result<Option<T>, ResolveError> {
+        self.try_resolve_option_by_key_async(Key::qualified::<T, Q>())
             .await
-        {
-            Resolved::SingletonOrTransient(instance) => Some(instance),
-            Resolved::NotFoundProvider(_) | Resolved::NotSingletonOrTransient(_) => None,
-            Resolved::NotSingletonOrSingleOwner(_) | Resolved::NoReturn => unreachable!(),
-        }
     }
 
     /// Async version of [`Context::resolve_by_type`].
@@ -1204,6 +2503,7 @@ impl Context {
     /// # Panics
     ///
     /// - Panics if there is a provider that panics on construction.
+    /// - Panics if there is a provider whose constructor returns an error.
     ///
     /// # Example
     ///
@@ -1233,27 +2533,135 @@ impl Context {
     /// }
     /// ```
     pub async fn resolve_by_type_async<T: 'static>(&mut self) -> Vec<T> {
-        let names = self.names::<T>();
+        unwrap_or_panic(self.try_resolve_by_type_async().await)
+    }
+
+    /// Async version of [`Context::try_resolve_by_type`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rudi::{Context, Transient};
+    ///
+    /// #[Transient(name = "a")]
+    /// async fn A() -> i32 {
+    ///     1
+    /// }
+    ///
+    /// #[Transient(name = "b")]
+    /// async fn B() -> i32 {
+    ///     2
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut cx = Context::auto_register();
+    ///     assert_eq!(
+    ///         cx.try_resolve_by_type_async::<i32>()
+    ///             .await
+    ///             .unwrap()
+    ///             .into_iter()
+    ///             .sum::<i32>(),
+    ///         3
+    ///     );
+    /// }
+    /// ```
+    pub async fn try_resolve_by_type_async<T: 'static>(&mut self) -> Result<Vec<T>, ResolveError> {
+        let keys = self.keys::<T>();
 
-        let mut instances = Vec::with_capacity(names.len());
+        let mut instances = Vec::with_capacity(keys.len());
 
-        for name in names {
-            if let Some(instance) = self.resolve_option_with_name_async(name).await {
+        for key in keys {
+            if let Some(instance) = self.try_resolve_option_by_key_async(key).await? {
                 instances.push(instance);
             }
         }
 
-        instances
+        Ok(instances)
+    }
+
+    /// Async version of [`Context::resolve_map_by_type`].
+    ///
+    /// # Panics
+    ///
+    /// - Panics if there is a provider that panics on construction.
+    /// - Panics if there is a provider whose constructor returns an error.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rudi::{Context, Transient};
+    ///
+    /// #[Transient(name = "a")]
+    /// async fn A() -> i32 {
+    ///     1
+    /// }
+    ///
+    /// #[Transient(name = "b")]
+    /// async fn B() -> i32 {
+    ///     2
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut cx = Context::auto_register();
+    ///
+    ///     let map = cx.resolve_map_by_type_async::<i32>().await;
+    ///     assert_eq!(map["a"], 1);
+    ///     assert_eq!(map["b"], 2);
+    /// }
+    /// ```
+    pub async fn resolve_map_by_type_async<T: 'static>(&mut self) -> HashMap<Cow<'static, str>, T> {
+        unwrap_or_panic(self.try_resolve_map_by_type_async().await)
+    }
+
+    /// Async version of [`Context::try_resolve_map_by_type`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rudi::{Context, Transient};
+    ///
+    /// #[Transient(name = "a")]
+    /// async fn A() -> i32 {
+    ///     1
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut cx = Context::auto_register();
+    ///     assert_eq!(
+    ///         cx.try_resolve_map_by_type_async::<i32>().await.unwrap()["a"],
+    ///         1
+    ///     );
+    /// }
+    /// ```
+    pub async fn try_resolve_map_by_type_async<T: 'static>(
+        &mut self,
+    ) -> Result<HashMap<Cow<'static, str>, T>, ResolveError> {
+        let keys = self.map_keys::<T>();
+
+        let mut instances = HashMap::with_capacity(keys.len());
+
+        for (name, key) in keys {
+            if let Some(instance) = self.try_resolve_option_by_key_async(key).await? {
+                instances.insert(name, instance);
+            }
+        }
+
+        Ok(instances)
     }
 
     #[doc(hidden)]
-    pub async fn just_create_async<T: 'static>(&mut self, name: Cow<'static, str>) {
+    pub async fn just_create_async<T: 'static>(&mut self, key: Key) -> Result<(), ResolveError> {
         match self
-            .inner_resolve_async::<T>(name, Behaviour::JustCreateAllScopeForEagerCreate)
-            .await
+            .inner_resolve_async::<T>(key, Behaviour::JustCreateAllScopeForEagerCreate)
+            .await?
         {
-            Resolved::NoReturn => {}
-            Resolved::NotFoundProvider(key) => no_provider_panic(key),
+            Resolved::NoReturn => Ok(()),
+            Resolved::NotFoundProvider(key) => {
+                Err(self.error(ResolveErrorKind::NotFoundProvider(key)))
+            }
             Resolved::SingletonOrTransient(_)
             | Resolved::NotSingletonOrTransient(_)
             | Resolved::NotSingletonOrSingleOwner(_) => {
@@ -1268,6 +2676,7 @@ impl Context {
     ///
     /// - Panics if no provider is registered for the given type and default name `""`.
     /// - Panics if there is a provider that panics on construction.
+    /// - Panics if there is a provider whose constructor returns an error.
     /// - Panics if the provider is not a [`Singleton`](crate::Scope::Singleton) or [`SingleOwner`](crate::Scope::SingleOwner).
     ///
     /// # Example
@@ -1297,6 +2706,7 @@ impl Context {
     ///
     /// - Panics if no provider is registered for the given type and name.
     /// - Panics if there is a provider that panics on construction.
+    /// - Panics if there is a provider whose constructor returns an error.
     /// - Panics if the provider is not a [`Singleton`](crate::Scope::Singleton) or [`SingleOwner`](crate::Scope::SingleOwner).
     ///
     /// # Example
@@ -1320,19 +2730,19 @@ impl Context {
         &mut self,
         name: impl Into<Cow<'static, str>>,
     ) {
-        match self
-            .inner_resolve_async::<T>(name.into(), Behaviour::JustCreateSingletonOrSingleOwner)
+        unwrap_or_panic(
+            self.just_create_single_with_name_checked_async::<T>(name)
+                .await,
+        )
+    }
+
+    #[doc(hidden)]
+    pub async fn just_create_single_with_name_checked_async<T: 'static>(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+    ) -> Result<(), ResolveError> {
+        self.just_create_single_by_key_checked_async::<T>(Key::new::<T>(name.into()))
             .await
-        {
-            Resolved::NoReturn => {}
-            Resolved::NotFoundProvider(key) => no_provider_panic(key),
-            Resolved::NotSingletonOrSingleOwner(definition) => {
-                not_singleton_or_single_owner_panic(definition)
-            }
-            Resolved::SingletonOrTransient(_) | Resolved::NotSingletonOrTransient(_) => {
-                unreachable!()
-            }
-        }
     }
 
     /// Async version of [`Context::try_just_create_single`].
@@ -1340,6 +2750,7 @@ impl Context {
     /// # Panics
     ///
     /// - Panics if there is a provider that panics on construction.
+    /// - Panics if there is a provider whose constructor returns an error.
     ///
     /// # Example
     ///
@@ -1376,6 +2787,7 @@ impl Context {
     /// # Panics
     ///
     /// - Panics if there is a provider that panics on construction.
+    /// - Panics if there is a provider whose constructor returns an error.
     ///
     /// # Example
     ///
@@ -1407,16 +2819,35 @@ impl Context {
         &mut self,
         name: impl Into<Cow<'static, str>>,
     ) -> bool {
-        match self
-            .inner_resolve_async::<T>(name.into(), Behaviour::JustCreateSingletonOrSingleOwner)
+        unwrap_or_panic(
+            self.try_just_create_single_with_name_checked_async::<T>(name)
+                .await,
+        )
+    }
+
+    #[doc(hidden)]
+    pub async fn try_just_create_single_with_name_checked_async<T: 'static>(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+    ) -> Result<bool, ResolveError> {
+        self.try_just_create_single_by_key_checked_async::<T>(Key::new::<T>(name.into()))
+            .await
+    }
+
+    #[doc(hidden)]
+    pub async fn just_create_single_qualified_checked_async<T: 'static, Q: 'static>(
+        &mut self,
+    ) -> Result<(), ResolveError> {
+        self.just_create_single_by_key_checked_async::<T>(Key::qualified::<T, Q>())
+            .await
+    }
+
+    #[doc(hidden)]
+    pub async fn try_just_create_single_qualified_checked_async<T: 'static, Q: 'static>(
+        &mut self,
+    ) -> Result<bool, ResolveError> {
+        self.try_just_create_single_by_key_checked_async::<T>(Key::qualified::<T, Q>())
             .await
-        {
-            Resolved::NoReturn => true,
-            Resolved::NotFoundProvider(_) | Resolved::NotSingletonOrSingleOwner(_) => false,
-            Resolved::SingletonOrTransient(_) | Resolved::NotSingletonOrTransient(_) => {
-                unreachable!()
-            }
-        }
     }
 
     /// Async version of [`Context::try_just_create_singles_by_type`].
@@ -1424,6 +2855,7 @@ impl Context {
     /// # Panics
     ///
     /// - Panics if there is a provider that panics on construction.
+    /// - Panics if there is a provider whose constructor returns an error.
     ///
     /// # Example
     ///
@@ -1455,15 +2887,27 @@ impl Context {
     /// }
     /// ```
     pub async fn try_just_create_singles_by_type_async<T: 'static>(&mut self) -> Vec<bool> {
-        let names = self.names::<T>();
-        let mut results = Vec::with_capacity(names.len());
+        unwrap_or_panic(
+            self.try_just_create_singles_by_type_checked_async::<T>()
+                .await,
+        )
+    }
 
-        for name in names {
-            let result = self.try_just_create_single_with_name_async::<T>(name).await;
+    #[doc(hidden)]
+    pub async fn try_just_create_singles_by_type_checked_async<T: 'static>(
+        &mut self,
+    ) -> Result<Vec<bool>, ResolveError> {
+        let keys = self.keys::<T>();
+        let mut results = Vec::with_capacity(keys.len());
+
+        for key in keys {
+            let result = self
+                .try_just_create_single_by_key_checked_async::<T>(key)
+                .await?;
             results.push(result);
         }
 
-        results
+        Ok(results)
     }
 
     /// Returns true if the context contains a provider for the specified type and default name `""`.
@@ -1506,8 +2950,8 @@ impl Context {
         &self,
         name: impl Into<Cow<'static, str>>,
     ) -> bool {
-        let key = Key::new::<T>(name.into());
-        self.provider_registry.contains(&key)
+        let key = self.key(Key::new::<T>(name.into()));
+        self.find_provider::<T>(&key).is_some()
     }
 
     /// Returns a reference to an provider based on the given type and default name `""`.
@@ -1548,8 +2992,8 @@ impl Context {
         &self,
         name: impl Into<Cow<'static, str>>,
     ) -> Option<&Provider<T>> {
-        let key = Key::new::<T>(name.into());
-        self.provider_registry.get(&key)
+        let key = self.key(Key::new::<T>(name.into()));
+        self.find_provider(&key)
     }
 
     /// Returns a collection of references to providers based on the given type.
@@ -1575,12 +3019,9 @@ impl Context {
     /// }
     /// ```
     pub fn get_providers_by_type<T: 'static>(&self) -> Vec<&Provider<T>> {
-        let type_id = TypeId::of::<T>();
-
-        self.provider_registry()
-            .iter()
-            .filter(|(key, _)| key.ty.id == type_id)
-            .filter_map(|(_, provider)| provider.as_provider())
+        self.keys::<T>()
+            .into_iter()
+            .filter_map(|key| self.find_provider(&key))
             .collect()
     }
 
@@ -1624,8 +3065,8 @@ impl Context {
         &self,
         name: impl Into<Cow<'static, str>>,
     ) -> bool {
-        let key = Key::new::<T>(name.into());
-        self.single_registry.contains(&key)
+        let key = self.key(Key::new::<T>(name.into()));
+        self.find_single::<T>(&key).is_some()
     }
 
     /// Returns a reference to a [`Singleton`](crate::Scope::Singleton) or [`SingleOwner`](crate::Scope::SingleOwner) instance based on the given type and default name `""`.
@@ -1677,33 +3118,40 @@ impl Context {
     /// ```
     #[track_caller]
     pub fn get_single_with_name<T: 'static>(&self, name: impl Into<Cow<'static, str>>) -> &T {
-        let key = Key::new::<T>(name.into());
-        self.single_registry
-            .get_ref(&key)
+        let key = self.key(Key::new::<T>(name.into()));
+        self.find_single(&key)
+            .map(Single::get_ref)
             .unwrap_or_else(|| panic!("no instance registered for: {:?}", key))
     }
 
-    /// Returns an optional reference to a [`Singleton`](crate::Scope::Singleton) or [`SingleOwner`](crate::Scope::SingleOwner) instance based on the given type and default name `""`.
+    /// Returns a reference to a [`Singleton`](crate::Scope::Singleton) or [`SingleOwner`](crate::Scope::SingleOwner) instance based on the given type and default name `""`,
+    /// or a [`ResolveError`] if no instance is stored.
     ///
     /// # Example
     ///
     /// ```rust
-    /// use rudi::{Context, Singleton};
+    /// use rudi::{Context, ResolveErrorKind, Singleton};
     ///
     /// #[derive(Clone, Debug)]
-    /// #[Singleton(eager_create)]
+    /// #[Singleton]
     /// struct A;
     ///
     /// # fn main() {
-    /// let cx = Context::auto_register();
-    /// assert!(cx.get_single_option::<A>().is_some());
+    /// let mut cx = Context::auto_register();
+    ///
+    /// let err = cx.try_get_single::<A>().unwrap_err();
+    /// assert!(matches!(err.kind(), ResolveErrorKind::NotFoundSingle(_)));
+    ///
+    /// cx.just_create_single::<A>();
+    /// assert!(cx.try_get_single::<A>().is_ok());
     /// # }
     /// ```
-    pub fn get_single_option<T: 'static>(&self) -> Option<&T> {
-        self.get_single_option_with_name("")
+    pub fn try_get_single<T: 'static>(&self) -> Result<&T, ResolveError> {
+        self.try_get_single_with_name("")
     }
 
-    /// Returns an optional reference to a [`Singleton`](crate::Scope::Singleton) or [`SingleOwner`](crate::Scope::SingleOwner) instance based on the given type and name.
+    /// Returns a reference to a [`Singleton`](crate::Scope::Singleton) or [`SingleOwner`](crate::Scope::SingleOwner) instance based on the given type and name,
+    /// or a [`ResolveError`] if no instance is stored.
     ///
     /// # Example
     ///
@@ -1716,31 +3164,119 @@ impl Context {
     ///
     /// # fn main() {
     /// let cx = Context::auto_register();
-    /// assert!(cx.get_single_option_with_name::<A>("a").is_some());
+    /// assert!(cx.try_get_single_with_name::<A>("a").is_ok());
+    /// assert!(cx.try_get_single_with_name::<A>("b").is_err());
     /// # }
     /// ```
-    pub fn get_single_option_with_name<T: 'static>(
+    pub fn try_get_single_with_name<T: 'static>(
         &self,
         name: impl Into<Cow<'static, str>>,
-    ) -> Option<&T> {
-        let key = Key::new::<T>(name.into());
-        self.single_registry.get_ref(&key)
+    ) -> Result<&T, ResolveError> {
+        let key = self.key(Key::new::<T>(name.into()));
+
+        match self.find_single(&key) {
+            Some(single) => Ok(single.get_ref()),
+            None => Err(self.error(ResolveErrorKind::NotFoundSingle(key))),
+        }
     }
 
-    /// Returns a collection of references to [`Singleton`](crate::Scope::Singleton) and [`SingleOwner`](crate::Scope::SingleOwner) instances based on the given type.
+    /// Returns an optional reference to a [`Singleton`](crate::Scope::Singleton) or [`SingleOwner`](crate::Scope::SingleOwner) instance based on the given type and default name `""`.
     ///
     /// # Example
     ///
     /// ```rust
     /// use rudi::{Context, Singleton};
     ///
-    /// #[Singleton(eager_create, name = "a")]
-    /// fn A() -> i32 {
-    ///     1
-    /// }
+    /// #[derive(Clone, Debug)]
+    /// #[Singleton(eager_create)]
+    /// struct A;
     ///
-    /// #[Singleton(eager_create, name = "b")]
-    /// fn B() -> i32 {
+    /// # fn main() {
+    /// let cx = Context::auto_register();
+    /// assert!(cx.get_single_option::<A>().is_some());
+    /// # }
+    /// ```
+    pub fn get_single_option<T: 'static>(&self) -> Option<&T> {
+        self.get_single_option_with_name("")
+    }
+
+    /// Returns an optional reference to a [`Singleton`](crate::Scope::Singleton) or [`SingleOwner`](crate::Scope::SingleOwner) instance based on the given type and name.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rudi::{Context, Singleton};
+    ///
+    /// #[derive(Clone, Debug)]
+    /// #[Singleton(eager_create, name = "a")]
+    /// struct A;
+    ///
+    /// # fn main() {
+    /// let cx = Context::auto_register();
+    /// assert!(cx.get_single_option_with_name::<A>("a").is_some());
+    /// # }
+    /// ```
+    pub fn get_single_option_with_name<T: 'static>(
+        &self,
+        name: impl Into<Cow<'static, str>>,
+    ) -> Option<&T> {
+        let key = self.key(Key::new::<T>(name.into()));
+        self.find_single(&key).map(Single::get_ref)
+    }
+
+    /// Returns a reference to a [`Singleton`](crate::Scope::Singleton) or [`SingleOwner`](crate::Scope::SingleOwner) instance based on the given type and qualifier `Q`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if no instance is stored for the given type and qualifier.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rudi::{Context, Singleton};
+    ///
+    /// struct Primary;
+    ///
+    /// #[Singleton(eager_create, qualifier = Primary)]
+    /// fn Url() -> String {
+    ///     "postgres://primary".to_string()
+    /// }
+    ///
+    /// # fn main() {
+    /// let cx = Context::auto_register();
+    /// assert_eq!(cx.get_single_qualified::<String, Primary>(), "postgres://primary");
+    /// # }
+    /// ```
+    #[track_caller]
+    pub fn get_single_qualified<T: 'static, Q: 'static>(&self) -> &T {
+        let key = Key::qualified::<T, Q>();
+        self.find_single(&key)
+            .map(Single::get_ref)
+            .unwrap_or_else(|| panic!("no instance registered for: {:?}", key))
+    }
+
+    /// Returns an optional reference to a [`Singleton`](crate::Scope::Singleton) or [`SingleOwner`](crate::Scope::SingleOwner) instance based on the given type and qualifier `Q`.
+    pub fn get_single_option_qualified<T: 'static, Q: 'static>(&self) -> Option<&T> {
+        self.find_single(&Key::qualified::<T, Q>())
+            .map(Single::get_ref)
+    }
+
+    /// Returns a collection of references to [`Singleton`](crate::Scope::Singleton) and [`SingleOwner`](crate::Scope::SingleOwner) instances based on the given type.
+    ///
+    /// The instances are sorted in the same order as [`Context::resolve_by_type`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rudi::{Context, Singleton};
+    ///
+    /// #[Singleton(eager_create, name = "a")]
+    /// fn A() -> i32 {
+    ///     1
+    /// }
+    ///
+    /// #[Singleton(eager_create, name = "b")]
+    /// fn B() -> i32 {
     ///     2
     /// }
     ///
@@ -1750,15 +3286,239 @@ impl Context {
     /// }
     /// ```
     pub fn get_singles_by_type<T: 'static>(&self) -> Vec<&T> {
-        let type_id = TypeId::of::<T>();
+        self.keys::<T>()
+            .into_iter()
+            .filter_map(|key| self.find_single(&key))
+            .map(Single::get_ref)
+            .collect()
+    }
 
-        self.single_registry()
-            .iter()
-            .filter(|(key, _)| key.ty.id == type_id)
-            .filter_map(|(_, instance)| instance.as_single())
-            .map(|instance| instance.get_ref())
+    /// Returns a map from the names of the providers to references to [`Singleton`](crate::Scope::Singleton) and [`SingleOwner`](crate::Scope::SingleOwner) instances based on the given type.
+    ///
+    /// Same as [`Context::resolve_map_by_type`], the instances of the [qualified](Context::resolve_qualified) providers are keyed by the type name of their qualifier.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rudi::{Context, Singleton};
+    ///
+    /// #[Singleton(eager_create, name = "a")]
+    /// fn A() -> i32 {
+    ///     1
+    /// }
+    ///
+    /// #[Singleton(eager_create, name = "b")]
+    /// fn B() -> i32 {
+    ///     2
+    /// }
+    ///
+    /// fn main() {
+    ///     let cx = Context::auto_register();
+    ///
+    ///     let map = cx.get_singles_map_by_type::<i32>();
+    ///     assert_eq!(map["a"], &1);
+    ///     assert_eq!(map["b"], &2);
+    /// }
+    /// ```
+    pub fn get_singles_map_by_type<T: 'static>(&self) -> HashMap<Cow<'static, str>, &T> {
+        self.map_keys::<T>()
+            .into_iter()
+            .filter_map(|(name, key)| {
+                let single = self.find_single(&key)?;
+                Some((name, single.get_ref()))
+            })
             .collect()
     }
+
+    /// Returns a [`Factory`] that creates [`Transient`](crate::Scope::Transient) instances based on the given type and default name `""`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if no provider is registered for the given type and default name `""`.
+    /// - Panics if the provider is not a [`Transient`](crate::Scope::Transient).
+    /// - Panics if the constructor of the provider is async.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rudi::{Context, Transient};
+    ///
+    /// #[Transient]
+    /// struct A;
+    ///
+    /// # fn main() {
+    /// let mut cx = Context::auto_register();
+    ///
+    /// let factory = cx.factory::<A>();
+    /// let _a: A = factory.create();
+    /// # }
+    /// ```
+    #[track_caller]
+    pub fn factory<T: 'static>(&mut self) -> Factory<T> {
+        self.factory_with_name("")
+    }
+
+    /// Returns a [`Factory`] that creates [`Transient`](crate::Scope::Transient) instances based on the given type and name.
+    ///
+    /// See [`Context::factory`] for more details.
+    #[track_caller]
+    pub fn factory_with_name<T: 'static>(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+    ) -> Factory<T> {
+        unwrap_or_panic(self.try_factory_with_name(name))
+    }
+
+    /// Non-panicking version of [`Context::factory`].
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rudi::{Context, ResolveErrorKind, Singleton};
+    ///
+    /// #[derive(Clone)]
+    /// #[Singleton]
+    /// struct A;
+    ///
+    /// # fn main() {
+    /// let mut cx = Context::auto_register();
+    ///
+    /// let err = cx.try_factory::<A>().unwrap_err();
+    /// assert!(matches!(err.kind(), ResolveErrorKind::NotTransient(_)));
+    /// # }
+    /// ```
+    pub fn try_factory<T: 'static>(&mut self) -> Result<Factory<T>, ResolveError> {
+        self.try_factory_with_name("")
+    }
+
+    /// Non-panicking version of [`Context::factory_with_name`].
+    pub fn try_factory_with_name<T: 'static>(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+    ) -> Result<Factory<T>, ResolveError> {
+        let provider = self.find_transient_provider::<T>(name.into())?;
+
+        let definition = provider.definition().clone();
+        let post_construct = provider.post_construct();
+
+        match provider.constructor() {
+            Constructor::Sync(constructor) => Ok(Factory::new(
+                definition,
+                constructor,
+                post_construct,
+                self.handle(),
+            )),
+            Constructor::Async(_) | Constructor::None => {
+                Err(self.error(ResolveErrorKind::AsyncConstructorInSync(definition)))
+            }
+        }
+    }
+
+    /// Returns an [`AsyncFactory`] that creates [`Transient`](crate::Scope::Transient) instances based on the given type and default name `""`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if no provider is registered for the given type and default name `""`.
+    /// - Panics if the provider is not a [`Transient`](crate::Scope::Transient).
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rudi::{Context, Transient};
+    ///
+    /// #[Transient(async)]
+    /// struct A;
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let mut cx = Context::auto_register();
+    ///
+    ///     let factory = cx.async_factory::<A>();
+    ///     let _a: A = factory.create().await;
+    /// }
+    /// ```
+    #[track_caller]
+    pub fn async_factory<T: 'static>(&mut self) -> AsyncFactory<T> {
+        self.async_factory_with_name("")
+    }
+
+    /// Returns an [`AsyncFactory`] that creates [`Transient`](crate::Scope::Transient) instances based on the given type and name.
+    ///
+    /// See [`Context::async_factory`] for more details.
+    #[track_caller]
+    pub fn async_factory_with_name<T: 'static>(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+    ) -> AsyncFactory<T> {
+        unwrap_or_panic(self.try_async_factory_with_name(name))
+    }
+
+    /// Non-panicking version of [`Context::async_factory`].
+    pub fn try_async_factory<T: 'static>(&mut self) -> Result<AsyncFactory<T>, ResolveError> {
+        self.try_async_factory_with_name("")
+    }
+
+    /// Non-panicking version of [`Context::async_factory_with_name`].
+    pub fn try_async_factory_with_name<T: 'static>(
+        &mut self,
+        name: impl Into<Cow<'static, str>>,
+    ) -> Result<AsyncFactory<T>, ResolveError> {
+        let provider = self.find_transient_provider::<T>(name.into())?;
+
+        let definition = provider.definition().clone();
+        let constructor = provider.constructor();
+        let post_construct = provider.post_construct();
+
+        Ok(AsyncFactory::new(
+            definition,
+            constructor,
+            post_construct,
+            self.handle(),
+        ))
+    }
+
+    /// Returns a [`Lazy`] handle that resolves a [`Singleton`](crate::Scope::Singleton) or [`Transient`](crate::Scope::Transient) instance
+    /// based on the given type and default name `""` on first use.
+    ///
+    /// The provider is looked up when the handle is resolved, so it does not need to be registered yet.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rudi::{Context, Transient};
+    ///
+    /// #[Transient]
+    /// struct A;
+    ///
+    /// # fn main() {
+    /// let mut cx = Context::auto_register();
+    ///
+    /// let lazy = cx.lazy::<A>();
+    /// assert!(lazy.get().is_none());
+    ///
+    /// lazy.resolve();
+    /// assert!(lazy.get().is_some());
+    /// # }
+    /// ```
+    pub fn lazy<T: 'static>(&mut self) -> Lazy<T> {
+        self.lazy_with_name("")
+    }
+
+    /// Returns a [`Lazy`] handle that resolves a [`Singleton`](crate::Scope::Singleton) or [`Transient`](crate::Scope::Transient) instance
+    /// based on the given type and name on first use.
+    ///
+    /// See [`Context::lazy`] for more details.
+    pub fn lazy_with_name<T: 'static>(&mut self, name: impl Into<Cow<'static, str>>) -> Lazy<T> {
+        Lazy::new(Key::new::<T>(name.into()), self.handle())
+    }
+
+    /// Returns a [`Lazy`] handle that resolves a [`Singleton`](crate::Scope::Singleton) or [`Transient`](crate::Scope::Transient) instance
+    /// based on the given type and qualifier `Q` on first use.
+    ///
+    /// See [`Context::lazy`] and [`Context::resolve_qualified`] for more details.
+    pub fn lazy_qualified<T: 'static, Q: 'static>(&mut self) -> Lazy<T> {
+        Lazy::new(Key::qualified::<T, Q>(), self.handle())
+    }
 }
 
 impl Context {
@@ -1771,17 +3531,23 @@ impl Context {
 
         let allow_only_single_and_it_is_single = matches!(
             (self.allow_only_single_eager_create, definition.scope),
-            (true, Scope::Singleton) | (true, Scope::SingleOwner)
+            (true, Scope::Singleton) | (true, Scope::SingleOwner) | (true, Scope::Custom(_))
         );
 
         let allow_eager_create = allow_all_scope || allow_only_single_and_it_is_single;
 
+        if let Scope::Custom(custom_scope) = definition.scope {
+            if self.find_custom_scope(custom_scope.id()).is_none() {
+                panic!("no custom scope registered for: {:?}", definition);
+            }
+        }
+
         if need_eager_create && allow_eager_create {
             self.eager_create_functions
                 .push((definition.clone(), provider.eager_create_function()));
         }
 
-        self.provider_registry.insert(provider, self.allow_override);
+        self.insert_provider(provider);
     }
 
     #[track_caller]
@@ -1790,10 +3556,18 @@ impl Context {
             return;
         }
 
+        self.unchecked_dependencies = true;
+
         let providers = flatten(providers, DynProvider::binding_providers);
 
         providers.into_iter().for_each(|provider| {
-            if provider.condition().is_some() {
+            if !provider.definition().is_active_in(&self.profiles) {
+                #[cfg(feature = "tracing")]
+                tracing::warn!("(×) profile not active: {:?}", provider.definition());
+                return;
+            }
+
+            if provider.condition_with().is_some() || !provider.definition().presence.is_empty() {
                 self.conditional_providers.push((eager_create, provider));
                 return;
             }
@@ -1802,329 +3576,1839 @@ impl Context {
         });
     }
 
-    fn unload_providers(&mut self, providers: Vec<DynProvider>) {
-        if providers.is_empty() {
+    #[track_caller]
+    fn inner_load_modules(&mut self, modules: Vec<ResolveModule>) {
+        if modules.is_empty() {
             return;
         }
 
-        let providers = flatten(providers, DynProvider::binding_providers);
+        let modules = flatten(modules, ResolveModule::submodules);
 
-        providers.into_iter().for_each(|provider| {
-            let key = provider.key();
-            self.provider_registry.remove(key);
-            self.single_registry.remove(key);
+        modules.into_iter().for_each(|mut module| {
+            let ty = module.ty();
+            self.loaded_modules.push(ty);
+            self.load_decorators(ty, module.decorators());
+            self.load_providers(module.eager_create(), module.providers());
+            self.notify(|observer| observer.on_module_loaded(&ty));
         });
     }
 
-    #[track_caller]
-    fn create_eager_instances(&mut self) {
-        if self.eager_create_functions.is_empty() {
-            return;
-        }
+    /// Checks that at most one unqualified provider of each type registered in the context is primary,
+    /// a primary provider of a child context takes precedence over the ones of its ancestors.
+    fn check_primary_providers(&self) -> Result<(), ResolveError> {
+        let mut primaries = HashMap::<Type, Vec<Definition>>::new();
+        let mut conflict = None;
 
-        self.eager_create_functions.reverse();
+        for (_, provider) in self.provider_registry.iter() {
+            let definition = provider.definition();
 
-        while let Some((definition, eager_create_function)) = self.eager_create_functions.pop() {
-            match eager_create_function {
-                EagerCreateFunction::Async(_) => {
-                    panic!(
-                        "unable to call an async eager create function in a sync context for: {:?}
+            if !definition.primary || definition.key.qualifier.is_some() {
+                continue;
+            }
 
-please use instead:
-1. Context::create_async(modules).await
-2. Context::auto_register_async().await
-3. ContextOptions::create_async(options, modules).await
-4. ContextOptions::auto_register_async(options).await
-",
-                        definition
-                    )
-                }
-                EagerCreateFunction::Sync(eager_create_function) => {
-                    eager_create_function(self, definition.key.name)
-                }
-                EagerCreateFunction::None => unreachable!(),
+            let definitions = primaries.entry(definition.key.ty).or_default();
+            definitions.push(definition.clone());
+
+            if definitions.len() == 2 && conflict.is_none() {
+                conflict = Some(definition.key.ty);
             }
         }
+
+        match conflict {
+            Some(ty) => Err(ResolveError::new(
+                ResolveErrorKind::MultiplePrimaryProviders(
+                    primaries.remove(&ty).unwrap_or_default(),
+                ),
+                Vec::new(),
+            )),
+            None => Ok(()),
+        }
     }
 
-    async fn create_eager_instances_async(&mut self) {
-        if self.eager_create_functions.is_empty() {
-            return;
+    fn check_circular_dependencies(&mut self) -> Result<(), ResolveError> {
+        if !self.unchecked_dependencies {
+            return Ok(());
         }
 
-        self.eager_create_functions.reverse();
+        // the conditional providers are checked once they are evaluated, only the ones whose conditions are met may form a cycle
+        let cycles = find_cycles(self.visible_providers().into_values());
+
+        if cycles.is_empty() {
+            self.unchecked_dependencies = false;
+            Ok(())
+        } else {
+            Err(ResolveError::new(
+                ResolveErrorKind::CircularDependencies(cycles),
+                Vec::new(),
+            ))
+        }
+    }
 
-        while let Some((definition, eager_create_function)) = self.eager_create_functions.pop() {
+    fn inner_unload_modules(&mut self, modules: Vec<ResolveModule>) -> Vec<(Key, DynSingle)> {
+        if modules.is_empty() {
+            return Vec::new();
+        }
+
+        let modules = flatten(modules, ResolveModule::submodules);
+
+        let mut keys = Vec::new();
+
+        modules.into_iter().for_each(|module| {
+            let ty = module.ty();
+            self.loaded_modules.retain(|loaded| loaded != &ty);
+            self.update_decorators(|decorators| {
+                decorators.retain(|(module, _)| module != &Some(ty))
+            });
+            keys.extend(self.unload_providers(module.providers()));
+            self.notify(|observer| observer.on_module_unloaded(&ty));
+        });
+
+        self.remove_singles(&mut |key| keys.contains(key))
+    }
+
+    fn unload_providers(&mut self, providers: Vec<DynProvider>) -> Vec<Key> {
+        if providers.is_empty() {
+            return Vec::new();
+        }
+
+        let providers = flatten(providers, DynProvider::binding_providers);
+
+        providers
+            .into_iter()
+            .map(|provider| {
+                let key = provider.key();
+                self.remove_provider(key);
+                key.clone()
+            })
+            .collect()
+    }
+
+    fn remove_singles(&mut self, f: &mut dyn FnMut(&Key) -> bool) -> Vec<(Key, DynSingle)> {
+        // the instances created by the handle are disposed with the instances of the context
+        self.take_handle_singles();
+
+        let mut singles = self.scoped_registry.remove_rev(&mut *f);
+        singles.extend(self.update_singles(|registry| registry.remove_rev(f)));
+        singles
+    }
+
+    #[track_caller]
+    fn create_eager_instances(&mut self) -> Result<(), ResolveError> {
+        if self.eager_create_functions.is_empty() {
+            return Ok(());
+        }
+
+        self.eager_create_functions.reverse();
+
+        while let Some((definition, eager_create_function)) = self.eager_create_functions.pop() {
             match eager_create_function {
-                EagerCreateFunction::Async(eager_create_function) => {
-                    eager_create_function(self, definition.key.name).await
+                EagerCreateFunction::Async(_) => {
+                    panic!(
+                        "unable to call an async eager create function in a sync context for: {:?}
+
+please use instead:
+1. Context::create_async(modules).await
+2. Context::auto_register_async().await
+3. ContextOptions::create_async(options, modules).await
+4. ContextOptions::auto_register_async(options).await
+",
+                        definition
+                    )
                 }
                 EagerCreateFunction::Sync(eager_create_function) => {
-                    eager_create_function(self, definition.key.name)
+                    eager_create_function(self, definition.key)?
                 }
                 EagerCreateFunction::None => unreachable!(),
             }
         }
+
+        Ok(())
     }
 
-    #[track_caller]
-    fn evaluate_providers(&mut self) {
-        if self.conditional_providers.is_empty() {
-            return;
+    async fn create_eager_instances_async(&mut self) -> Result<(), ResolveError> {
+        if self.eager_create_functions.is_empty() {
+            return Ok(());
         }
 
-        self.conditional_providers.reverse();
+        if self.concurrent_eager_create {
+            return self.create_eager_instances_concurrently().await;
+        }
 
-        while let Some((eager_create, provider)) = self.conditional_providers.pop() {
-            let evaluate = provider.condition().expect("unreachable: a provider in `conditional_providers`, its `condition()` method must return `Some(_)`");
+        self.eager_create_functions.reverse();
 
-            if evaluate(self) {
-                self.load_provider(eager_create, provider);
-            } else {
-                #[cfg(feature = "tracing")]
-                tracing::warn!("(×) condition not met: {:?}", provider.definition());
+        while let Some((definition, eager_create_function)) = self.eager_create_functions.pop() {
+            match eager_create_function {
+                EagerCreateFunction::Async(eager_create_function) => {
+                    eager_create_function(self, definition.key).await?
+                }
+                EagerCreateFunction::Sync(eager_create_function) => {
+                    eager_create_function(self, definition.key)?
+                }
+                EagerCreateFunction::None => unreachable!(),
             }
         }
+
+        Ok(())
     }
 
-    fn before_resolve<T: 'static>(
-        &mut self,
-        name: Cow<'static, str>,
-        behaviour: Behaviour,
-    ) -> Result<Resolved<T>, Holder<'_, T>> {
-        let key = Key::new::<T>(name);
+    async fn create_eager_instances_concurrently(&mut self) -> Result<(), ResolveError> {
+        self.eager_create_functions.reverse();
 
-        let Some(provider) = self.provider_registry.get::<T>(&key) else {
-            return Ok(Resolved::NotFoundProvider(key));
-        };
+        let mut group: Vec<(Definition, AsyncEagerCreateFunction)> = Vec::new();
 
-        let definition = provider.definition();
+        loop {
+            let next = self.eager_create_functions.pop();
 
-        if self.single_registry.contains(&key) {
-            return Ok(match behaviour {
-                Behaviour::CreateThenReturnSingletonOrTransient => {
-                    match self.single_registry.get_owned::<T>(&key) {
-                        Some(instance) => Resolved::SingletonOrTransient(instance),
-                        None => Resolved::NotSingletonOrTransient(definition.clone()),
+            if let Some((definition, EagerCreateFunction::Async(eager_create_function))) = &next {
+                if self.can_create_concurrently(&definition.key, &group) {
+                    group.push((definition.clone(), *eager_create_function));
+                    continue;
+                }
+            }
+
+            if let Err(e) = self.create_group(mem::take(&mut group)).await {
+                // the next provider has not been created yet
+                self.eager_create_functions.extend(next);
+                return Err(e);
+            }
+
+            let Some((definition, eager_create_function)) = next else {
+                return Ok(());
+            };
+
+            match eager_create_function {
+                EagerCreateFunction::Async(eager_create_function) => {
+                    if self.can_create_concurrently(&definition.key, &group) {
+                        group.push((definition, eager_create_function));
+                    } else {
+                        eager_create_function(self, definition.key).await?
                     }
                 }
-                Behaviour::JustCreateAllScopeForEagerCreate
-                | Behaviour::JustCreateSingletonOrSingleOwner => Resolved::NoReturn,
-            });
+                EagerCreateFunction::Sync(eager_create_function) => {
+                    eager_create_function(self, definition.key)?
+                }
+                EagerCreateFunction::None => unreachable!(),
+            }
         }
+    }
 
-        match (definition.scope, behaviour) {
-            (Scope::Transient, Behaviour::JustCreateSingletonOrSingleOwner) => {
-                return Ok(Resolved::NotSingletonOrSingleOwner(definition.clone()))
+    /// Returns whether the instance of the key can be created concurrently with the instances of the group,
+    /// i.e. it is not in the group, and it [is independent](Context::is_independent).
+    fn can_create_concurrently(
+        &self,
+        key: &Key,
+        group: &[(Definition, AsyncEagerCreateFunction)],
+    ) -> bool {
+        !group.iter().any(|(definition, _)| &definition.key == key) && self.is_independent(key)
+    }
+
+    /// Returns whether all the dependencies cached in the context that the instance of the key needs have been created,
+    /// so it can be created on a child context, concurrently with other independent instances.
+    ///
+    /// A provider that does not declare its dependencies may resolve anything, so it is never independent.
+    pub(crate) fn is_independent(&self, key: &Key) -> bool {
+        let providers = self.visible_providers();
+
+        let mut visited = HashSet::new();
+        let mut stack = vec![key];
+
+        while let Some(key) = stack.pop() {
+            if !visited.insert(key) {
+                continue;
             }
-            (Scope::SingleOwner, Behaviour::CreateThenReturnSingletonOrTransient) => {
-                return Ok(Resolved::NotSingletonOrTransient(definition.clone()))
+
+            let Some(provider) = providers.get(key) else {
+                continue;
+            };
+
+            if let Scope::Custom(_) = provider.definition().scope {
+                return false;
             }
-            _ => {}
-        }
 
-        let constructor = provider.constructor();
-        let clone_instance = provider.clone_instance();
+            if !provider.declares_dependencies() {
+                return false;
+            }
 
-        Err(Holder {
-            key,
-            constructor,
-            clone_instance,
-            definition,
-        })
+            for dependency in provider.dependencies() {
+                let keys = match dependency.kind {
+                    // the handle must be captured from the context itself, not from a child that is dropped
+                    DependencyKind::Lazy
+                    | DependencyKind::Factory
+                    | DependencyKind::AsyncFactory => return false,
+                    DependencyKind::Vec | DependencyKind::Map => providers
+                        .keys()
+                        .filter(|key| key.ty == dependency.key.ty)
+                        .copied()
+                        .collect(),
+                    _ => providers
+                        .get_key_value(&dependency.key)
+                        .map(|(key, _)| *key)
+                        .or_else(|| {
+                            (dependency.key.name.is_empty() && dependency.key.qualifier.is_none())
+                                .then(|| self.find_primary(&dependency.key.ty))
+                                .flatten()
+                        })
+                        .into_iter()
+                        .collect::<Vec<_>>(),
+                };
+
+                for key in keys {
+                    match providers[key].definition().scope {
+                        Scope::Transient => stack.push(key),
+                        Scope::Singleton | Scope::SingleOwner => {
+                            if !self.contains_single_key(key) {
+                                return false;
+                            }
+                        }
+                        Scope::Custom(_) => return false,
+                    }
+                }
+            }
+        }
+
+        true
     }
 
-    fn after_resolve<T: 'static>(
+    /// Awaits the eager create functions of the group concurrently, each on a child context of this context,
+    /// then moves the created instances into this context in the order of the group.
+    async fn create_group(
         &mut self,
-        key: Key,
-        behaviour: Behaviour,
-        scope: Scope,
-        instance: T,
-        clone_instance: Option<fn(&T) -> T>,
-    ) -> Resolved<T> {
-        match (scope, behaviour) {
-            // Singleton
-            (Scope::Singleton, Behaviour::CreateThenReturnSingletonOrTransient) => {
-                self.single_registry.insert(
-                    key,
-                    Single::new((clone_instance.unwrap())(&instance), clone_instance).into(),
-                );
-
-                Resolved::SingletonOrTransient(instance)
+        group: Vec<(Definition, AsyncEagerCreateFunction)>,
+    ) -> Result<(), ResolveError> {
+        if group.len() <= 1 {
+            for (definition, eager_create_function) in group {
+                eager_create_function(self, definition.key).await?;
             }
-            (Scope::Singleton, Behaviour::JustCreateAllScopeForEagerCreate)
-            | (Scope::Singleton, Behaviour::JustCreateSingletonOrSingleOwner) => {
-                self.single_registry
-                    .insert(key, Single::new(instance, clone_instance).into());
 
-                Resolved::NoReturn
-            }
-            // Transient
-            (Scope::Transient, Behaviour::CreateThenReturnSingletonOrTransient) => {
-                Resolved::SingletonOrTransient(instance)
+            return Ok(());
+        }
+
+        let recording = self.startup.recording;
+        // the context is not mutated until the children are absorbed, so they can share its registries
+        let parent = Shared::new(self.view());
+
+        let mut children = group
+            .iter()
+            .map(|_| {
+                let mut child = parent.inner_child(Vec::new());
+                child.startup.recording = recording;
+                child
+            })
+            .collect::<Vec<_>>();
+
+        let results = JoinAll::new(
+            children
+                .iter_mut()
+                .zip(&group)
+                .map(|(child, (definition, eager_create_function))| {
+                    eager_create_function(child, definition.key.clone())
+                })
+                .collect(),
+        )
+        .await;
+
+        children.iter_mut().for_each(Context::detach);
+        drop(parent);
+
+        let mut absorbed = Ok(());
+
+        for child in children {
+            let result = self.absorb(child);
+            absorbed = absorbed.and(result);
+        }
+
+        for ((definition, _), result) in group.into_iter().zip(results) {
+            match result {
+                Ok(Ok(())) => {}
+                Ok(Err(e)) => return Err(e),
+                Err(payload) => {
+                    let message = payload
+                        .downcast_ref::<&str>()
+                        .copied()
+                        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+                        .unwrap_or("Box<dyn Any>");
+
+                    panic!(
+                        "panicked while eagerly creating: {:?}\n\n{}",
+                        definition, message
+                    );
+                }
             }
-            (Scope::Transient, Behaviour::JustCreateAllScopeForEagerCreate) => Resolved::NoReturn,
-            (Scope::Transient, Behaviour::JustCreateSingletonOrSingleOwner) => unreachable!(),
-            // SingleOwner
-            (Scope::SingleOwner, Behaviour::CreateThenReturnSingletonOrTransient) => unreachable!(),
-            (Scope::SingleOwner, Behaviour::JustCreateAllScopeForEagerCreate)
-            | (Scope::SingleOwner, Behaviour::JustCreateSingletonOrSingleOwner) => {
-                self.single_registry
-                    .insert(key, Single::new(instance, None).into());
+        }
 
-                Resolved::NoReturn
+        absorbed
+    }
+
+    /// Releases the parent of a child context, so the parent can be mutated again after the child is [absorbed](Context::absorb).
+    pub(crate) fn detach(&mut self) {
+        self.parent = None;
+    }
+
+    /// Moves the instances created by a child context, whose instances are [independent](Context::is_independent)
+    /// of the instances created by other children, into this context.
+    ///
+    /// Returns an error if an instance of the child is already cached in this context,
+    /// e.g. it was created by another child too, the instance of this context is kept.
+    pub(crate) fn absorb(&mut self, mut child: Context) -> Result<(), ResolveError> {
+        child.take_handle_singles();
+
+        let mut duplicate = None;
+
+        for (key, single) in Shared::make_mut(&mut child.single_registry).drain() {
+            if self.single_registry.contains(&key) {
+                duplicate.get_or_insert(key);
+            } else {
+                self.insert_single(key, single);
             }
         }
+
+        self.dependency_chain
+            .edges
+            .extend(child.dependency_chain.edges);
+        self.startup.merge(child.startup);
+
+        match duplicate {
+            Some(key) => Err(ResolveError::new(
+                ResolveErrorKind::CreatedConcurrently(key),
+                Vec::new(),
+            )),
+            None => Ok(()),
+        }
     }
 
     #[track_caller]
-    fn inner_resolve<T: 'static>(
-        &mut self,
-        name: Cow<'static, str>,
-        behaviour: Behaviour,
-    ) -> Resolved<T> {
-        let Holder {
-            key,
-            constructor,
-            clone_instance,
-            definition,
-        } = match self.before_resolve(name, behaviour) {
-            Ok(o) => return o,
-            Err(e) => e,
-        };
+    fn evaluate_providers(&mut self) -> Result<(), ResolveError> {
+        if self.conditional_providers.is_empty() {
+            return Ok(());
+        }
 
-        let scope = definition.scope;
+        self.unchecked_dependencies = true;
 
-        let instance = match constructor {
-            Constructor::Async(_) => {
-                panic!(
-                    "unable to call an async constructor in a sync context for: {:?}
+        // the presence conditions are checked last, so that the providers inserted by the other conditions are taken into account
+        let (deferred, providers): (Vec<_>, Vec<_>) = mem::take(&mut self.conditional_providers)
+            .into_iter()
+            .partition(|(_, provider)| !provider.definition().presence.is_empty());
 
-please check all the references to the above type, there are 3 scenarios that will be referenced:
-1. use `Context::resolve_xxx::<Type>(cx)` to get instances of the type, change to `Context::resolve_xxx_async::<Type>(cx).await`.
-2. use `yyy: Type` as a field of a struct, or a field of a variant of a enum, use `#[Singleton(async)]`, `#[Transient(async)]` or `#[SingleOwner(async)]` on the struct or enum.
-3. use `zzz: Type` as a argument of a function, add the `async` keyword to the function.
-",
-                    definition
-                )
+        let (eager_creates, mut providers): (Vec<_>, Vec<_>) = providers.into_iter().unzip();
+
+        let met = match self.evaluate_conditions(&mut providers) {
+            Ok(met) => met,
+            Err(e) => {
+                // keep the providers, so that the next flush reports the same error
+                self.conditional_providers = eager_creates
+                    .into_iter()
+                    .zip(providers)
+                    .chain(deferred)
+                    .collect();
+
+                return Err(e);
             }
-            Constructor::Sync(constructor) => self.resolve_instance(key.clone(), constructor),
-            Constructor::None => unreachable!(),
         };
 
-        self.after_resolve(key, behaviour, scope, instance, clone_instance)
+        for ((eager_create, provider), met) in eager_creates.into_iter().zip(providers).zip(met) {
+            self.load_conditional_provider(eager_create, provider, met);
+        }
+
+        for (eager_create, provider) in deferred {
+            let met = provider
+                .condition_with()
+                .is_none_or(|evaluate| evaluate(&ConditionContext::new(self)))
+                && self.is_presence_met(provider.definition());
+
+            self.load_conditional_provider(eager_create, provider, met);
+        }
+
+        Ok(())
     }
 
-    async fn inner_resolve_async<T: 'static>(
+    /// Evaluates the conditions of the providers to a fixpoint, returns whether the condition of each provider is met.
+    ///
+    /// Starting with none of the providers met, each round evaluates the conditions
+    /// with the providers met in the previous round tentatively inserted, except the providers of the same group
+    /// as the evaluated one, until a round gives the same result as the previous one.
+    /// So the result does not depend on the order in which the providers are loaded.
+    /// A condition is only evaluated again when a provider of a type it queried changed in the previous round.
+    ///
+    /// If the results of the rounds repeat without reaching a fixpoint, the conditions are evaluated one at a time,
+    /// in load order and in reverse load order. If both settle on different results, the conditions are ambiguous,
+    /// otherwise the providers whose result changes in the repeated rounds are reported as contradictory.
+    fn evaluate_conditions(
         &mut self,
-        name: Cow<'static, str>,
-        behaviour: Behaviour,
-    ) -> Resolved<T> {
-        let Holder {
-            key,
-            constructor,
-            clone_instance,
-            definition,
-        } = match self.before_resolve(name, behaviour) {
-            Ok(o) => return o,
-            Err(e) => e,
-        };
+        providers: &mut Vec<DynProvider>,
+    ) -> Result<Vec<bool>, ResolveError> {
+        let mut tentative = TentativeProviders::new(mem::take(providers));
+        let len = tentative.len();
+
+        let mut reads = (0..len)
+            .map(|_| ConditionReads::default())
+            .collect::<Vec<_>>();
+        let mut current = vec![false; len];
+        let mut rounds = vec![current.clone()];
+        let mut seen = HashMap::from([(current.clone(), 0)]);
+        let mut stale = (0..len).collect::<Vec<_>>();
+
+        let result = loop {
+            let mut next = current.clone();
+
+            for &index in &stale {
+                let read = RefCell::default();
+                next[index] = self.evaluate_tentative(&mut tentative, index, &read);
+                reads[index] = read.into_inner();
+            }
 
-        let scope = definition.scope;
+            let changed = (0..len)
+                .filter(|&i| next[i] != current[i])
+                .collect::<Vec<_>>();
 
-        let instance = {
-            let key = key.clone();
+            if changed.is_empty() {
+                break Ok(next);
+            }
 
-            match constructor {
-                Constructor::Async(constructor) => {
-                    self.resolve_instance_async(key, constructor).await
-                }
-                Constructor::Sync(constructor) => self.resolve_instance(key, constructor),
-                Constructor::None => unreachable!(),
+            if let Some(&start) = seen.get(&next) {
+                let cycle = &rounds[start..];
+
+                let oscillating = (0..len)
+                    .filter(|&i| cycle.iter().any(|round| round[i] != cycle[0][i]))
+                    .collect::<Vec<_>>();
+
+                break Err(self.unstable_conditions_error(&mut tentative, oscillating));
+            }
+
+            for &i in &changed {
+                self.update_providers(|registry| tentative.set_inserted(registry, i, next[i]));
             }
+
+            let changed = changed
+                .into_iter()
+                .map(|i| tentative.keys[i].ty)
+                .collect::<HashSet<_>>();
+
+            stale = (0..len)
+                .filter(|&i| reads[i].is_affected_by(&changed))
+                .collect();
+
+            seen.insert(next.clone(), rounds.len());
+            rounds.push(next.clone());
+            current = next;
         };
 
-        self.after_resolve(key, behaviour, scope, instance, clone_instance)
+        *providers = self.update_providers(|registry| tentative.into_providers(registry));
+
+        result
     }
 
-    #[track_caller]
-    fn resolve_instance<T: 'static>(
+    /// Returns the error of the conditions that do not reach a fixpoint,
+    /// see [`Context::evaluate_conditions`] for more details.
+    fn unstable_conditions_error(
         &mut self,
-        key: Key,
-        constructor: Rc<dyn Fn(&mut Context) -> T>,
-    ) -> T {
-        self.dependency_chain.push(key);
-        let instance = constructor(self);
-        self.dependency_chain.pop();
-        instance
+        tentative: &mut TentativeProviders,
+        oscillating: Vec<usize>,
+    ) -> ResolveError {
+        let len = tentative.len();
+
+        let forward = self.evaluate_one_at_a_time(tentative, &(0..len).collect::<Vec<_>>());
+        let backward = self.evaluate_one_at_a_time(tentative, &(0..len).rev().collect::<Vec<_>>());
+
+        self.update_providers(|registry| tentative.remove_all(registry));
+
+        let kind = match (forward, backward) {
+            (Some(forward), Some(backward)) if forward != backward => {
+                ResolveErrorKind::AmbiguousConditions(
+                    (0..len)
+                        .filter(|&i| forward[i] != backward[i])
+                        .map(|i| tentative.definition(i).clone())
+                        .collect(),
+                )
+            }
+            _ => ResolveErrorKind::ContradictoryConditions(
+                oscillating
+                    .into_iter()
+                    .map(|i| tentative.definition(i).clone())
+                    .collect(),
+            ),
+        };
+
+        ResolveError::new(kind, Vec::new())
     }
 
-    #[allow(clippy::type_complexity)]
-    async fn resolve_instance_async<T: 'static>(
+    /// Evaluates the conditions one at a time in the given order, each result is taken into account immediately,
+    /// returns the result once a pass changes nothing, or `None` if the results of the passes repeat.
+    fn evaluate_one_at_a_time(
         &mut self,
-        key: Key,
-        constructor: Rc<dyn for<'a> Fn(&'a mut Context) -> BoxFuture<'a, T>>,
-    ) -> T {
-        self.dependency_chain.push(key);
-        let instance = constructor(self).await;
-        self.dependency_chain.pop();
-        instance
-    }
+        tentative: &mut TentativeProviders,
+        order: &[usize],
+    ) -> Option<Vec<bool>> {
+        self.update_providers(|registry| tentative.remove_all(registry));
 
-    fn names<T: 'static>(&self) -> Vec<Cow<'static, str>> {
-        let type_id = TypeId::of::<T>();
+        let mut state = vec![false; tentative.len()];
+        let mut seen = HashSet::from([state.clone()]);
 
-        self.provider_registry()
-            .keys()
-            .filter(|&key| key.ty.id == type_id)
-            .map(|key| key.name.clone())
-            .collect()
+        loop {
+            let mut changed = false;
+
+            for &index in order {
+                let met = self.evaluate_tentative(tentative, index, &RefCell::default());
+
+                if met != state[index] {
+                    state[index] = met;
+                    self.update_providers(|registry| tentative.set_inserted(registry, index, met));
+                    changed = true;
+                }
+            }
+
+            if !changed {
+                return Some(state);
+            }
+
+            if !seen.insert(state.clone()) {
+                return None;
+            }
+        }
     }
-}
 
-#[derive(Clone, Copy)]
-enum Behaviour {
-    CreateThenReturnSingletonOrTransient,
-    JustCreateAllScopeForEagerCreate,
-    JustCreateSingletonOrSingleOwner,
-}
+    /// Evaluates the condition of the provider at `index` while the providers of its group are removed from the registry,
+    /// recording the types of the providers the condition queries into `reads`.
+    fn evaluate_tentative(
+        &mut self,
+        tentative: &mut TentativeProviders,
+        index: usize,
+        reads: &RefCell<ConditionReads>,
+    ) -> bool {
+        let hidden = tentative
+            .group(index)
+            .iter()
+            .copied()
+            .filter(|&i| tentative.is_inserted(i))
+            .collect::<Vec<_>>();
+
+        for &i in &hidden {
+            self.update_providers(|registry| tentative.set_inserted(registry, i, false));
+        }
+
+        let evaluate = tentative.provider(index).condition_with().unwrap();
+        let met = evaluate(&ConditionContext::recording(self, reads));
+
+        for &i in &hidden {
+            self.update_providers(|registry| tentative.set_inserted(registry, i, true));
+        }
+
+        met
+    }
+
+    #[track_caller]
+    fn load_conditional_provider(&mut self, eager_create: bool, provider: DynProvider, met: bool) {
+        self.notify(|observer| observer.on_condition_evaluated(provider.definition(), met));
+
+        if met {
+            self.load_provider(eager_create, provider);
+        } else {
+            #[cfg(feature = "tracing")]
+            tracing::warn!("(×) condition not met: {:?}", provider.definition());
+        }
+    }
+
+    fn is_presence_met(&self, definition: &Definition) -> bool {
+        definition.presence.iter().all(|condition| match condition {
+            PresenceCondition::Missing(ty) => !self.contains_provider_of_type(ty, definition),
+            PresenceCondition::Present(ty) => self.contains_provider_of_type(ty, definition),
+        })
+    }
+
+    /// Returns whether a provider of the type is registered in the context or its ancestors,
+    /// except the providers bound from the same provider as the given definition.
+    fn contains_provider_of_type(&self, ty: &Type, except: &Definition) -> bool {
+        let except = group(except);
+
+        self.ancestors().any(|cx| {
+            cx.provider_registry
+                .iter()
+                .any(|(key, provider)| key.ty == *ty && group(provider.definition()) != except)
+        })
+    }
+
+    pub(crate) fn try_resolve_by_key<T: 'static>(&mut self, key: Key) -> Result<T, ResolveError> {
+        match self.inner_resolve(key, Behaviour::CreateThenReturnSingletonOrTransient)? {
+            Resolved::SingletonOrTransient(instance) => Ok(instance),
+            Resolved::NotFoundProvider(key) => {
+                Err(self.error(ResolveErrorKind::NotFoundProvider(key)))
+            }
+            Resolved::NotSingletonOrTransient(definition) => {
+                Err(self.error(ResolveErrorKind::NotSingletonOrTransient(definition)))
+            }
+            Resolved::NotSingletonOrSingleOwner(_) | Resolved::NoReturn => unreachable!(),
+        }
+    }
+
+    fn try_resolve_option_by_key<T: 'static>(
+        &mut self,
+        key: Key,
+    ) -> Result<Option<T>, ResolveError> {
+        match self.inner_resolve(key, Behaviour::CreateThenReturnSingletonOrTransient)? {
+            Resolved::SingletonOrTransient(instance) => Ok(Some(instance)),
+            Resolved::NotFoundProvider(_) | Resolved::NotSingletonOrTransient(_) => Ok(None),
+            Resolved::NotSingletonOrSingleOwner(_) | Resolved::NoReturn => unreachable!(),
+        }
+    }
+
+    fn just_create_single_by_key_checked<T: 'static>(
+        &mut self,
+        key: Key,
+    ) -> Result<(), ResolveError> {
+        match self.inner_resolve::<T>(key, Behaviour::JustCreateSingletonOrSingleOwner)? {
+            Resolved::NoReturn => Ok(()),
+            Resolved::NotFoundProvider(key) => {
+                Err(self.error(ResolveErrorKind::NotFoundProvider(key)))
+            }
+            Resolved::NotSingletonOrSingleOwner(definition) => {
+                Err(self.error(ResolveErrorKind::NotSingletonOrSingleOwner(definition)))
+            }
+            Resolved::SingletonOrTransient(_) | Resolved::NotSingletonOrTransient(_) => {
+                unreachable!()
+            }
+        }
+    }
+
+    fn try_just_create_single_by_key_checked<T: 'static>(
+        &mut self,
+        key: Key,
+    ) -> Result<bool, ResolveError> {
+        match self.inner_resolve::<T>(key, Behaviour::JustCreateSingletonOrSingleOwner)? {
+            Resolved::NoReturn => Ok(true),
+            Resolved::NotFoundProvider(_) | Resolved::NotSingletonOrSingleOwner(_) => Ok(false),
+            Resolved::SingletonOrTransient(_) | Resolved::NotSingletonOrTransient(_) => {
+                unreachable!()
+            }
+        }
+    }
+
+    pub(crate) async fn try_resolve_by_key_async<T: 'static>(
+        &mut self,
+        key: Key,
+    ) -> Result<T, ResolveError> {
+        match self
+            .inner_resolve_async(key, Behaviour::CreateThenReturnSingletonOrTransient)
+            .await?
+        {
+            Resolved::SingletonOrTransient(instance) => Ok(instance),
+            Resolved::NotFoundProvider(key) => {
+                Err(self.error(ResolveErrorKind::NotFoundProvider(key)))
+            }
+            Resolved::NotSingletonOrTransient(definition) => {
+                Err(self.error(ResolveErrorKind::NotSingletonOrTransient(definition)))
+            }
+            Resolved::NotSingletonOrSingleOwner(_) | Resolved::NoReturn => unreachable!(),
+        }
+    }
+
+    pub(crate) async fn try_resolve_option_by_key_async<T: 'static>(
+        &mut self,
+        key: Key,
+    ) -> Result<Option<T>, ResolveError> {
+        match self
+            .inner_resolve_async(key, Behaviour::CreateThenReturnSingletonOrTransient)
+            .await?
+        {
+            Resolved::SingletonOrTransient(instance) => Ok(Some(instance)),
+            Resolved::NotFoundProvider(_) | Resolved::NotSingletonOrTransient(_) => Ok(None),
+            Resolved::NotSingletonOrSingleOwner(_) | Resolved::NoReturn => unreachable!(),
+        }
+    }
+
+    async fn just_create_single_by_key_checked_async<T: 'static>(
+        &mut self,
+        key: Key,
+    ) -> Result<(), ResolveError> {
+        match self
+            .inner_resolve_async::<T>(key, Behaviour::JustCreateSingletonOrSingleOwner)
+            .await?
+        {
+            Resolved::NoReturn => Ok(()),
+            Resolved::NotFoundProvider(key) => {
+                Err(self.error(ResolveErrorKind::NotFoundProvider(key)))
+            }
+            Resolved::NotSingletonOrSingleOwner(definition) => {
+                Err(self.error(ResolveErrorKind::NotSingletonOrSingleOwner(definition)))
+            }
+            Resolved::SingletonOrTransient(_) | Resolved::NotSingletonOrTransient(_) => {
+                unreachable!()
+            }
+        }
+    }
+
+    async fn try_just_create_single_by_key_checked_async<T: 'static>(
+        &mut self,
+        key: Key,
+    ) -> Result<bool, ResolveError> {
+        match self
+            .inner_resolve_async::<T>(key, Behaviour::JustCreateSingletonOrSingleOwner)
+            .await?
+        {
+            Resolved::NoReturn => Ok(true),
+            Resolved::NotFoundProvider(_) | Resolved::NotSingletonOrSingleOwner(_) => Ok(false),
+            Resolved::SingletonOrTransient(_) | Resolved::NotSingletonOrTransient(_) => {
+                unreachable!()
+            }
+        }
+    }
+
+    // `Err` is not an error here, it holds what is needed to create the instance.
+    #[allow(clippy::result_large_err)]
+    fn before_resolve<T: 'static>(
+        &mut self,
+        key: Key,
+        behaviour: Behaviour,
+    ) -> Result<Resolved<T>, Holder<'_, T>> {
+        let key = self.key(key);
+
+        self.dependency_chain.observe(&key);
+        self.startup.resolve(&key);
+        self.take_handle_singles();
+
+        let Some(provider) = self.find_provider::<T>(&key) else {
+            return Ok(Resolved::NotFoundProvider(key));
+        };
+
+        let definition = provider.definition();
+
+        if let Some(single) = self.find_single::<T>(&key) {
+            return Ok(match behaviour {
+                Behaviour::CreateThenReturnSingletonOrTransient => match single.get_owned() {
+                    Some(instance) => Resolved::SingletonOrTransient(instance),
+                    None => Resolved::NotSingletonOrTransient(definition.clone()),
+                },
+                Behaviour::JustCreateAllScopeForEagerCreate
+                | Behaviour::JustCreateSingletonOrSingleOwner => Resolved::NoReturn,
+            });
+        }
+
+        match (definition.scope, behaviour) {
+            (Scope::Transient, Behaviour::JustCreateSingletonOrSingleOwner) => {
+                return Ok(Resolved::NotSingletonOrSingleOwner(definition.clone()))
+            }
+            (Scope::SingleOwner, Behaviour::CreateThenReturnSingletonOrTransient) => {
+                return Ok(Resolved::NotSingletonOrTransient(definition.clone()))
+            }
+            (Scope::Custom(_), Behaviour::JustCreateSingletonOrSingleOwner) => {
+                return Ok(Resolved::NotSingletonOrSingleOwner(definition.clone()))
+            }
+            _ => {}
+        }
+
+        let mut partition = None;
+
+        if let Scope::Custom(custom_scope) = definition.scope {
+            // the instances of a custom scope are cached by the context the handle belongs to, which the handle cannot mutate
+            if let Some(Handle::Borrowed(_)) = &self.handle {
+                return Ok(Resolved::NotFoundProvider(key));
+            }
+
+            let custom_scope = self.find_custom_scope(custom_scope.id()).expect(
+                "unreachable: the custom scope of a registered provider must be registered",
+            );
+
+            let id = custom_scope.partition(self, &key);
+            let cached = self.scoped_registry.get(&key, id);
+            let decision = custom_scope.decide(&key, cached.map(|(_, cached)| cached));
+
+            if let (ScopeDecision::Reuse, Some((single, _))) = (decision, cached) {
+                return Ok(match behaviour {
+                    Behaviour::CreateThenReturnSingletonOrTransient => {
+                        match single.as_single::<T>().and_then(Single::get_owned) {
+                            Some(instance) => Resolved::SingletonOrTransient(instance),
+                            None => Resolved::NotSingletonOrTransient(definition.clone()),
+                        }
+                    }
+                    Behaviour::JustCreateAllScopeForEagerCreate
+                    | Behaviour::JustCreateSingletonOrSingleOwner => Resolved::NoReturn,
+                });
+            }
+
+            partition = Some(Partition { id, decision });
+        }
+
+        Err(Holder {
+            key,
+            constructor: provider.constructor(),
+            store: Store {
+                clone_instance: provider.clone_instance(),
+                erase_single: provider.erase_single(),
+                post_construct: provider.post_construct(),
+                dispose: provider.dispose(),
+                partition,
+            },
+            definition,
+        })
+    }
+
+    fn after_resolve<T: 'static>(
+        &mut self,
+        key: Key,
+        behaviour: Behaviour,
+        scope: Scope,
+        instance: T,
+        store: Store<T>,
+    ) -> Resolved<T> {
+        let Store {
+            clone_instance,
+            erase_single,
+            post_construct: _,
+            dispose,
+            partition,
+        } = store;
+
+        match (scope, behaviour) {
+            // Singleton
+            (Scope::Singleton, Behaviour::CreateThenReturnSingletonOrTransient) => {
+                self.notify(|observer| observer.on_single_cached(&key));
+                self.insert_single(
+                    key,
+                    (erase_single.unwrap())(
+                        Single::new((clone_instance.unwrap())(&instance), clone_instance)
+                            .with_dispose(dispose),
+                    ),
+                );
+
+                Resolved::SingletonOrTransient(instance)
+            }
+            (Scope::Singleton, Behaviour::JustCreateAllScopeForEagerCreate)
+            | (Scope::Singleton, Behaviour::JustCreateSingletonOrSingleOwner) => {
+                self.notify(|observer| observer.on_single_cached(&key));
+                self.insert_single(
+                    key,
+                    (erase_single.unwrap())(
+                        Single::new(instance, clone_instance).with_dispose(dispose),
+                    ),
+                );
+
+                Resolved::NoReturn
+            }
+            // Transient
+            (Scope::Transient, Behaviour::CreateThenReturnSingletonOrTransient) => {
+                Resolved::SingletonOrTransient(instance)
+            }
+            (Scope::Transient, Behaviour::JustCreateAllScopeForEagerCreate) => Resolved::NoReturn,
+            (Scope::Transient, Behaviour::JustCreateSingletonOrSingleOwner) => unreachable!(),
+            // SingleOwner
+            (Scope::SingleOwner, Behaviour::CreateThenReturnSingletonOrTransient) => unreachable!(),
+            (Scope::SingleOwner, Behaviour::JustCreateAllScopeForEagerCreate)
+            | (Scope::SingleOwner, Behaviour::JustCreateSingletonOrSingleOwner) => {
+                self.notify(|observer| observer.on_single_cached(&key));
+                self.insert_single(
+                    key,
+                    (erase_single.unwrap())(Single::new(instance, None).with_dispose(dispose)),
+                );
+
+                Resolved::NoReturn
+            }
+            // Custom
+            (Scope::Custom(_), Behaviour::JustCreateSingletonOrSingleOwner) => unreachable!(),
+            (Scope::Custom(_), behaviour) => {
+                let Partition { id, decision } = partition.unwrap();
+
+                match decision {
+                    ScopeDecision::Reuse | ScopeDecision::Create => {
+                        self.notify(|observer| observer.on_single_cached(&key));
+                        self.scoped_registry.insert(
+                            key,
+                            id,
+                            (erase_single.unwrap())(
+                                Single::new((clone_instance.unwrap())(&instance), clone_instance)
+                                    .with_dispose(dispose),
+                            ),
+                        )
+                    }
+                    ScopeDecision::Evict => self.scoped_registry.remove(&key, id),
+                }
+
+                match behaviour {
+                    Behaviour::CreateThenReturnSingletonOrTransient => {
+                        Resolved::SingletonOrTransient(instance)
+                    }
+                    _ => Resolved::NoReturn,
+                }
+            }
+        }
+    }
+
+    fn inner_resolve<T: 'static>(
+        &mut self,
+        key: Key,
+        behaviour: Behaviour,
+    ) -> Result<Resolved<T>, ResolveError> {
+        let Holder {
+            key,
+            constructor,
+            store,
+            definition,
+        } = match self.before_resolve(key, behaviour) {
+            Ok(o) => return Ok(o),
+            Err(e) => e,
+        };
+
+        let scope = definition.scope;
+
+        let instance = match constructor {
+            Constructor::Async(_) => {
+                let definition = definition.clone();
+                return Err(self.error(ResolveErrorKind::AsyncConstructorInSync(definition)));
+            }
+            Constructor::Sync(constructor) => {
+                self.construct(key.clone(), constructor, store.post_construct.clone())?
+            }
+            Constructor::None => unreachable!(),
+        };
+
+        Ok(self.after_resolve(key, behaviour, scope, instance, store))
+    }
+
+    async fn inner_resolve_async<T: 'static>(
+        &mut self,
+        key: Key,
+        behaviour: Behaviour,
+    ) -> Result<Resolved<T>, ResolveError> {
+        let Holder {
+            key,
+            constructor,
+            store,
+            definition,
+        } = match self.before_resolve(key, behaviour) {
+            Ok(o) => return Ok(o),
+            Err(e) => e,
+        };
+
+        let scope = definition.scope;
+
+        let instance = self
+            .construct_async(key.clone(), constructor, store.post_construct.clone())
+            .await?;
+
+        Ok(self.after_resolve(key, behaviour, scope, instance, store))
+    }
+
+    pub(crate) fn construct<T: 'static>(
+        &mut self,
+        key: Key,
+        constructor: SyncConstructor<T>,
+        post_construct: Option<PostConstruct<T>>,
+    ) -> Result<T, ResolveError> {
+        let mut instance = self.resolve_instance(key.clone(), constructor)?;
+
+        match post_construct {
+            // an async post-construct hook is only set on a provider with an async constructor
+            Some(PostConstruct::Async(_)) => unreachable!(),
+            Some(PostConstruct::Sync(post_construct)) => {
+                self.dependency_chain.push(key.clone());
+                post_construct(&mut instance, self);
+                self.dependency_chain.pop();
+            }
+            None => {}
+        }
+
+        Ok(self.decorate_instance(key, instance))
+    }
+
+    pub(crate) async fn construct_async<T: 'static>(
+        &mut self,
+        key: Key,
+        constructor: Constructor<T>,
+        post_construct: Option<PostConstruct<T>>,
+    ) -> Result<T, ResolveError> {
+        let mut instance = match constructor {
+            Constructor::Async(constructor) => {
+                self.resolve_instance_async(key.clone(), constructor)
+                    .await?
+            }
+            Constructor::Sync(constructor) => self.resolve_instance(key.clone(), constructor)?,
+            Constructor::None => unreachable!(),
+        };
+
+        if let Some(post_construct) = post_construct {
+            self.dependency_chain.push(key.clone());
+
+            match post_construct {
+                PostConstruct::Async(post_construct) => {
+                    instance = post_construct(instance, self).await
+                }
+                PostConstruct::Sync(post_construct) => post_construct(&mut instance, self),
+            }
+
+            self.dependency_chain.pop();
+        }
+
+        Ok(self.decorate_instance(key, instance))
+    }
+
+    fn decorate_instance<T: 'static>(&mut self, key: Key, mut instance: T) -> T {
+        let decorators = {
+            let mut ancestors = self.ancestors().collect::<Vec<_>>();
+            ancestors.reverse();
+
+            ancestors
+                .into_iter()
+                .flat_map(|cx| cx.decorators.iter())
+                .map(|(_, decorator)| decorator)
+                .filter(|decorator| decorator.key() == &key)
+                .filter_map(|decorator| decorator.as_decorate::<T>().cloned())
+                .collect::<Vec<_>>()
+        };
+
+        if decorators.is_empty() {
+            return instance;
+        }
+
+        self.dependency_chain.push(key);
+
+        for decorate in decorators {
+            instance = decorate(instance, self);
+        }
+
+        self.dependency_chain.pop();
+
+        instance
+    }
+
+    fn resolve_instance<T: 'static>(
+        &mut self,
+        key: Key,
+        constructor: SyncConstructor<T>,
+    ) -> Result<T, ResolveError> {
+        let depth = self.dependency_chain.stack.len();
+        self.push_dependency(key.clone())?;
+        let start = self.before_construct(&key, depth);
+
+        let result = constructor(self).map_err(|e| self.constructor_error::<T>(e));
+
+        self.dependency_chain.pop();
+        self.after_construct(&key, depth, start);
+        result
+    }
+
+    async fn resolve_instance_async<T: 'static>(
+        &mut self,
+        key: Key,
+        constructor: AsyncConstructor<T>,
+    ) -> Result<T, ResolveError> {
+        let depth = self.dependency_chain.stack.len();
+        self.push_dependency(key.clone())?;
+        let start = self.before_construct(&key, depth);
+
+        let result = constructor(self)
+            .await
+            .map_err(|e| self.constructor_error::<T>(e));
+
+        self.dependency_chain.pop();
+        self.after_construct(&key, depth, start);
+        result
+    }
+
+    fn before_construct(&mut self, key: &Key, depth: usize) -> Instant {
+        self.startup.resolve(key);
+        self.notify(|observer| observer.on_construct_started(key, depth));
+        self.startup.start()
+    }
+
+    fn after_construct(&mut self, key: &Key, depth: usize, start: Instant) {
+        let (own_time, total_time) = self.startup.finish(start);
+
+        if self.startup.recording {
+            let definition = self
+                .ancestors()
+                .find_map(|cx| cx.provider_registry.inner().get(key))
+                .map(|provider| provider.definition().clone());
+
+            if let Some(definition) = definition {
+                self.startup.record(definition, depth, own_time, total_time);
+            }
+        }
+
+        self.notify(|observer| observer.on_construct_finished(key, total_time, depth));
+    }
+
+    fn constructor_error<T: 'static>(&self, error: BoxError) -> ResolveError {
+        let error = match error.downcast::<ResolveError>() {
+            Ok(e) => return *e,
+            Err(e) => e,
+        };
+
+        let definition = self
+            .dependency_chain
+            .stack
+            .last()
+            .and_then(|key| self.find_provider::<T>(key))
+            .map(|provider| provider.definition().clone())
+            .expect(
+                "unreachable: the provider of the instance being constructed must be registered",
+            );
+
+        self.error(ResolveErrorKind::ConstructorFailed(
+            definition,
+            Shared::from(error),
+        ))
+    }
+
+    fn push_dependency(&mut self, key: Key) -> Result<(), ResolveError> {
+        if self.dependency_chain.contains(&key) {
+            let mut dependency_chain = self.dependency_chain.stack.clone();
+            dependency_chain.push(key.clone());
+
+            return Err(ResolveError::new(
+                ResolveErrorKind::CircularDependency(key),
+                dependency_chain,
+            ));
+        }
+
+        self.dependency_chain.push(key);
+        Ok(())
+    }
+
+    fn error(&self, kind: ResolveErrorKind) -> ResolveError {
+        ResolveError::new(kind, self.dependency_chain.stack.clone())
+    }
+
+    /// Returns the keys of the visible providers of the type,
+    /// sorted by their [`order`](Definition::order), then by registration order, the ancestors first.
+    pub(crate) fn keys<T: 'static>(&self) -> Vec<Key> {
+        let type_id = TypeId::of::<T>();
+
+        let mut keys: Vec<(Key, i32)> = Vec::new();
+
+        let ancestors = self.ancestors().collect::<Vec<_>>();
+
+        for cx in ancestors.into_iter().rev() {
+            for (key, provider) in cx.provider_registry.iter() {
+                if key.ty.id != type_id {
+                    continue;
+                }
+
+                let order = provider.definition().order;
+
+                // a provider in a child context shadows the provider of its ancestors, but keeps its place
+                match keys.iter_mut().find(|(shadowed, _)| shadowed == key) {
+                    Some((_, shadowed)) => *shadowed = order,
+                    None => keys.push((key.clone(), order)),
+                }
+            }
+        }
+
+        keys.sort_by_key(|(_, order)| *order);
+
+        keys.into_iter().map(|(key, _)| key).collect()
+    }
+
+    /// Same as [`Context::keys`], but paired with the name of each provider in a map,
+    /// which is the type name of the qualifier for a qualified provider.
+    fn map_keys<T: 'static>(&self) -> Vec<(Cow<'static, str>, Key)> {
+        self.keys::<T>()
+            .into_iter()
+            .map(|key| match key.qualifier {
+                Some(qualifier) => (Cow::Borrowed(qualifier.name), key),
+                None => (key.name.clone(), key),
+            })
+            .collect()
+    }
+
+    #[track_caller]
+    pub(crate) fn inner_child(self: &Shared<Self>, modules: Vec<ResolveModule>) -> Context {
+        let mut cx = Context {
+            allow_override: self.allow_override,
+            allow_only_single_eager_create: self.allow_only_single_eager_create,
+            eager_create: self.eager_create,
+            concurrent_eager_create: self.concurrent_eager_create,
+            profiles: self.profiles.clone(),
+            parent: Some(Shared::clone(self)),
+            ..Default::default()
+        };
+
+        cx.inner_load_modules(modules);
+
+        cx
+    }
+
+    fn ancestors(&self) -> impl Iterator<Item = &Context> {
+        iter::successors(Some(self), |cx| cx.parent.as_deref())
+    }
+
+    /// Returns the providers registered in the context and its ancestors,
+    /// a provider in a child context shadows the provider with the same key of its ancestors.
+    fn visible_providers(&self) -> HashMap<&Key, &DynProvider> {
+        let mut providers = HashMap::new();
+
+        for cx in self.ancestors() {
+            for (key, provider) in cx.provider_registry.iter() {
+                providers.entry(key).or_insert(provider);
+            }
+        }
+
+        providers
+    }
+
+    /// Returns the given key, or the key of the primary provider of the type
+    /// if the key is neither named nor qualified and no provider is registered for it.
+    pub(crate) fn key(&self, key: Key) -> Key {
+        if !key.name.is_empty()
+            || key.qualifier.is_some()
+            || self
+                .ancestors()
+                .any(|cx| cx.provider_registry.contains(&key))
+        {
+            return key;
+        }
+
+        self.find_primary(&key.ty).cloned().unwrap_or(key)
+    }
+
+    /// Returns the key of the visible primary provider of the type, the nearest context first.
+    fn find_primary(&self, ty: &Type) -> Option<&Key> {
+        let ancestors = self.ancestors().collect::<Vec<_>>();
+
+        ancestors.iter().enumerate().find_map(|(depth, cx)| {
+            cx.provider_registry
+                .iter()
+                .find(|(key, provider)| {
+                    key.ty == *ty
+                        && key.qualifier.is_none()
+                        && provider.definition().primary
+                        // a provider in a child context shadows the provider of its ancestors
+                        && !ancestors[..depth]
+                            .iter()
+                            .any(|child| child.provider_registry.contains(key))
+                })
+                .map(|(key, _)| key)
+        })
+    }
+
+    pub(crate) fn find_provider<T: 'static>(&self, key: &Key) -> Option<&Provider<T>> {
+        self.ancestors()
+            .find_map(|cx| cx.provider_registry.get(key))
+    }
+
+    fn find_transient_provider<T: 'static>(
+        &self,
+        name: Cow<'static, str>,
+    ) -> Result<&Provider<T>, ResolveError> {
+        let key = self.key(Key::new::<T>(name));
+
+        let Some(provider) = self.find_provider::<T>(&key) else {
+            return Err(self.error(ResolveErrorKind::NotFoundProvider(key)));
+        };
+
+        if provider.definition().scope != Scope::Transient {
+            let definition = provider.definition().clone();
+            return Err(self.error(ResolveErrorKind::NotTransient(definition)));
+        }
+
+        Ok(provider)
+    }
+
+    fn find_custom_scope(&self, id: TypeId) -> Option<&dyn CustomScope> {
+        self.ancestors()
+            .find_map(|cx| cx.custom_scopes.get(&id))
+            .map(Box::as_ref)
+    }
+
+    /// Notifies the observers registered in the context and its ancestors.
+    fn notify(&self, f: impl Fn(&dyn ContextObserver)) {
+        self.ancestors()
+            .flat_map(|cx| cx.observers.iter())
+            .for_each(|observer| f(observer.as_ref()));
+    }
+
+    #[track_caller]
+    fn insert_provider(&mut self, provider: DynProvider) {
+        let definition = provider.definition().clone();
+        let allow_override = self.allow_override;
+
+        // checked before the registry is withdrawn from the handle, so the handle is not left without it
+        if !allow_override && self.provider_registry.contains(&definition.key) {
+            panic!(
+                "already existing a provider with the same `key`: {:?}",
+                definition
+            );
+        }
+
+        if self.update_providers(|registry| registry.insert(provider, allow_override)) {
+            self.notify(|observer| observer.on_provider_overridden(&definition));
+        } else {
+            self.notify(|observer| observer.on_provider_inserted(&definition));
+        }
+    }
+
+    fn remove_provider(&mut self, key: &Key) {
+        self.update_providers(|registry| registry.remove(key));
+    }
+
+    fn insert_single(&mut self, key: Key, single: DynSingle) {
+        // the instances cached for a handle are kept by the handle, until the context it belongs to takes them
+        if let Some(Handle::Borrowed(handle)) = &self.handle {
+            handle.lock_singles().insert(key.clone(), single.share());
+        }
+
+        self.update_singles(|registry| registry.insert(key, single));
+    }
+
+    /// Registers the decorators of the module, replacing the ones registered by a previous load of the module.
+    fn load_decorators(&mut self, module: Type, decorators: Vec<DynDecorator>) {
+        self.update_decorators(|loaded| {
+            loaded.retain(|(loaded, _)| loaded != &Some(module));
+            loaded.extend(
+                decorators
+                    .into_iter()
+                    .map(|decorator| (Some(module), decorator)),
+            );
+        });
+    }
+
+    fn update_providers<R>(&mut self, f: impl FnOnce(&mut ProviderRegistry) -> R) -> R {
+        self.update(|cx| f(Shared::make_mut(&mut cx.provider_registry)))
+    }
+
+    fn update_singles<R>(&mut self, f: impl FnOnce(&mut SingleRegistry) -> R) -> R {
+        self.update(|cx| f(Shared::make_mut(&mut cx.single_registry)))
+    }
+
+    fn update_decorators<R>(
+        &mut self,
+        f: impl FnOnce(&mut Vec<(Option<Type>, DynDecorator)>) -> R,
+    ) -> R {
+        self.update(|cx| f(Shared::make_mut(&mut cx.decorators)))
+    }
+
+    /// Mutates the registries of the context, withdrawing them from the handle of the context meanwhile,
+    /// so they are not copied on write, then publishes them to the handle again.
+    ///
+    /// `f` must not call code of the user, which could use the handle.
+    fn update<R>(&mut self, f: impl FnOnce(&mut Context) -> R) -> R {
+        let Some(Handle::Own(handle)) = &self.handle else {
+            return f(self);
+        };
+
+        let handle = Shared::clone(handle);
+        let mut view = handle.lock_view();
+
+        *view = None;
+        let result = f(self);
+        *view = Some(Shared::new(self.view()));
+
+        result
+    }
+
+    /// Returns a context that shares the registries of this context, with the same parent,
+    /// which is not mutated but used as the parent of the contexts that resolve instances without this context.
+    fn view(&self) -> Context {
+        Context {
+            allow_override: self.allow_override,
+            allow_only_single_eager_create: self.allow_only_single_eager_create,
+            eager_create: self.eager_create,
+            concurrent_eager_create: self.concurrent_eager_create,
+            profiles: self.profiles.clone(),
+            #[cfg(feature = "config")]
+            config: Shared::clone(&self.config),
+            single_registry: Shared::clone(&self.single_registry),
+            provider_registry: Shared::clone(&self.provider_registry),
+            custom_scopes: Shared::clone(&self.custom_scopes),
+            scoped_registry: Default::default(),
+            observers: Shared::clone(&self.observers),
+            decorators: Shared::clone(&self.decorators),
+            loaded_modules: Default::default(),
+            conditional_providers: Default::default(),
+            eager_create_functions: Default::default(),
+            dependency_chain: Default::default(),
+            unchecked_dependencies: Default::default(),
+            startup: Default::default(),
+            parent: self.parent.clone(),
+            handle: None,
+        }
+    }
+
+    /// Returns the handle of the context, which is captured by the handles that resolve instances on demand,
+    /// e.g. [`Lazy`], creating it on first use.
+    ///
+    /// A context that resolves an instance for a handle returns that handle.
+    pub(crate) fn handle(&mut self) -> Shared<ContextHandle> {
+        if let Some(Handle::Own(handle) | Handle::Borrowed(handle)) = &self.handle {
+            return Shared::clone(handle);
+        }
+
+        let handle = Shared::new(ContextHandle::new(self.view()));
+        self.handle = Some(Handle::Own(Shared::clone(&handle)));
+
+        handle
+    }
+
+    /// Returns a context that resolves instances for the handle, i.e. a child of the view published to the handle,
+    /// which shares the instances cached by the handle.
+    #[track_caller]
+    pub(crate) fn for_handle(handle: &Shared<ContextHandle>) -> Context {
+        let mut cx = handle.view().inner_child(Vec::new());
+
+        cx.single_registry = Shared::new(handle.lock_singles().clone());
+        cx.handle = Some(Handle::Borrowed(Shared::clone(handle)));
+
+        cx
+    }
+
+    /// Takes the instances cached by the handle of the context, so they are cached and disposed by the context.
+    fn take_handle_singles(&mut self) {
+        let singles = match &self.handle {
+            Some(Handle::Own(handle)) => handle.lock_singles().drain(),
+            _ => return,
+        };
+
+        if singles.is_empty() {
+            return;
+        }
+
+        self.update_singles(|registry| {
+            for (key, single) in singles {
+                // the context has created the instance meanwhile
+                if !registry.contains(&key) {
+                    registry.insert(key, single);
+                }
+            }
+        });
+    }
+
+    fn contains_single_key(&self, key: &Key) -> bool {
+        for cx in self.ancestors() {
+            if cx.single_registry.contains(key) {
+                return true;
+            }
+
+            // a provider in a child context shadows the instances of its ancestors
+            if cx.provider_registry.contains(key) {
+                return false;
+            }
+        }
+
+        false
+    }
+
+    pub(crate) fn find_single<T: 'static>(&self, key: &Key) -> Option<&Single<T>> {
+        for cx in self.ancestors() {
+            if let Some(single) = cx.single_registry.get(key) {
+                return single.as_single();
+            }
+
+            // a provider in a child context shadows the instances of its ancestors
+            if cx.provider_registry.contains(key) {
+                return None;
+            }
+        }
+
+        None
+    }
+}
+
+#[derive(Clone, Copy)]
+enum Behaviour {
+    CreateThenReturnSingletonOrTransient,
+    JustCreateAllScopeForEagerCreate,
+    JustCreateSingletonOrSingleOwner,
+}
+
+enum Resolved<T> {
+    NotFoundProvider(Key),
+
+    SingletonOrTransient(T),
+    NotSingletonOrTransient(Definition),
+
+    NoReturn,
+
+    NotSingletonOrSingleOwner(Definition),
+}
+
+struct Holder<'a, T> {
+    key: Key,
+    constructor: Constructor<T>,
+    store: Store<T>,
+    definition: &'a Definition,
+}
+
+struct Store<T> {
+    clone_instance: Option<fn(&T) -> T>,
+    erase_single: Option<fn(Single<T>) -> DynSingle>,
+    post_construct: Option<PostConstruct<T>>,
+    dispose: Option<Dispose<T>>,
+    partition: Option<Partition>,
+}
+
+struct Partition {
+    id: u64,
+    decision: ScopeDecision,
+}
+
+fn validate_dependency(
+    providers: &HashMap<&Key, &DynProvider>,
+    primary: Option<&Key>,
+    dependent: &Definition,
+    dependency: &Dependency,
+) -> Vec<ValidationErrorKind> {
+    let in_sync = dependent.color == Some(Color::Sync);
+
+    if matches!(dependency.kind, DependencyKind::Vec | DependencyKind::Map) {
+        return providers
+            .values()
+            .map(|provider| provider.definition())
+            .filter(|definition| definition.key.ty == dependency.key.ty)
+            .filter(|definition| in_sync && definition.color == Some(Color::Async))
+            .map(|definition| ValidationErrorKind::AsyncConstructorInSync(definition.clone()))
+            .collect();
+    }
+
+    let provider = providers
+        .get(&dependency.key)
+        .or_else(|| primary.and_then(|key| providers.get(key)));
+
+    let Some(provider) = provider else {
+        if matches!(
+            dependency.kind,
+            DependencyKind::Option | DependencyKind::Default
+        ) {
+            return Vec::new();
+        }
+
+        let mut candidates = providers
+            .keys()
+            .filter(|key| key.ty == dependency.key.ty)
+            .map(|key| (*key).clone())
+            .collect::<Vec<_>>();
+
+        return if dependency.key.name.is_empty()
+            && dependency.key.qualifier.is_none()
+            && candidates.len() > 1
+        {
+            candidates.sort();
+            vec![ValidationErrorKind::Ambiguous(candidates)]
+        } else {
+            vec![ValidationErrorKind::NotFoundProvider]
+        };
+    };
+
+    let definition = provider.definition();
+
+    let mut errors = Vec::new();
+
+    let scope_matches = match (dependency.by_ref, dependency.kind) {
+        (true, _) => matches!(definition.scope, Scope::Singleton | Scope::SingleOwner),
+        (false, DependencyKind::Factory | DependencyKind::AsyncFactory) => {
+            definition.scope == Scope::Transient
+        }
+        (false, _) => definition.scope != Scope::SingleOwner,
+    };
+
+    if !scope_matches {
+        errors.push(ValidationErrorKind::ScopeMismatch(definition.clone()));
+    }
+
+    let taken_in_sync = match dependency.kind {
+        DependencyKind::Lazy | DependencyKind::AsyncFactory => false,
+        DependencyKind::Factory => true,
+        _ => in_sync,
+    };
+
+    if taken_in_sync && definition.color == Some(Color::Async) {
+        errors.push(ValidationErrorKind::AsyncConstructorInSync(
+            definition.clone(),
+        ));
+    }
+
+    errors
+}
+
+#[track_caller]
+fn dispose_singles(singles: Vec<(Key, DynSingle)>) {
+    if let Some((key, _)) = singles
+        .iter()
+        .find(|(_, single)| single.dispose_color() == Some(Color::Async))
+    {
+        panic!(
+            "unable to call an async dispose hook in a sync context for: {:?}
+
+please use instead:
+1. Context::shutdown_async().await
+2. Context::unload_modules_async(modules).await
+",
+            key
+        )
+    }
+
+    singles.into_iter().for_each(|(_, single)| {
+        let future = single.dispose();
+        debug_assert!(future.is_none());
+    });
+}
+
+async fn dispose_singles_async(singles: Vec<(Key, DynSingle)>) {
+    for (_, single) in singles {
+        if let Some(future) = single.dispose() {
+            future.await;
+        }
+    }
+}
+
+#[track_caller]
+#[inline(always)]
+pub(crate) fn unwrap_or_panic<T>(result: Result<T, ResolveError>) -> T {
+    match result {
+        Ok(o) => o,
+        Err(e) => panic!("{}", e),
+    }
+}
+
+/// Returns the group of the provider, i.e. the provider and the providers bound from it.
+/// The conditional providers whose conditions are being evaluated,
+/// any of which can be tentatively inserted into the registry while the other conditions are evaluated.
+struct TentativeProviders {
+    // the providers that are not inserted into the registry
+    slots: Vec<Option<DynProvider>>,
+    keys: Vec<Key>,
+    inserted: Vec<bool>,
+    // the providers of the same group as each provider, including itself
+    groups: Vec<Shared<[usize]>>,
+    // the providers of each key, in load order
+    providers_by_key: HashMap<Key, Vec<usize>>,
+    // the provider of each key in the registry, `None` for the provider the registry had before
+    occupants: HashMap<Key, Option<usize>>,
+    // the providers the registry had before, while they are replaced by a tentative provider
+    replaced: HashMap<Key, Option<DynProvider>>,
+}
+
+impl TentativeProviders {
+    fn new(providers: Vec<DynProvider>) -> Self {
+        let keys = providers
+            .iter()
+            .map(|provider| provider.key().clone())
+            .collect::<Vec<_>>();
+
+        let mut members = HashMap::<_, Vec<usize>>::new();
+        let mut providers_by_key = HashMap::<_, Vec<usize>>::new();
+
+        for (index, provider) in providers.iter().enumerate() {
+            members
+                .entry(group(provider.definition()))
+                .or_default()
+                .push(index);
+            providers_by_key
+                .entry(keys[index].clone())
+                .or_default()
+                .push(index);
+        }
+
+        let members = members
+            .into_values()
+            .map(Shared::<[usize]>::from)
+            .collect::<Vec<_>>();
+
+        let mut groups = vec![Shared::<[usize]>::from([]); providers.len()];
+
+        for group in &members {
+            for &index in group.iter() {
+                groups[index] = Shared::clone(group);
+            }
+        }
+
+        Self {
+            inserted: vec![false; providers.len()],
+            slots: providers.into_iter().map(Some).collect(),
+            keys,
+            groups,
+            providers_by_key,
+            occupants: HashMap::new(),
+            replaced: HashMap::new(),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.slots.len()
+    }
+
+    fn group(&self, index: usize) -> Shared<[usize]> {
+        Shared::clone(&self.groups[index])
+    }
+
+    fn is_inserted(&self, index: usize) -> bool {
+        self.inserted[index]
+    }
+
+    /// Returns the provider at `index`, which must not be inserted.
+    fn provider(&self, index: usize) -> &DynProvider {
+        self.slots[index]
+            .as_ref()
+            .expect("unreachable: the provider is inserted into the registry")
+    }
+
+    /// Returns the definition of the provider at `index`, which must not be inserted.
+    fn definition(&self, index: usize) -> &Definition {
+        self.provider(index).definition()
+    }
+
+    /// Inserts or removes the provider at `index`, the registry then holds the last inserted provider of its key
+    /// in load order, or the provider it had before if none is inserted.
+    fn set_inserted(&mut self, registry: &mut ProviderRegistry, index: usize, inserted: bool) {
+        if self.inserted[index] == inserted {
+            return;
+        }
+
+        self.inserted[index] = inserted;
+
+        let key = &self.keys[index];
+
+        let desired = self.providers_by_key[key]
+            .iter()
+            .rev()
+            .copied()
+            .find(|&i| self.inserted[i]);
+
+        let occupant = self.occupants.get(key).copied().flatten();
+
+        if desired == occupant {
+            return;
+        }
 
-enum Resolved<T> {
-    NotFoundProvider(Key),
+        let incoming = match desired {
+            Some(i) => self.slots[i].take(),
+            None => self.replaced.remove(key).flatten(),
+        };
 
-    SingletonOrTransient(T),
-    NotSingletonOrTransient(Definition),
+        let outgoing = match incoming {
+            Some(provider) => registry.replace(provider),
+            None => registry.remove(key),
+        };
 
-    NoReturn,
+        match occupant {
+            Some(i) => self.slots[i] = outgoing,
+            None => {
+                self.replaced.insert(key.clone(), outgoing);
+            }
+        }
 
-    NotSingletonOrSingleOwner(Definition),
-}
+        self.occupants.insert(key.clone(), desired);
+    }
 
-struct Holder<'a, T> {
-    key: Key,
-    constructor: Constructor<T>,
-    clone_instance: Option<fn(&T) -> T>,
-    definition: &'a Definition,
-}
+    /// Removes all the providers from the registry, restoring the providers it had before.
+    fn remove_all(&mut self, registry: &mut ProviderRegistry) {
+        for index in 0..self.len() {
+            self.set_inserted(registry, index, false);
+        }
+    }
 
-#[inline(always)]
-fn no_provider_panic(key: Key) -> ! {
-    panic!("no provider registered for: {:?}", key)
-}
+    fn into_providers(mut self, registry: &mut ProviderRegistry) -> Vec<DynProvider> {
+        self.remove_all(registry);
 
-#[inline(always)]
-fn not_singleton_or_single_owner_panic(definition: Definition) -> ! {
-    panic!(
-        "registered provider is not `Singleton` or `SingleOwner` for: {:?}",
-        definition
-    )
+        self.slots
+            .into_iter()
+            .map(|provider| {
+                provider.expect("unreachable: all the providers are removed from the registry")
+            })
+            .collect()
+    }
 }
 
-#[inline(always)]
-fn not_singleton_or_transient_panic(definition: Definition) -> ! {
-    panic!(
-        "registered provider is not `Singleton` or `Transient` for: {:?}",
-        definition
+fn group(definition: &Definition) -> (Type, Cow<'static, str>, Option<Type>) {
+    (
+        definition.origin.unwrap_or(definition.key.ty),
+        definition.key.name.clone(),
+        definition.key.qualifier,
     )
 }
 
@@ -2217,6 +5501,12 @@ pub struct ContextOptions {
     allow_override: bool,
     allow_only_single_eager_create: bool,
     eager_create: bool,
+    concurrent_eager_create: bool,
+    profiles: Vec<Cow<'static, str>>,
+    #[cfg(feature = "config")]
+    config_sources: Vec<ConfigSource>,
+    custom_scopes: HashMap<TypeId, Box<dyn CustomScope>>,
+    observers: Vec<Box<dyn ContextObserver>>,
     providers: Vec<DynProvider>,
     singles: Vec<DynSingle>,
 }
@@ -2227,6 +5517,12 @@ impl Default for ContextOptions {
             allow_override: true,
             allow_only_single_eager_create: true,
             eager_create: Default::default(),
+            concurrent_eager_create: Default::default(),
+            profiles: Default::default(),
+            #[cfg(feature = "config")]
+            config_sources: Default::default(),
+            custom_scopes: Default::default(),
+            observers: Default::default(),
             providers: Default::default(),
             singles: Default::default(),
         }
@@ -2297,6 +5593,293 @@ impl ContextOptions {
         self
     }
 
+    /// Sets the option for whether the context should run the async constructors of independent eagerly created instances concurrently.
+    ///
+    /// This option, when true, makes [`Context::flush_async`] (and so [`ContextOptions::create_async`])
+    /// group consecutive providers with async constructors that will be eagerly created,
+    /// whose [`dependencies`](crate::Provider::dependencies) that are cached in the context, i.e. [`Singleton`](crate::Scope::Singleton)
+    /// and [`SingleOwner`](crate::Scope::SingleOwner) dependencies, have already been created,
+    /// and awaits the constructors of each group concurrently instead of one after another.
+    /// The instances are stored in the context in the same order as sequential creation.
+    ///
+    /// A provider that does not declare its dependencies, e.g. a provider created by [`singleton_async`](crate::singleton_async)
+    /// without setting the dependencies, is always created on its own. A constructor that resolves an instance cached in the context
+    /// which is not declared as its dependency may construct it a second time,
+    /// which is reported as [`ResolveErrorKind::CreatedConcurrently`], the first instance is kept.
+    ///
+    /// If a constructor returns an error, the error of the first failed provider in the group is returned.
+    /// If a constructor panics, the panic is resumed with a message containing the definition of the provider.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rudi::{Context, Singleton};
+    ///
+    /// #[Singleton(eager_create)]
+    /// async fn Database() -> String {
+    ///     // connect to the database
+    ///     "database".to_string()
+    /// }
+    ///
+    /// #[Singleton(eager_create)]
+    /// async fn Cache() -> i32 {
+    ///     // connect to the cache
+    ///     42
+    /// }
+    ///
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let cx = Context::options()
+    ///         .concurrent_eager_create(true)
+    ///         .auto_register_async()
+    ///         .await;
+    ///
+    ///     assert!(cx.concurrent_eager_create());
+    ///     assert!(cx.contains_single::<String>());
+    ///     assert!(cx.contains_single::<i32>());
+    /// }
+    /// ```
+    pub fn concurrent_eager_create(mut self, concurrent_eager_create: bool) -> Self {
+        self.concurrent_eager_create = concurrent_eager_create;
+        self
+    }
+
+    /// Sets the active profiles of the context, default is no profile.
+    ///
+    /// A provider with profiles, e.g. `#[Singleton(profile = "test")]` or [`SingletonProvider::profiles`](crate::SingletonProvider::profiles),
+    /// is only inserted into the context when one of its profiles is active, other providers are always inserted.
+    ///
+    /// The active profiles are also active in the children of the context,
+    /// and can be queried by [`Context::profiles`] and [`Context::is_profile_active`], e.g. in conditions.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rudi::{Context, Singleton};
+    ///
+    /// #[Singleton(profile = "test")]
+    /// fn MemoryUrl() -> String {
+    ///     "sqlite::memory:".to_string()
+    /// }
+    ///
+    /// #[Singleton(profile = ["dev", "prod"])]
+    /// fn FileUrl() -> String {
+    ///     "sqlite://data.db".to_string()
+    /// }
+    ///
+    /// # fn main() {
+    /// let mut cx = Context::options().profiles(["test"]).auto_register();
+    /// assert_eq!(cx.resolve::<String>(), "sqlite::memory:");
+    ///
+    /// let mut cx = Context::options().profiles(["prod"]).auto_register();
+    /// assert_eq!(cx.resolve::<String>(), "sqlite://data.db");
+    /// # }
+    /// ```
+    pub fn profiles<I, P>(mut self, profiles: I) -> Self
+    where
+        I: IntoIterator<Item = P>,
+        P: Into<Cow<'static, str>>,
+    {
+        self.profiles = profiles.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Registers a configuration file as a source of the [`Config`] of the context.
+    ///
+    /// The format of the file is determined by its extension, `.json` or `.toml`.
+    /// The sources are loaded in the order in which they are registered when the context is created,
+    /// and a value of a later source overrides the value with the same key of an earlier source.
+    ///
+    /// # Panics
+    ///
+    /// Creating the context panics if the file cannot be read or parsed.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rudi::{modules, Context};
+    ///
+    /// # fn main() {
+    /// let path = std::env::temp_dir().join("rudi-config-file-example.toml");
+    /// std::fs::write(&path, "[database]\nurl = \"postgres://localhost\"\nport = 5432\n").unwrap();
+    ///
+    /// let cx = Context::options()
+    ///     .config_file(&path)
+    ///     .config_values([("database.port", 6543)])
+    ///     .create(modules![]);
+    ///
+    /// assert_eq!(cx.config().get::<String>("database.url").unwrap(), "postgres://localhost");
+    /// assert_eq!(cx.config().get::<u16>("database.port").unwrap(), 6543);
+    /// # }
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "config")))]
+    #[cfg(feature = "config")]
+    pub fn config_file<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.config_sources.push(ConfigSource::File(path.into()));
+        self
+    }
+
+    /// Registers the environment variables with the given prefix as a source of the [`Config`] of the context.
+    ///
+    /// A variable named `{prefix}_{key}` provides the value for the lowercased key,
+    /// and `__` separates the segments of a nested key,
+    /// e.g. `APP_DATABASE__MAX_CONNECTIONS` provides the value for `database.max_connections` with the prefix `APP`.
+    ///
+    /// See [`ContextOptions::config_file`] for the order of the sources.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rudi::{modules, Context};
+    ///
+    /// # fn main() {
+    /// std::env::set_var("EXAMPLE_DATABASE__MAX_CONNECTIONS", "16");
+    ///
+    /// let cx = Context::options()
+    ///     .config_env("EXAMPLE")
+    ///     .create(modules![]);
+    ///
+    /// assert_eq!(cx.config().get::<u32>("database.max_connections").unwrap(), 16);
+    /// # }
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "config")))]
+    #[cfg(feature = "config")]
+    pub fn config_env<S: Into<String>>(mut self, prefix: S) -> Self {
+        self.config_sources.push(ConfigSource::Env(prefix.into()));
+        self
+    }
+
+    /// Registers the given values as a source of the [`Config`] of the context,
+    /// each value is stored under its dotted key, e.g. `database.url`.
+    ///
+    /// See [`ContextOptions::config_file`] for the order of the sources.
+    ///
+    /// # Panics
+    ///
+    /// Panics if a value cannot be serialized.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::collections::HashMap;
+    ///
+    /// use rudi::{modules, Context};
+    ///
+    /// # fn main() {
+    /// let cx = Context::options()
+    ///     .config_values(HashMap::from([("redis.url", "redis://localhost")]))
+    ///     .config_values([("redis.pool", 8)])
+    ///     .create(modules![]);
+    ///
+    /// assert_eq!(cx.config().get::<String>("redis.url").unwrap(), "redis://localhost");
+    /// assert_eq!(cx.config().get::<u8>("redis.pool").unwrap(), 8);
+    /// # }
+    /// ```
+    #[cfg_attr(docsrs, doc(cfg(feature = "config")))]
+    #[cfg(feature = "config")]
+    #[track_caller]
+    pub fn config_values<I, K, V>(mut self, values: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: Into<String>,
+        V: serde::Serialize,
+    {
+        let values = values
+            .into_iter()
+            .map(|(key, value)| {
+                let key = key.into();
+
+                match serde_json::to_value(value) {
+                    Ok(value) => (key, value),
+                    Err(e) => panic!(
+                        "failed to serialize the configuration value for: `{}`, {}",
+                        key, e
+                    ),
+                }
+            })
+            .collect();
+
+        self.config_sources.push(ConfigSource::Values(values));
+        self
+    }
+
+    /// Registers a [`CustomScope`] in the context,
+    /// which can be used by providers with [`SingletonProvider::scope`](crate::SingletonProvider::scope),
+    /// [`SingletonAsyncProvider::scope`](crate::SingletonAsyncProvider::scope) or `#[Singleton(scope = S)]`.
+    ///
+    /// The custom scopes are also available in the children of the context.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rudi::{modules, providers, singleton, Context, CustomScope, DynProvider, Module};
+    ///
+    /// struct MyScope;
+    ///
+    /// impl CustomScope for MyScope {}
+    ///
+    /// struct MyModule;
+    ///
+    /// impl Module for MyModule {
+    ///     fn providers() -> Vec<DynProvider> {
+    ///         providers![singleton(|_| 42).scope::<MyScope>()]
+    ///     }
+    /// }
+    ///
+    /// # fn main() {
+    /// let mut cx = Context::options()
+    ///     .custom_scope(MyScope)
+    ///     .create(modules![MyModule]);
+    ///
+    /// assert_eq!(cx.resolve::<i32>(), 42);
+    /// # }
+    /// ```
+    pub fn custom_scope<S: CustomScope>(mut self, scope: S) -> Self {
+        self.custom_scopes
+            .insert(TypeId::of::<S>(), Box::new(scope));
+        self
+    }
+
+    /// Registers a [`ContextObserver`] in the context,
+    /// which is notified of the lifecycle events of the context, e.g. a provider is inserted or an instance is constructed.
+    ///
+    /// Multiple observers are notified in registration order.
+    /// The observers are also notified of the events of the children of the context.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use std::sync::{
+    ///     atomic::{AtomicUsize, Ordering},
+    ///     Arc,
+    /// };
+    ///
+    /// use rudi::{modules, Context, ContextObserver, Type};
+    ///
+    /// #[derive(Clone, Default)]
+    /// struct ModuleCounter(Arc<AtomicUsize>);
+    ///
+    /// impl ContextObserver for ModuleCounter {
+    ///     fn on_module_loaded(&self, _module: &Type) {
+    ///         self.0.fetch_add(1, Ordering::SeqCst);
+    ///     }
+    /// }
+    ///
+    /// # fn main() {
+    /// let counter = ModuleCounter::default();
+    ///
+    /// let _cx = Context::options()
+    ///     .observer(counter.clone())
+    ///     .auto_register();
+    ///
+    /// assert_eq!(counter.0.load(Ordering::SeqCst), 1);
+    /// # }
+    /// ```
+    pub fn observer<O: ContextObserver>(mut self, observer: O) -> Self {
+        self.observers.push(Box::new(observer));
+        self
+    }
+
     /// Appends a standalone [`Singleton`](crate::Scope::Singleton) instance to the context with default name `""`.
     ///
     /// # Example
@@ -2311,7 +5894,7 @@ impl ContextOptions {
     /// ```
     pub fn singleton<T>(self, instance: T) -> Self
     where
-        T: 'static + Clone,
+        T: 'static + Clone + MaybeSendSync,
     {
         self.singleton_with_name(instance, "")
     }
@@ -2335,7 +5918,7 @@ impl ContextOptions {
     /// ```
     pub fn singleton_with_name<T, N>(mut self, instance: T, name: N) -> Self
     where
-        T: 'static + Clone,
+        T: 'static + Clone + MaybeSendSync,
         N: Into<Cow<'static, str>>,
     {
         let provider = Provider::<T>::never_construct(name.into(), Scope::Singleton).into();
@@ -2366,7 +5949,7 @@ impl ContextOptions {
     /// ```
     pub fn single_owner<T>(self, instance: T) -> Self
     where
-        T: 'static,
+        T: 'static + MaybeSendSync,
     {
         self.single_owner_with_name(instance, "")
     }
@@ -2393,7 +5976,7 @@ impl ContextOptions {
     /// ```
     pub fn single_owner_with_name<T, N>(mut self, instance: T, name: N) -> Self
     where
-        T: 'static,
+        T: 'static + MaybeSendSync,
         N: Into<Cow<'static, str>>,
     {
         let provider = Provider::<T>::never_construct(name.into(), Scope::SingleOwner).into();
@@ -2414,6 +5997,12 @@ impl ContextOptions {
             allow_override,
             allow_only_single_eager_create,
             eager_create,
+            concurrent_eager_create,
+            profiles,
+            #[cfg(feature = "config")]
+            config_sources,
+            custom_scopes,
+            observers,
             providers,
             singles,
         } = self;
@@ -2422,6 +6011,15 @@ impl ContextOptions {
             allow_override,
             allow_only_single_eager_create,
             eager_create,
+            concurrent_eager_create,
+            profiles,
+            #[cfg(feature = "config")]
+            config: match Config::load(config_sources) {
+                Ok(config) => Shared::new(config),
+                Err(e) => panic!("{}", e),
+            },
+            custom_scopes: Shared::new(custom_scopes),
+            observers: Shared::new(observers),
             ..Default::default()
         };
 
@@ -2431,8 +6029,8 @@ impl ContextOptions {
                 .zip(singles)
                 .for_each(|(provider, single)| {
                     let key = provider.key().clone();
-                    cx.provider_registry.insert(provider, allow_override);
-                    cx.single_registry.insert(key, single);
+                    cx.insert_provider(provider);
+                    cx.update_singles(|registry| registry.insert(key, single));
                 });
         }
 
@@ -2443,18 +6041,21 @@ impl ContextOptions {
 
     #[track_caller]
     fn inner_create_with_modules(self, modules: Vec<ResolveModule>) -> Context {
-        self.inner_create(|cx| cx.load_modules(modules))
+        self.inner_create(|cx| cx.inner_load_modules(modules))
     }
 
     #[cfg(feature = "auto-register")]
     #[track_caller]
     fn inner_create_with_auto(self) -> Context {
-        use crate::AutoRegisterModule;
+        use super::AutoRegisterModule;
 
         self.inner_create(|cx| {
-            let module = ResolveModule::new::<AutoRegisterModule>();
-            cx.loaded_modules.push(module.ty());
-            cx.load_providers(module.eager_create(), module.providers())
+            let mut module = ResolveModule::new::<AutoRegisterModule>();
+            let ty = module.ty();
+            cx.loaded_modules.push(ty);
+            cx.load_decorators(ty, module.decorators());
+            cx.load_providers(module.eager_create(), module.providers());
+            cx.notify(|observer| observer.on_module_loaded(&ty));
         })
     }
 
@@ -2465,6 +6066,7 @@ impl ContextOptions {
     /// - Panics if there are multiple providers with the same key and the context's [`allow_override`](Context::allow_override) is false.
     /// - Panics if there is a provider whose constructor is async and the provider will be eagerly created.
     /// - Panics if there is a provider that panics on construction.
+    /// - Panics if there is a provider whose constructor returns an error.
     ///
     /// # Example
     ///
@@ -2489,9 +6091,7 @@ impl ContextOptions {
     /// ```
     #[track_caller]
     pub fn create(self, modules: Vec<ResolveModule>) -> Context {
-        let mut cx = self.inner_create_with_modules(modules);
-        cx.flush();
-        cx
+        unwrap_or_panic(self.try_create(modules))
     }
 
     /// Creates a new context with the [`AutoRegisterModule`].
@@ -2505,15 +6105,14 @@ impl ContextOptions {
     /// - Panics if there are multiple providers with the same key and the context's [`allow_override`](Context::allow_override) is false.
     /// - Panics if there is a provider whose constructor is async and the provider will be eagerly created.
     /// - Panics if there is a provider that panics on construction.
+    /// - Panics if there is a provider whose constructor returns an error.
     ///
     /// [`AutoRegisterModule`]: crate::AutoRegisterModule
     #[cfg_attr(docsrs, doc(cfg(feature = "auto-register")))]
     #[cfg(feature = "auto-register")]
     #[track_caller]
     pub fn auto_register(self) -> Context {
-        let mut cx = self.inner_create_with_auto();
-        cx.flush();
-        cx
+        unwrap_or_panic(self.try_auto_register())
     }
 
     /// Async version of [`ContextOptions::create`].
@@ -2527,10 +6126,9 @@ impl ContextOptions {
     ///
     /// - Panics if there are multiple providers with the same key and the context's [`allow_override`](Context::allow_override) is false.
     /// - Panics if there is a provider that panics on construction.
+    /// - Panics if there is a provider whose constructor returns an error.
     pub async fn create_async(self, modules: Vec<ResolveModule>) -> Context {
-        let mut cx = self.inner_create_with_modules(modules);
-        cx.flush_async().await;
-        cx
+        unwrap_or_panic(self.try_create_async(modules).await)
     }
 
     /// Async version of [`ContextOptions::auto_register`].
@@ -2544,47 +6142,129 @@ impl ContextOptions {
     ///
     /// - Panics if there are multiple providers with the same key and the context's [`allow_override`](Context::allow_override) is false.
     /// - Panics if there is a provider that panics on construction.
+    /// - Panics if there is a provider whose constructor returns an error.
     #[cfg_attr(docsrs, doc(cfg(feature = "auto-register")))]
     #[cfg(feature = "auto-register")]
     pub async fn auto_register_async(self) -> Context {
+        unwrap_or_panic(self.try_auto_register_async().await)
+    }
+
+    /// Creates a new context with the given modules,
+    /// or returns a [`ResolveError`] if a provider that is eagerly created cannot be resolved.
+    ///
+    /// See [`ContextOptions::create`] for more details.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if there are multiple providers with the same key and the context's [`allow_override`](Context::allow_override) is false.
+    /// - Panics if there is a provider whose constructor is async and the provider will be eagerly created.
+    /// - Panics if there is a provider that panics on construction.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use rudi::{modules, providers, try_singleton, ContextOptions, DynProvider, Module};
+    ///
+    /// struct MyModule;
+    ///
+    /// impl Module for MyModule {
+    ///     fn providers() -> Vec<DynProvider> {
+    ///         providers![try_singleton(|_| "forty-two".parse::<i32>())]
+    ///     }
+    /// }
+    ///
+    /// # fn main() {
+    /// let result = ContextOptions::default()
+    ///     .eager_create(true)
+    ///     .try_create(modules![MyModule]);
+    ///
+    /// assert!(result.is_err());
+    /// # }
+    /// ```
+    #[track_caller]
+    pub fn try_create(self, modules: Vec<ResolveModule>) -> Result<Context, ResolveError> {
+        let mut cx = self.inner_create_with_modules(modules);
+        cx.try_flush()?;
+        Ok(cx)
+    }
+
+    /// Creates a new context with the [`AutoRegisterModule`],
+    /// or returns a [`ResolveError`] if a provider that is eagerly created cannot be resolved.
+    ///
+    /// Same as `ContextOptions::default().try_create(modules![AutoRegisterModule])`.
+    ///
+    /// See [`ContextOptions::try_create`] for more details.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if there are multiple providers with the same key and the context's [`allow_override`](Context::allow_override) is false.
+    /// - Panics if there is a provider whose constructor is async and the provider will be eagerly created.
+    /// - Panics if there is a provider that panics on construction.
+    ///
+    /// [`AutoRegisterModule`]: crate::AutoRegisterModule
+    #[cfg_attr(docsrs, doc(cfg(feature = "auto-register")))]
+    #[cfg(feature = "auto-register")]
+    #[track_caller]
+    pub fn try_auto_register(self) -> Result<Context, ResolveError> {
         let mut cx = self.inner_create_with_auto();
-        cx.flush_async().await;
-        cx
+        cx.try_flush()?;
+        Ok(cx)
+    }
+
+    /// Async version of [`ContextOptions::try_create`].
+    ///
+    /// See [`ContextOptions::try_create`] for more details.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if there are multiple providers with the same key and the context's [`allow_override`](Context::allow_override) is false.
+    /// - Panics if there is a provider that panics on construction.
+    pub async fn try_create_async(
+        self,
+        modules: Vec<ResolveModule>,
+    ) -> Result<Context, ResolveError> {
+        let mut cx = self.inner_create_with_modules(modules);
+        cx.try_flush_async().await?;
+        Ok(cx)
+    }
+
+    /// Async version of [`ContextOptions::try_auto_register`].
+    ///
+    /// See [`ContextOptions::try_auto_register`] for more details.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if there are multiple providers with the same key and the context's [`allow_override`](Context::allow_override) is false.
+    /// - Panics if there is a provider that panics on construction.
+    #[cfg_attr(docsrs, doc(cfg(feature = "auto-register")))]
+    #[cfg(feature = "auto-register")]
+    pub async fn try_auto_register_async(self) -> Result<Context, ResolveError> {
+        let mut cx = self.inner_create_with_auto();
+        cx.try_flush_async().await?;
+        Ok(cx)
     }
 }
 
 #[derive(Default)]
 struct DependencyChain {
     stack: Vec<Key>,
+    edges: HashSet<(Key, Key)>,
 }
 
 impl DependencyChain {
-    fn push(&mut self, key: Key) {
-        let already_contains = self.stack.contains(&key);
-        self.stack.push(key);
-
-        if already_contains {
-            let key = self.stack.last().unwrap();
-
-            let mut buf = String::with_capacity(1024);
-            buf.push('[');
-            buf.push('\n');
-
-            self.stack.iter().for_each(|k| {
-                if key == k {
-                    buf.push_str(" --> ")
-                } else {
-                    buf.push_str("  |  ")
-                }
-
-                buf.push_str(&format!("{:?}", k));
-                buf.push('\n');
-            });
+    /// Records that the key is resolved by the instance on the top of the chain, if any.
+    fn observe(&mut self, key: &Key) {
+        if let Some(dependent) = self.stack.last() {
+            self.edges.insert((dependent.clone(), key.clone()));
+        }
+    }
 
-            buf.push(']');
+    fn contains(&self, key: &Key) -> bool {
+        self.stack.contains(key)
+    }
 
-            panic!("circular dependency detected: {}", buf);
-        }
+    fn push(&mut self, key: Key) {
+        self.stack.push(key);
     }
 
     fn pop(&mut self) {
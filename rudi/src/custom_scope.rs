@@ -0,0 +1,95 @@
+use std::time::Instant;
+
+use super::{Context, Key, MaybeSendSync};
+
+/// A scope that decides how the instances of a provider are cached,
+/// in addition to [`Singleton`](crate::Scope::Singleton), [`Transient`](crate::Scope::Transient) and [`SingleOwner`](crate::Scope::SingleOwner).
+///
+/// A custom scope is registered in the context with [`ContextOptions::custom_scope`](crate::ContextOptions::custom_scope),
+/// and a provider uses it with [`SingletonProvider::scope`](crate::SingletonProvider::scope),
+/// [`SingletonAsyncProvider::scope`](crate::SingletonAsyncProvider::scope)
+/// or `#[Singleton(scope = MyScope)]`.
+///
+/// The instances of a provider with a custom scope are cached in partitions, e.g. one partition per thread or tenant.
+/// Each time an instance is resolved, the custom scope is asked for the partition of the current resolving
+/// and what to do with the instance cached in that partition.
+///
+/// # Example
+///
+/// ```rust
+/// use std::time::Duration;
+///
+/// use rudi::{CachedInstance, Context, CustomScope, Key, ScopeDecision, Singleton};
+///
+/// struct Expiring;
+///
+/// impl CustomScope for Expiring {
+///     fn decide(&self, _: &Key, cached: Option<&CachedInstance>) -> ScopeDecision {
+///         match cached {
+///             Some(cached) if cached.created_at().elapsed() < Duration::from_secs(60) => {
+///                 ScopeDecision::Reuse
+///             }
+///             _ => ScopeDecision::Create,
+///         }
+///     }
+/// }
+///
+/// #[derive(Clone)]
+/// #[Singleton(scope = Expiring)]
+/// struct Token;
+///
+/// # fn main() {
+/// let mut cx = Context::options().custom_scope(Expiring).auto_register();
+/// cx.resolve::<Token>();
+/// # }
+/// ```
+pub trait CustomScope: MaybeSendSync + 'static {
+    /// Returns the partition of the cache that the current resolving belongs to,
+    /// e.g. the id of the current thread or tenant, default is `0`.
+    fn partition(&self, cx: &Context, key: &Key) -> u64 {
+        let _ = (cx, key);
+        0
+    }
+
+    /// Decides what to do with the instance cached in the current partition, if any.
+    ///
+    /// By default, the cached instance is reused, and a new instance is created if there is none.
+    fn decide(&self, key: &Key, cached: Option<&CachedInstance>) -> ScopeDecision {
+        let _ = key;
+
+        match cached {
+            Some(_) => ScopeDecision::Reuse,
+            None => ScopeDecision::Create,
+        }
+    }
+}
+
+/// Represents what to do when resolving an instance of a provider with a [`CustomScope`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScopeDecision {
+    /// Returns a clone of the cached instance, or creates and caches a new instance if there is none.
+    Reuse,
+    /// Creates a new instance, and caches it in place of the cached instance.
+    Create,
+    /// Removes the cached instance, and creates a new instance without caching it.
+    Evict,
+}
+
+/// Represents the information of an instance cached by a [`CustomScope`].
+#[derive(Clone, Debug)]
+pub struct CachedInstance {
+    created_at: Instant,
+}
+
+impl CachedInstance {
+    pub(crate) fn new() -> Self {
+        Self {
+            created_at: Instant::now(),
+        }
+    }
+
+    /// Returns the time when the instance was created.
+    pub fn created_at(&self) -> Instant {
+        self.created_at
+    }
+}
@@ -0,0 +1,74 @@
+use std::borrow::Cow;
+
+use super::{Context, Decorate, DynAny, Key, MaybeSendSync, Shared};
+
+/// Represents a decorator that wraps or modifies the instances of type `T` with a given name,
+/// each time a provider of that key constructs an instance.
+///
+/// Decorators are applied in registration order, after the [`post_construct`](crate::SingletonProvider::post_construct) hook,
+/// and before the instance is cached, so a [`Singleton`](crate::Scope::Singleton) is decorated only once.
+///
+/// A decorator can be registered with [`Context::decorate`], returned by [`Module::decorators`](crate::Module::decorators),
+/// or defined with [`#[Decorator]`](crate::Decorator).
+///
+/// # Example
+///
+/// ```rust
+/// use rudi::{modules, providers, transient, Context, DynDecorator, DynProvider, Module};
+///
+/// struct MyModule;
+///
+/// impl Module for MyModule {
+///     fn providers() -> Vec<DynProvider> {
+///         providers![transient(|_| 1)]
+///     }
+///
+///     fn decorators() -> Vec<DynDecorator> {
+///         vec![DynDecorator::new("", |n: i32, _cx| n * 10)]
+///     }
+/// }
+///
+/// # fn main() {
+/// let mut cx = Context::create(modules![MyModule]);
+/// assert_eq!(cx.resolve::<i32>(), 10);
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct DynDecorator {
+    key: Key,
+    decorate: Shared<DynAny>,
+}
+
+impl DynDecorator {
+    /// Create a new [`DynDecorator`] for the instances of type `T` with the given name.
+    pub fn new<T, F>(name: impl Into<Cow<'static, str>>, decorate: F) -> Self
+    where
+        T: 'static,
+        F: Fn(T, &mut Context) -> T + 'static + MaybeSendSync,
+    {
+        let decorate: Decorate<T> = Shared::new(decorate);
+
+        Self {
+            key: Key::new::<T>(name.into()),
+            decorate: Shared::new(decorate),
+        }
+    }
+
+    /// Returns the key of the instances to be decorated.
+    pub fn key(&self) -> &Key {
+        &self.key
+    }
+
+    pub(crate) fn as_decorate<T: 'static>(&self) -> Option<&Decorate<T>> {
+        self.decorate.downcast_ref()
+    }
+}
+
+/// Represents a type that can be converted to a [`DynDecorator`].
+///
+/// It is implemented by the [`#[Decorator]`](crate::Decorator) attribute macro,
+/// and used by the [`decorators!`](crate::decorators) macro.
+pub trait DefaultDecorator {
+    /// Returns the [`DynDecorator`] of the type.
+    fn decorator() -> DynDecorator;
+}
@@ -13,6 +13,9 @@ pub struct Key {
     pub name: Cow<'static, str>,
     /// The type of the provider generic.
     pub ty: Type,
+    /// The marker type that qualifies the provider, in addition to the name,
+    /// see [`SingletonProvider::qualifier`](crate::SingletonProvider::qualifier).
+    pub qualifier: Option<Type>,
 }
 
 impl Key {
@@ -20,13 +23,22 @@ impl Key {
         Self {
             name,
             ty: Type::new::<T>(),
+            qualifier: None,
+        }
+    }
+
+    pub(crate) fn qualified<T: 'static, Q: 'static>() -> Self {
+        Self {
+            name: Cow::Borrowed(""),
+            ty: Type::new::<T>(),
+            qualifier: Some(Type::new::<Q>()),
         }
     }
 }
 
 impl PartialEq for Key {
     fn eq(&self, other: &Self) -> bool {
-        self.ty == other.ty && self.name == other.name
+        self.ty == other.ty && self.name == other.name && self.qualifier == other.qualifier
     }
 }
 
@@ -44,7 +56,11 @@ impl Ord for Key {
             Ordering::Equal => {}
             ord => return ord,
         }
-        self.name.cmp(&other.name)
+        match self.name.cmp(&other.name) {
+            Ordering::Equal => {}
+            ord => return ord,
+        }
+        self.qualifier.cmp(&other.qualifier)
     }
 }
 
@@ -52,9 +68,21 @@ impl Hash for Key {
     fn hash<H: Hasher>(&self, state: &mut H) {
         self.ty.hash(state);
         self.name.hash(state);
+        self.qualifier.hash(state);
     }
 }
 
+/// Represents a declarative condition on whether providers of a type are registered in the [`Context`](crate::Context),
+/// see [`SingletonProvider::on_missing`](crate::SingletonProvider::on_missing)
+/// and [`SingletonProvider::on_present`](crate::SingletonProvider::on_present).
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum PresenceCondition {
+    /// Met when no provider of the type is registered.
+    Missing(Type),
+    /// Met when a provider of the type is registered.
+    Present(Type),
+}
+
 /// Represents a definition of a provider.
 #[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord)]
 pub struct Definition {
@@ -77,6 +105,17 @@ pub struct Definition {
     pub color: Option<Color>,
     /// Whether the provider is conditional.
     pub conditional: bool,
+    /// The priority of the provider among the providers of the same type,
+    /// the instances resolved by type are sorted by it in ascending order, then by registration order.
+    pub order: i32,
+    /// Whether the provider is the primary provider of its type,
+    /// which is resolved when no provider is registered for the type with the default name `""`.
+    pub primary: bool,
+    /// The profiles in which the provider is active, an empty collection means all profiles,
+    /// see [`ContextOptions::profiles`](crate::ContextOptions::profiles).
+    pub profiles: Vec<Cow<'static, str>>,
+    /// The presence conditions of the provider, which are checked after the unconditional providers are loaded.
+    pub presence: Vec<PresenceCondition>,
 }
 
 impl Definition {
@@ -92,24 +131,54 @@ impl Definition {
             scope,
             color,
             conditional,
+            order: 0,
+            primary: false,
+            profiles: Vec::new(),
+            presence: Vec::new(),
         }
     }
 
     pub(crate) fn bind<T: 'static>(self) -> Definition {
         let Definition {
-            key: Key { name, ty },
+            key:
+                Key {
+                    name,
+                    ty,
+                    qualifier,
+                },
             scope,
             color,
             conditional,
+            order,
+            primary: _primary,
+            profiles,
+            presence,
             origin: _origin,
         } = self;
 
         Self {
-            key: Key::new::<T>(name),
+            key: Key {
+                qualifier,
+                ..Key::new::<T>(name)
+            },
             origin: Some(ty),
             scope,
             color,
             conditional,
+            order,
+            // a type-erased binding must not take over the default of its type, e.g. `dyn Trait`
+            primary: false,
+            profiles,
+            presence,
         }
     }
+
+    /// Returns whether the provider is active in one of the given profiles.
+    pub(crate) fn is_active_in(&self, profiles: &[Cow<'static, str>]) -> bool {
+        self.profiles.is_empty()
+            || self
+                .profiles
+                .iter()
+                .any(|profile| profiles.contains(profile))
+    }
 }
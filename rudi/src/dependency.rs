@@ -0,0 +1,301 @@
+use std::{
+    borrow::Cow,
+    collections::{HashMap, HashSet, VecDeque},
+};
+
+use super::{AsyncFactory, DynProvider, Factory, Key, Lazy, Type};
+
+/// Represents a dependency declared by a provider, i.e. an instance its constructor takes from the [`Context`](crate::Context).
+///
+/// The attribute macros, e.g. [`#[Singleton]`](crate::Singleton), declare the dependencies of the generated providers,
+/// which are used by [`Context::validate`](crate::Context::validate) to check the providers without constructing anything.
+#[derive(Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Dependency {
+    /// The key of the dependency.
+    ///
+    /// For [`DependencyKind::Vec`] and [`DependencyKind::Map`], the name of the key is ignored, all providers of the type are taken.
+    pub key: Key,
+    /// How the dependency is taken.
+    pub kind: DependencyKind,
+    /// Whether the dependency is taken as a reference to a [`Singleton`](crate::Scope::Singleton) or [`SingleOwner`](crate::Scope::SingleOwner) instance.
+    pub by_ref: bool,
+}
+
+impl Dependency {
+    /// Create a new [`Dependency`] based on the type `T`, the given name and kind.
+    pub fn new<T: 'static>(name: impl Into<Cow<'static, str>>, kind: DependencyKind) -> Self {
+        Self {
+            key: Key::new::<T>(name.into()),
+            kind,
+            by_ref: false,
+        }
+    }
+
+    /// Sets the marker type that qualifies the dependency, see [`SingletonProvider::qualifier`](crate::SingletonProvider::qualifier).
+    pub fn qualifier<Q: 'static>(mut self) -> Self {
+        self.key.qualifier = Some(Type::new::<Q>());
+        self
+    }
+
+    /// Marks the dependency as taken by reference.
+    pub fn by_ref(mut self) -> Self {
+        self.by_ref = true;
+        self
+    }
+}
+
+/// Represents how a [`Dependency`] is taken.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum DependencyKind {
+    /// The dependency must be resolved, e.g. `a: A`.
+    Required,
+    /// The dependency is optional, e.g. `#[di(option)] a: Option<A>`.
+    Option,
+    /// The dependency falls back to a default value, e.g. `#[di(default)] a: A`.
+    Default,
+    /// All instances of the type are collected, e.g. `#[di(vec)] a: Vec<A>`.
+    Vec,
+    /// All instances of the type are collected by name, e.g. `#[di(map)] a: HashMap<Cow<'static, str>, A>`.
+    Map,
+    /// The dependency is resolved on first use, e.g. `#[di(lazy)] a: Lazy<A>`.
+    Lazy,
+    /// A [`Factory`] of the dependency, e.g. `#[di(factory)] a: Factory<A>`.
+    Factory,
+    /// An [`AsyncFactory`] of the dependency, e.g. `#[di(factory)] a: AsyncFactory<A>`.
+    AsyncFactory,
+}
+
+/// Extracts the type of the instances from the type of a field or argument, used by the attribute macros.
+#[doc(hidden)]
+pub trait Wrapper {
+    type Inner;
+}
+
+impl<T> Wrapper for Option<T> {
+    type Inner = T;
+}
+
+impl<T> Wrapper for Vec<T> {
+    type Inner = T;
+}
+
+impl<K, T, S> Wrapper for HashMap<K, T, S> {
+    type Inner = T;
+}
+
+impl<T> Wrapper for Lazy<T> {
+    type Inner = T;
+}
+
+impl<T> Wrapper for Factory<T> {
+    type Inner = T;
+}
+
+impl<T> Wrapper for AsyncFactory<T> {
+    type Inner = T;
+}
+
+/// Returns the cycles in the dependencies declared by the given providers,
+/// each of which is a chain of keys whose last key closes the cycle.
+///
+/// The providers that depend on each other are reported by one cycle, i.e. the shortest cycle
+/// through the first of their keys, so that a provider is not reported again for every path that leads back to it.
+///
+/// Dependencies that are resolved after the construction, i.e. [`DependencyKind::Lazy`],
+/// [`DependencyKind::Factory`] and [`DependencyKind::AsyncFactory`], do not form a cycle.
+pub(crate) fn find_cycles<'a>(
+    providers: impl IntoIterator<Item = &'a DynProvider>,
+) -> Vec<Vec<Key>> {
+    let providers = providers.into_iter().collect::<Vec<_>>();
+
+    let mut keys = providers
+        .iter()
+        .map(|provider| &provider.definition().key)
+        .collect::<Vec<_>>();
+    keys.sort_by_key(|key| (key.ty.name, &key.name, key.qualifier.map(|ty| ty.name)));
+    keys.dedup();
+
+    let indices = keys
+        .iter()
+        .enumerate()
+        .map(|(index, &key)| (key, index))
+        .collect::<HashMap<_, _>>();
+
+    let primaries = providers
+        .iter()
+        .map(|provider| provider.definition())
+        .filter(|definition| definition.primary && definition.key.qualifier.is_none())
+        .map(|definition| indices[&definition.key])
+        .collect::<HashSet<_>>();
+
+    let mut indices_by_type = HashMap::<Type, Vec<usize>>::new();
+
+    for (index, key) in keys.iter().enumerate() {
+        indices_by_type.entry(key.ty).or_default().push(index);
+    }
+
+    let mut edges = vec![Vec::new(); keys.len()];
+
+    for provider in &providers {
+        let dependencies = provider.dependencies().iter().filter(|dependency| {
+            !matches!(
+                dependency.kind,
+                DependencyKind::Lazy | DependencyKind::Factory | DependencyKind::AsyncFactory
+            )
+        });
+
+        let targets = &mut edges[indices[&provider.definition().key]];
+
+        for dependency in dependencies {
+            let Some(candidates) = indices_by_type.get(&dependency.key.ty) else {
+                continue;
+            };
+
+            match dependency.kind {
+                DependencyKind::Vec | DependencyKind::Map => targets.extend(candidates),
+                _ => match indices.get(&dependency.key) {
+                    Some(&index) => targets.push(index),
+                    // an unqualified dependency with the default name falls back to the primary provider of its type
+                    None if dependency.key.name.is_empty()
+                        && dependency.key.qualifier.is_none() =>
+                    {
+                        targets.extend(candidates.iter().filter(|index| primaries.contains(*index)))
+                    }
+                    None => {}
+                },
+            }
+        }
+    }
+
+    for targets in &mut edges {
+        targets.sort_unstable();
+        targets.dedup();
+    }
+
+    let mut cycles = strongly_connected_components(&edges)
+        .into_iter()
+        .filter_map(|component| {
+            let start = *component.iter().min()?;
+            let members = component.into_iter().collect::<HashSet<_>>();
+            shortest_cycle(&edges, start, &members)
+        })
+        .collect::<Vec<_>>();
+
+    cycles.sort();
+
+    cycles
+        .into_iter()
+        .map(|cycle| cycle.into_iter().map(|index| keys[index].clone()).collect())
+        .collect()
+}
+
+/// Returns the strongly connected components of the graph, by Tarjan's algorithm.
+///
+/// Iterative, so a long chain of dependencies cannot overflow the stack.
+fn strongly_connected_components(edges: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    const UNVISITED: usize = usize::MAX;
+
+    let mut order = vec![UNVISITED; edges.len()];
+    let mut low = vec![UNVISITED; edges.len()];
+    let mut on_stack = vec![false; edges.len()];
+    let mut stack = Vec::new();
+    let mut next_order = 0;
+    let mut components = Vec::new();
+
+    for root in 0..edges.len() {
+        if order[root] != UNVISITED {
+            continue;
+        }
+
+        order[root] = next_order;
+        low[root] = next_order;
+        next_order += 1;
+        stack.push(root);
+        on_stack[root] = true;
+
+        let mut path = vec![(root, 0)];
+
+        while let Some((node, edge)) = path.last_mut() {
+            let node = *node;
+
+            if let Some(&next) = edges[node].get(*edge) {
+                *edge += 1;
+
+                if order[next] == UNVISITED {
+                    order[next] = next_order;
+                    low[next] = next_order;
+                    next_order += 1;
+                    stack.push(next);
+                    on_stack[next] = true;
+                    path.push((next, 0));
+                } else if on_stack[next] {
+                    low[node] = low[node].min(order[next]);
+                }
+
+                continue;
+            }
+
+            path.pop();
+
+            if let Some(&(parent, _)) = path.last() {
+                low[parent] = low[parent].min(low[node]);
+            }
+
+            if low[node] == order[node] {
+                let mut component = Vec::new();
+
+                while let Some(member) = stack.pop() {
+                    on_stack[member] = false;
+                    component.push(member);
+
+                    if member == node {
+                        break;
+                    }
+                }
+
+                components.push(component);
+            }
+        }
+    }
+
+    components
+}
+
+/// Returns the shortest cycle from `start` back to itself that only goes through the given members,
+/// or `None` if there is no such cycle, i.e. `start` is a single node without a self loop.
+fn shortest_cycle(
+    edges: &[Vec<usize>],
+    start: usize,
+    members: &HashSet<usize>,
+) -> Option<Vec<usize>> {
+    let mut parents = HashMap::new();
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(node) = queue.pop_front() {
+        for &next in &edges[node] {
+            if next == start {
+                let mut cycle = vec![start];
+                let mut current = node;
+
+                while current != start {
+                    cycle.push(current);
+                    current = parents[&current];
+                }
+
+                cycle.push(start);
+                // the nodes between the two `start`s are collected backwards
+                let len = cycle.len();
+                cycle[1..len - 1].reverse();
+
+                return Some(cycle);
+            }
+
+            if members.contains(&next) && !parents.contains_key(&next) {
+                parents.insert(next, node);
+                queue.push_back(next);
+            }
+        }
+    }
+
+    None
+}
@@ -0,0 +1,326 @@
+use std::{
+    error::Error,
+    fmt::{self, Display, Formatter},
+};
+
+use super::{Definition, Dependency, Key, SharedError};
+
+/// Represents the reason why an instance could not be resolved from the [`Context`](crate::Context).
+#[derive(Clone, Debug)]
+pub enum ResolveErrorKind {
+    /// No provider is registered for the key.
+    NotFoundProvider(Key),
+    /// No [`Singleton`](crate::Scope::Singleton) or [`SingleOwner`](crate::Scope::SingleOwner) instance is stored for the key.
+    NotFoundSingle(Key),
+    /// The registered provider is not a [`Singleton`](crate::Scope::Singleton) or [`Transient`](crate::Scope::Transient).
+    NotSingletonOrTransient(Definition),
+    /// The registered provider is not a [`Singleton`](crate::Scope::Singleton) or [`SingleOwner`](crate::Scope::SingleOwner).
+    NotSingletonOrSingleOwner(Definition),
+    /// The registered provider is not a [`Transient`](crate::Scope::Transient).
+    NotTransient(Definition),
+    /// The registered provider has an async constructor, but it is called in a sync context.
+    AsyncConstructorInSync(Definition),
+    /// The key is already in the dependency chain.
+    CircularDependency(Key),
+    /// The dependencies declared by the registered providers form cycles,
+    /// each of which is a chain of keys whose last key closes the cycle,
+    /// one for each group of providers that depend on each other.
+    CircularDependencies(Vec<Vec<Key>>),
+    /// The conditions of the conditional providers contradict each other,
+    /// i.e. whether each of the providers is registered depends on whether the others are registered,
+    /// so that the evaluation does not settle on a stable set of providers to register.
+    ContradictoryConditions(Vec<Definition>),
+    /// The conditions of the conditional providers have several stable results,
+    /// e.g. each of two providers is registered only if the other is not,
+    /// so that which providers are registered would depend on the order in which the conditions are evaluated.
+    AmbiguousConditions(Vec<Definition>),
+    /// Several providers of the same type registered in the context are marked as [`primary`](crate::Definition::primary).
+    MultiplePrimaryProviders(Vec<Definition>),
+    /// The constructor of the registered provider returned an error.
+    ConstructorFailed(Definition, SharedError),
+    /// The instance of the key was created more than once while creating instances concurrently,
+    /// because a provider resolves it without declaring it as a [`dependency`](crate::Provider::dependencies).
+    CreatedConcurrently(Key),
+}
+
+/// Represents an error that occurred while resolving an instance from the [`Context`](crate::Context).
+///
+/// Returned by the non-panicking methods, e.g. [`Context::try_resolve`](crate::Context::try_resolve).
+#[derive(Clone, Debug)]
+pub struct ResolveError {
+    kind: Box<ResolveErrorKind>,
+    dependency_chain: Vec<Key>,
+}
+
+impl ResolveError {
+    pub(crate) fn new(kind: ResolveErrorKind, dependency_chain: Vec<Key>) -> Self {
+        Self {
+            kind: Box::new(kind),
+            dependency_chain,
+        }
+    }
+
+    /// Returns the reason of the error.
+    pub fn kind(&self) -> &ResolveErrorKind {
+        &self.kind
+    }
+
+    /// Returns the key of the provider or instance that failed to be resolved.
+    pub fn key(&self) -> &Key {
+        match self.kind.as_ref() {
+            ResolveErrorKind::NotFoundProvider(key)
+            | ResolveErrorKind::NotFoundSingle(key)
+            | ResolveErrorKind::CircularDependency(key)
+            | ResolveErrorKind::CreatedConcurrently(key) => key,
+            ResolveErrorKind::CircularDependencies(cycles) => &cycles[0][0],
+            ResolveErrorKind::NotSingletonOrTransient(definition)
+            | ResolveErrorKind::NotSingletonOrSingleOwner(definition)
+            | ResolveErrorKind::NotTransient(definition)
+            | ResolveErrorKind::AsyncConstructorInSync(definition)
+            | ResolveErrorKind::ConstructorFailed(definition, _) => &definition.key,
+            ResolveErrorKind::ContradictoryConditions(definitions)
+            | ResolveErrorKind::AmbiguousConditions(definitions)
+            | ResolveErrorKind::MultiplePrimaryProviders(definitions) => &definitions[0].key,
+        }
+    }
+
+    /// Returns the definition of the provider that failed to be resolved, if a provider is registered.
+    pub fn definition(&self) -> Option<&Definition> {
+        match self.kind.as_ref() {
+            ResolveErrorKind::NotFoundProvider(_)
+            | ResolveErrorKind::NotFoundSingle(_)
+            | ResolveErrorKind::CircularDependency(_)
+            | ResolveErrorKind::CreatedConcurrently(_)
+            | ResolveErrorKind::CircularDependencies(_)
+            | ResolveErrorKind::ContradictoryConditions(_)
+            | ResolveErrorKind::AmbiguousConditions(_)
+            | ResolveErrorKind::MultiplePrimaryProviders(_) => None,
+            ResolveErrorKind::NotSingletonOrTransient(definition)
+            | ResolveErrorKind::NotSingletonOrSingleOwner(definition)
+            | ResolveErrorKind::NotTransient(definition)
+            | ResolveErrorKind::AsyncConstructorInSync(definition)
+            | ResolveErrorKind::ConstructorFailed(definition, _) => Some(definition),
+        }
+    }
+
+    /// Returns the dependency chain at the time the error occurred.
+    ///
+    /// For [`ResolveErrorKind::CircularDependency`], the last key of the chain is the key that closes the cycle.
+    pub fn dependency_chain(&self) -> &Vec<Key> {
+        &self.dependency_chain
+    }
+}
+
+impl Display for ResolveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.kind.as_ref() {
+            ResolveErrorKind::NotFoundProvider(key) => {
+                write!(f, "no provider registered for: {:?}", key)?
+            }
+            ResolveErrorKind::NotFoundSingle(key) => {
+                write!(f, "no instance registered for: {:?}", key)?
+            }
+            ResolveErrorKind::NotSingletonOrTransient(definition) => write!(
+                f,
+                "registered provider is not `Singleton` or `Transient` for: {:?}",
+                definition
+            )?,
+            ResolveErrorKind::NotSingletonOrSingleOwner(definition) => write!(
+                f,
+                "registered provider is not `Singleton` or `SingleOwner` for: {:?}",
+                definition
+            )?,
+            ResolveErrorKind::NotTransient(definition) => write!(
+                f,
+                "registered provider is not `Transient` for: {:?}",
+                definition
+            )?,
+            ResolveErrorKind::AsyncConstructorInSync(definition) => write!(
+                f,
+                "unable to call an async constructor in a sync context for: {:?}
+
+please check all the references to the above type, there are 3 scenarios that will be referenced:
+1. use `Context::resolve_xxx::<Type>(cx)` to get instances of the type, change to `Context::resolve_xxx_async::<Type>(cx).await`.
+2. use `yyy: Type` as a field of a struct, or a field of a variant of a enum, use `#[Singleton(async)]`, `#[Transient(async)]` or `#[SingleOwner(async)]` on the struct or enum.
+3. use `zzz: Type` as a argument of a function, add the `async` keyword to the function.
+",
+                definition
+            )?,
+            ResolveErrorKind::ConstructorFailed(definition, source) => write!(
+                f,
+                "failed to construct the instance for: {:?}\n\ncaused by: {}",
+                definition, source
+            )?,
+            ResolveErrorKind::CreatedConcurrently(key) => write!(
+                f,
+                "instance created more than once while creating instances concurrently for: {:?}\n\n\
+                 it must be declared as a dependency of the providers that resolve it",
+                key
+            )?,
+            ResolveErrorKind::CircularDependency(key) => {
+                f.write_str("circular dependency detected: ")?;
+                return write_cycle(f, key, &self.dependency_chain);
+            }
+            ResolveErrorKind::CircularDependencies(cycles) => {
+                f.write_str("circular dependencies detected in the registered providers:")?;
+
+                for cycle in cycles {
+                    f.write_str("\n")?;
+                    write_cycle(f, &cycle[0], cycle)?;
+                }
+
+                return Ok(());
+            }
+            ResolveErrorKind::ContradictoryConditions(definitions) => {
+                f.write_str("contradictory conditions detected, whether the following providers are registered depends on each other:")?;
+
+                for definition in definitions {
+                    write!(f, "\n  |  {:?}", definition)?;
+                }
+
+                return Ok(());
+            }
+            ResolveErrorKind::AmbiguousConditions(definitions) => {
+                f.write_str("ambiguous conditions detected, the following providers can be registered in more than one stable way, depending on the order in which their conditions are evaluated:")?;
+
+                for definition in definitions {
+                    write!(f, "\n  |  {:?}", definition)?;
+                }
+
+                return Ok(());
+            }
+            ResolveErrorKind::MultiplePrimaryProviders(definitions) => {
+                f.write_str("multiple primary providers of the same type registered:")?;
+
+                for definition in definitions {
+                    write!(f, "\n  |  {:?}", definition)?;
+                }
+
+                return Ok(());
+            }
+        }
+
+        if !self.dependency_chain.is_empty() {
+            f.write_str("\n\ndependency chain:\n")?;
+
+            for key in &self.dependency_chain {
+                writeln!(f, "  |  {:?}", key)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn write_cycle(f: &mut Formatter<'_>, key: &Key, chain: &[Key]) -> fmt::Result {
+    f.write_str("[\n")?;
+
+    for k in chain {
+        if key == k {
+            f.write_str(" --> ")?;
+        } else {
+            f.write_str("  |  ")?;
+        }
+
+        writeln!(f, "{:?}", k)?;
+    }
+
+    f.write_str("]")
+}
+
+impl Error for ResolveError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self.kind.as_ref() {
+            ResolveErrorKind::ConstructorFailed(_, source) => Some(source.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+/// Represents the reason why a [`Dependency`] of a provider is not satisfied.
+#[derive(Clone, Debug)]
+pub enum ValidationErrorKind {
+    /// No provider is registered for the dependency.
+    NotFoundProvider,
+    /// No provider is registered for the dependency with the default name `""`,
+    /// but multiple providers are registered for its type with other names, and none of them is primary,
+    /// see [`SingletonProvider::primary`](crate::SingletonProvider::primary).
+    Ambiguous(Vec<Key>),
+    /// The scope of the registered provider does not match how the dependency is taken,
+    /// e.g. a reference to a [`Transient`](crate::Scope::Transient) instance.
+    ScopeMismatch(Definition),
+    /// The registered provider has an async constructor, but it is taken in a sync context.
+    AsyncConstructorInSync(Definition),
+}
+
+/// Represents a problem found by [`Context::validate`](crate::Context::validate).
+#[derive(Clone, Debug)]
+pub struct ValidationError {
+    dependent: Definition,
+    dependency: Dependency,
+    kind: ValidationErrorKind,
+}
+
+impl ValidationError {
+    pub(crate) fn new(
+        dependent: Definition,
+        dependency: Dependency,
+        kind: ValidationErrorKind,
+    ) -> Self {
+        Self {
+            dependent,
+            dependency,
+            kind,
+        }
+    }
+
+    /// Returns the definition of the provider that declares the dependency.
+    pub fn dependent(&self) -> &Definition {
+        &self.dependent
+    }
+
+    /// Returns the dependency that is not satisfied.
+    pub fn dependency(&self) -> &Dependency {
+        &self.dependency
+    }
+
+    /// Returns the reason of the error.
+    pub fn kind(&self) -> &ValidationErrorKind {
+        &self.kind
+    }
+}
+
+impl Display for ValidationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            ValidationErrorKind::NotFoundProvider => {
+                write!(f, "no provider registered for: {:?}", self.dependency.key)?
+            }
+            ValidationErrorKind::Ambiguous(candidates) => write!(
+                f,
+                "no provider registered for: {:?}, but found multiple candidates with other names and no primary one: {:?}",
+                self.dependency.key, candidates
+            )?,
+            ValidationErrorKind::ScopeMismatch(definition) => write!(
+                f,
+                "registered provider cannot be taken as {:?}{} for: {:?}",
+                self.dependency.kind,
+                if self.dependency.by_ref {
+                    " reference"
+                } else {
+                    ""
+                },
+                definition
+            )?,
+            ValidationErrorKind::AsyncConstructorInSync(definition) => write!(
+                f,
+                "unable to call an async constructor in a sync context for: {:?}",
+                definition
+            )?,
+        }
+
+        write!(f, "\n\nrequired by: {:?}", self.dependent)
+    }
+}
+
+impl Error for ValidationError {}
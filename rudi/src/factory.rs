@@ -0,0 +1,208 @@
+use std::fmt;
+
+use super::{
+    context::unwrap_or_panic, Constructor, Context, ContextHandle, Definition, PostConstruct,
+    ResolveError, Shared, SyncConstructor,
+};
+
+/// A handle that creates a new [`Transient`](crate::Scope::Transient) instance of type `T` on each call,
+/// by running the constructor of the provider it was captured from.
+///
+/// It allows an instance that outlives a single resolution, e.g. a [`Singleton`](crate::Scope::Singleton),
+/// to create fresh instances on demand, without keeping the [`Context`](crate::Context) around.
+///
+/// The factory captures a shared handle of the context it was obtained from, and resolves the dependencies
+/// of the instances from it, the same way as a [`Lazy`](crate::Lazy) handle.
+///
+/// A field or argument of type `Factory<T>` can be injected by the attribute macros with `#[di(factory)]`,
+/// or obtained by [`Context::factory`](crate::Context::factory).
+///
+/// # Example
+///
+/// ```rust
+/// use rudi::{Context, Factory, Singleton, Transient};
+///
+/// #[Transient]
+/// struct Request;
+///
+/// #[derive(Clone)]
+/// #[Singleton]
+/// struct Client(#[di(factory)] Factory<Request>);
+///
+/// # fn main() {
+/// let mut cx = Context::auto_register();
+///
+/// let client = cx.resolve::<Client>();
+///
+/// let _first: Request = client.0.create();
+/// let _second: Request = client.0.create();
+/// # }
+/// ```
+pub struct Factory<T> {
+    definition: Definition,
+    constructor: SyncConstructor<T>,
+    post_construct: Option<PostConstruct<T>>,
+    handle: Shared<ContextHandle>,
+}
+
+impl<T> Factory<T> {
+    pub(crate) fn new(
+        definition: Definition,
+        constructor: SyncConstructor<T>,
+        post_construct: Option<PostConstruct<T>>,
+        handle: Shared<ContextHandle>,
+    ) -> Self {
+        Self {
+            definition,
+            constructor,
+            post_construct,
+            handle,
+        }
+    }
+
+    /// Returns the [`Definition`] of the provider the factory was captured from.
+    pub fn definition(&self) -> &Definition {
+        &self.definition
+    }
+}
+
+impl<T: 'static> Factory<T> {
+    /// Creates a new instance, resolving its dependencies from the context the factory was obtained from.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if there is a provider that panics on construction.
+    /// - Panics if there is a provider whose constructor returns an error.
+    /// - Panics if a dependency cannot be resolved, see [`Context::resolve`](crate::Context::resolve) for more details.
+    /// - Panics if the construction of the instance creates the instance itself through a handle of the same context.
+    #[track_caller]
+    pub fn create(&self) -> T {
+        unwrap_or_panic(self.try_create())
+    }
+
+    /// Non-panicking version of [`Factory::create`].
+    #[track_caller]
+    pub fn try_create(&self) -> Result<T, ResolveError> {
+        self.handle.enter(&self.definition.key, || {
+            Context::for_handle(&self.handle).construct(
+                self.definition.key.clone(),
+                self.constructor.clone(),
+                self.post_construct.clone(),
+            )
+        })
+    }
+}
+
+impl<T> Clone for Factory<T> {
+    fn clone(&self) -> Self {
+        Self {
+            definition: self.definition.clone(),
+            constructor: self.constructor.clone(),
+            post_construct: self.post_construct.clone(),
+            handle: Shared::clone(&self.handle),
+        }
+    }
+}
+
+impl<T> fmt::Debug for Factory<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Factory")
+            .field("definition", &self.definition)
+            .finish()
+    }
+}
+
+/// Async version of [`Factory`], which can also be captured from a provider whose constructor is async.
+///
+/// A field or argument of type `AsyncFactory<T>` can be injected by the attribute macros with `#[di(factory)]`,
+/// or obtained by [`Context::async_factory`](crate::Context::async_factory).
+///
+/// # Example
+///
+/// ```rust
+/// use rudi::{AsyncFactory, Context, Singleton, Transient};
+///
+/// #[Transient(async)]
+/// struct Request;
+///
+/// #[derive(Clone)]
+/// #[Singleton]
+/// struct Client(#[di(factory)] AsyncFactory<Request>);
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let mut cx = Context::auto_register();
+///
+///     let client = cx.resolve::<Client>();
+///
+///     let _request: Request = client.0.create().await;
+/// }
+/// ```
+pub struct AsyncFactory<T> {
+    definition: Definition,
+    constructor: Constructor<T>,
+    post_construct: Option<PostConstruct<T>>,
+    handle: Shared<ContextHandle>,
+}
+
+impl<T> AsyncFactory<T> {
+    pub(crate) fn new(
+        definition: Definition,
+        constructor: Constructor<T>,
+        post_construct: Option<PostConstruct<T>>,
+        handle: Shared<ContextHandle>,
+    ) -> Self {
+        Self {
+            definition,
+            constructor,
+            post_construct,
+            handle,
+        }
+    }
+
+    /// Returns the [`Definition`] of the provider the factory was captured from.
+    pub fn definition(&self) -> &Definition {
+        &self.definition
+    }
+}
+
+impl<T: 'static> AsyncFactory<T> {
+    /// Async version of [`Factory::create`].
+    pub async fn create(&self) -> T {
+        unwrap_or_panic(self.try_create().await)
+    }
+
+    /// Async version of [`Factory::try_create`].
+    pub async fn try_create(&self) -> Result<T, ResolveError> {
+        let mut cx = Context::for_handle(&self.handle);
+
+        let future = cx.construct_async(
+            self.definition.key.clone(),
+            self.constructor.clone(),
+            self.post_construct.clone(),
+        );
+
+        self.handle
+            .enter_async(&self.definition.key, Box::pin(future))
+            .await
+    }
+}
+
+impl<T> Clone for AsyncFactory<T> {
+    fn clone(&self) -> Self {
+        Self {
+            definition: self.definition.clone(),
+            constructor: self.constructor.clone(),
+            post_construct: self.post_construct.clone(),
+            handle: Shared::clone(&self.handle),
+        }
+    }
+}
+
+impl<T> fmt::Debug for AsyncFactory<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("AsyncFactory")
+            .field("definition", &self.definition)
+            .finish()
+    }
+}
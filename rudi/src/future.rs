@@ -1,8 +1,12 @@
-use std::{future::Future, pin::Pin};
+use std::{
+    any::Any,
+    future::Future,
+    panic::{self, AssertUnwindSafe},
+    pin::Pin,
+    task::{Context, Poll},
+};
 
-/// An owned dynamically typed [`Future`] for use in cases where you can't
-/// statically type your result or need to add some indirection.
-pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + 'a>>;
+use super::{BoxFuture, MaybeSend};
 
 impl<T: ?Sized> FutureExt for T where T: Future {}
 
@@ -11,8 +15,61 @@ pub trait FutureExt: Future {
     /// Wrap the future in a Box, pinning it.
     fn boxed<'a>(self) -> BoxFuture<'a, Self::Output>
     where
-        Self: Sized + 'a,
+        Self: Sized + MaybeSend + 'a,
     {
         Box::pin(self)
     }
 }
+
+/// Polls the futures concurrently, and resolves to their outputs in the same order,
+/// or to the payload of the panic for a future that panicked.
+pub(crate) struct JoinAll<'a, T> {
+    futures: Vec<Option<BoxFuture<'a, T>>>,
+    outputs: Vec<Option<Result<T, Box<dyn Any + Send>>>>,
+}
+
+impl<'a, T> JoinAll<'a, T> {
+    pub(crate) fn new(futures: Vec<BoxFuture<'a, T>>) -> Self {
+        Self {
+            outputs: futures.iter().map(|_| None).collect(),
+            futures: futures.into_iter().map(Some).collect(),
+        }
+    }
+}
+
+// the futures are pinned in boxes, and the outputs are never pinned
+impl<T> Unpin for JoinAll<'_, T> {}
+
+impl<T> Future for JoinAll<'_, T> {
+    type Output = Vec<Result<T, Box<dyn Any + Send>>>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+
+        for (future, output) in this.futures.iter_mut().zip(&mut this.outputs) {
+            let Some(inner) = future else {
+                continue;
+            };
+
+            let result = match panic::catch_unwind(AssertUnwindSafe(|| inner.as_mut().poll(cx))) {
+                Ok(Poll::Pending) => continue,
+                Ok(Poll::Ready(value)) => Ok(value),
+                Err(payload) => Err(payload),
+            };
+
+            *output = Some(result);
+            *future = None;
+        }
+
+        if this.futures.iter().any(Option::is_some) {
+            return Poll::Pending;
+        }
+
+        Poll::Ready(
+            this.outputs
+                .iter_mut()
+                .map(|output| output.take().unwrap())
+                .collect(),
+        )
+    }
+}
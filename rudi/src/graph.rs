@@ -0,0 +1,229 @@
+use std::{collections::HashMap, fmt::Write};
+
+use crate::{Color, Definition, Key, Scope};
+
+/// A snapshot of the providers registered in a [`Context`](crate::Context),
+/// and the dependencies between them that the context has observed while resolving instances.
+///
+/// An edge from `A` to `B` means that `B` was resolved while `A` was being constructed,
+/// so dependencies that have never been resolved do not appear in the graph.
+///
+/// Returned by [`Context::dependency_graph`](crate::Context::dependency_graph).
+///
+/// # Example
+///
+/// ```rust
+/// use rudi::{Context, Singleton, Transient};
+///
+/// #[derive(Clone)]
+/// #[Transient]
+/// struct A;
+///
+/// #[derive(Clone)]
+/// #[Singleton]
+/// struct B(A);
+///
+/// # fn main() {
+/// let mut cx = Context::auto_register();
+/// cx.resolve::<B>();
+///
+/// let graph = cx.dependency_graph();
+/// assert_eq!(graph.edges().len(), 1);
+///
+/// println!("{}", graph.to_dot());
+/// println!("{}", graph.to_mermaid());
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct DependencyGraph {
+    nodes: Vec<Definition>,
+    edges: Vec<(Key, Key)>,
+}
+
+impl DependencyGraph {
+    pub(crate) fn new(mut nodes: Vec<Definition>, mut edges: Vec<(Key, Key)>) -> Self {
+        nodes.sort_by(|a, b| sort_key(&a.key).cmp(&sort_key(&b.key)));
+        edges.sort_by(|(a1, b1), (a2, b2)| {
+            (sort_key(a1), sort_key(b1)).cmp(&(sort_key(a2), sort_key(b2)))
+        });
+
+        Self { nodes, edges }
+    }
+
+    /// Returns the definitions of the registered providers.
+    pub fn nodes(&self) -> &[Definition] {
+        &self.nodes
+    }
+
+    /// Returns the observed dependencies, each of which is a pair of the dependent key and the dependency key.
+    ///
+    /// The dependency key may have no registered provider, e.g. an optional dependency that is resolved with
+    /// [`Context::resolve_option`](crate::Context::resolve_option).
+    pub fn edges(&self) -> &[(Key, Key)] {
+        &self.edges
+    }
+
+    /// Renders the graph in the [DOT](https://graphviz.org/doc/info/lang.html) language.
+    pub fn to_dot(&self) -> String {
+        let (nodes, edges) = self.layout();
+
+        let mut out = String::from("digraph rudi {\n    node [shape=box];\n");
+
+        for (index, node) in nodes.iter().enumerate() {
+            let label = node
+                .lines
+                .iter()
+                .map(|line| escape_dot(line))
+                .collect::<Vec<_>>()
+                .join("\\n");
+            let style = if node.registered {
+                ""
+            } else {
+                ", style=dashed"
+            };
+            let _ = writeln!(out, "    n{} [label=\"{}\"{}];", index, label, style);
+        }
+
+        for (from, to) in edges {
+            let _ = writeln!(out, "    n{} -> n{};", from, to);
+        }
+
+        out.push('}');
+        out
+    }
+
+    /// Renders the graph as a [Mermaid](https://mermaid.js.org/syntax/flowchart.html) flowchart.
+    pub fn to_mermaid(&self) -> String {
+        let (nodes, edges) = self.layout();
+
+        let mut out = String::from("flowchart LR");
+
+        for (index, node) in nodes.iter().enumerate() {
+            let label = node
+                .lines
+                .iter()
+                .map(|line| escape_mermaid(line))
+                .collect::<Vec<_>>()
+                .join("<br/>");
+
+            if node.registered {
+                let _ = write!(out, "\n    n{}[\"{}\"]", index, label);
+            } else {
+                let _ = write!(out, "\n    n{}([\"{}\"])", index, label);
+            }
+        }
+
+        for (from, to) in edges {
+            let _ = write!(out, "\n    n{} --> n{}", from, to);
+        }
+
+        out
+    }
+
+    /// Returns the nodes to render, followed by the dependencies that have no registered provider,
+    /// and the edges as pairs of node indices.
+    fn layout(&self) -> (Vec<Node>, Vec<(usize, usize)>) {
+        let mut nodes = self
+            .nodes
+            .iter()
+            .map(|definition| Node {
+                lines: definition_label(definition),
+                registered: true,
+            })
+            .collect::<Vec<_>>();
+
+        let mut indices = self
+            .nodes
+            .iter()
+            .enumerate()
+            .map(|(index, definition)| (&definition.key, index))
+            .collect::<HashMap<_, _>>();
+
+        let mut index_of = |key| {
+            *indices.entry(key).or_insert_with(|| {
+                let mut lines = key_label(key);
+                lines.push(String::from("not registered"));
+
+                nodes.push(Node {
+                    lines,
+                    registered: false,
+                });
+
+                nodes.len() - 1
+            })
+        };
+
+        let edges = self
+            .edges
+            .iter()
+            .map(|(from, to)| (index_of(from), index_of(to)))
+            .collect();
+
+        (nodes, edges)
+    }
+}
+
+struct Node {
+    lines: Vec<String>,
+    registered: bool,
+}
+
+fn definition_label(definition: &Definition) -> Vec<String> {
+    let mut label = key_label(&definition.key);
+
+    label.push(scope_label(definition));
+
+    if let Some(origin) = definition.origin {
+        label.push(format!("bind from: {}", origin.name));
+    }
+
+    label
+}
+
+/// Returns the scope and color of the definition, e.g. `Singleton, sync`.
+pub(crate) fn scope_label(definition: &Definition) -> String {
+    let scope = match definition.scope {
+        Scope::Singleton => "Singleton",
+        Scope::Transient => "Transient",
+        Scope::SingleOwner => "SingleOwner",
+        Scope::Custom(custom_scope) => custom_scope.name(),
+    };
+
+    match definition.color {
+        Some(Color::Async) => format!("{}, async", scope),
+        Some(Color::Sync) => format!("{}, sync", scope),
+        None => scope.to_string(),
+    }
+}
+
+fn sort_key(key: &Key) -> (&'static str, &str, Option<&'static str>) {
+    (
+        key.ty.name,
+        &key.name,
+        key.qualifier.map(|qualifier| qualifier.name),
+    )
+}
+
+fn key_label(key: &Key) -> Vec<String> {
+    let mut label = vec![key.ty.name.to_string()];
+
+    if !key.name.is_empty() {
+        label.push(format!("name: {:?}", key.name));
+    }
+
+    if let Some(qualifier) = key.qualifier {
+        label.push(format!("qualifier: {}", qualifier.name));
+    }
+
+    label
+}
+
+fn escape_dot(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+fn escape_mermaid(s: &str) -> String {
+    s.replace('"', "#quot;")
+        .replace('<', "#lt;")
+        .replace('>', "#gt;")
+}
@@ -0,0 +1,153 @@
+use std::{
+    cell::RefCell,
+    future::Future,
+    iter,
+    ops::DerefMut,
+    pin::Pin,
+    task::{Context as TaskContext, Poll},
+};
+
+use super::{lock, Context, Key, Lock, ResolveError, ResolveErrorKind, Shared, SingleRegistry};
+
+thread_local! {
+    // the handles that are resolving an instance on the current thread, with the keys of the instances, innermost last
+    static RESOLVING: RefCell<Vec<(usize, Key)>> = const { RefCell::new(Vec::new()) };
+}
+
+/// A shared handle of a [`Context`], captured by the handles that resolve instances after the resolution
+/// that created them has ended, e.g. [`Lazy`](crate::Lazy) and [`Factory`](crate::Factory).
+///
+/// The context publishes to the handle a view that shares its registries, and withdraws it while mutating them,
+/// so they are neither copied nor kept in sync. The handles resolve instances on a child of that view,
+/// and the instances they cache are kept by the handle until the context takes them,
+/// the next time an instance is resolved from that context.
+pub(crate) struct ContextHandle {
+    // `None` while the context the handle belongs to mutates its registries
+    view: Lock<Option<Shared<Context>>>,
+    singles: Lock<SingleRegistry>,
+}
+
+impl ContextHandle {
+    pub(crate) fn new(view: Context) -> Self {
+        Self {
+            view: Lock::new(Some(Shared::new(view))),
+            singles: Default::default(),
+        }
+    }
+
+    /// Returns the view published by the context the handle belongs to.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if that context panicked while mutating its registries.
+    #[track_caller]
+    pub(crate) fn view(&self) -> Shared<Context> {
+        lock(&self.view).clone().unwrap_or_else(|| {
+            panic!("the context of the handle panicked while mutating its registries")
+        })
+    }
+
+    /// Locks the published view, so the context the handle belongs to can withdraw it while mutating its registries.
+    ///
+    /// The lock must not be held while calling code of the user.
+    pub(crate) fn lock_view(&self) -> impl DerefMut<Target = Option<Shared<Context>>> + '_ {
+        lock(&self.view)
+    }
+
+    /// Locks the instances cached by the handle.
+    ///
+    /// The lock must not be held while calling code of the user.
+    pub(crate) fn lock_singles(&self) -> impl DerefMut<Target = SingleRegistry> + '_ {
+        lock(&self.singles)
+    }
+
+    /// Calls `f`, with the key marked as being resolved by the handle on the current thread.
+    ///
+    /// Returns an error instead if the key is already being resolved by the handle on the current thread,
+    /// i.e. the construction of the instance resolves the instance itself through a handle.
+    pub(crate) fn enter<T>(
+        &self,
+        key: &Key,
+        f: impl FnOnce() -> Result<T, ResolveError>,
+    ) -> Result<T, ResolveError> {
+        self.check(key)?;
+
+        let _entered = Entered::new(self, key);
+        f()
+    }
+
+    /// Async version of [`ContextHandle::enter`], the key is marked each time the future is polled.
+    pub(crate) async fn enter_async<T, F>(&self, key: &Key, future: F) -> Result<T, ResolveError>
+    where
+        F: Future<Output = Result<T, ResolveError>> + Unpin,
+    {
+        self.check(key)?;
+
+        Resolving {
+            handle: self,
+            key,
+            future,
+        }
+        .await
+    }
+
+    fn check(&self, key: &Key) -> Result<(), ResolveError> {
+        let id = self.id();
+
+        RESOLVING.with_borrow(|resolving| {
+            if resolving
+                .iter()
+                .any(|(handle, k)| *handle == id && k == key)
+            {
+                let chain = resolving
+                    .iter()
+                    .filter(|(handle, _)| *handle == id)
+                    .map(|(_, key)| key.clone())
+                    .chain(iter::once(key.clone()))
+                    .collect();
+
+                Err(ResolveError::new(
+                    ResolveErrorKind::CircularDependency(key.clone()),
+                    chain,
+                ))
+            } else {
+                Ok(())
+            }
+        })
+    }
+
+    fn id(&self) -> usize {
+        self as *const Self as usize
+    }
+}
+
+/// Marks a key as being resolved by a handle on the current thread until it is dropped.
+struct Entered;
+
+impl Entered {
+    fn new(handle: &ContextHandle, key: &Key) -> Self {
+        RESOLVING.with_borrow_mut(|resolving| resolving.push((handle.id(), key.clone())));
+        Self
+    }
+}
+
+impl Drop for Entered {
+    fn drop(&mut self) {
+        RESOLVING.with_borrow_mut(|resolving| resolving.pop());
+    }
+}
+
+struct Resolving<'a, F> {
+    handle: &'a ContextHandle,
+    key: &'a Key,
+    future: F,
+}
+
+impl<F: Future + Unpin> Future for Resolving<'_, F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<Self::Output> {
+        let _entered = Entered::new(self.handle, self.key);
+        Pin::new(&mut self.future).poll(cx)
+    }
+}
@@ -0,0 +1,144 @@
+use std::fmt;
+
+use super::{
+    context::unwrap_or_panic, Context, ContextHandle, Key, OnceCell, ResolveError, Shared,
+};
+
+/// A handle that resolves an instance of type `T` on first use,
+/// instead of when the instance that holds it is constructed.
+///
+/// It can be used to break a construction cycle, or to avoid creating an expensive dependency that is rarely used.
+///
+/// The handle captures a shared handle of the [`Context`](crate::Context) it was obtained from,
+/// and resolves the instance from the providers and the instances of that context, without that context.
+/// The [`Singleton`](crate::Scope::Singleton) and [`SingleOwner`](crate::Scope::SingleOwner) instances created by the handle
+/// are moved into that context the next time an instance is resolved from it, so they are still created only once.
+/// The instances of a custom scope cannot be resolved by the handle, even as dependencies,
+/// as if no provider were registered for them.
+///
+/// Once resolved, the instance is kept in the handle, and shared by all the clones of the handle.
+///
+/// A field or argument of type `Lazy<T>` can be injected by the attribute macros with `#[di(lazy)]`,
+/// or obtained by [`Context::lazy`](crate::Context::lazy).
+///
+/// # Example
+///
+/// ```rust
+/// use rudi::{Context, Lazy, Singleton};
+///
+/// #[derive(Clone)]
+/// #[Singleton]
+/// struct A(#[di(lazy)] Lazy<B>);
+///
+/// #[derive(Clone)]
+/// #[Singleton]
+/// struct B(A);
+///
+/// # fn main() {
+/// let mut cx = Context::auto_register();
+///
+/// let a = cx.resolve::<A>();
+/// assert!(a.0.get().is_none());
+///
+/// a.0.resolve();
+/// assert!(a.0.get().is_some());
+/// # }
+/// ```
+pub struct Lazy<T> {
+    key: Key,
+    handle: Shared<ContextHandle>,
+    cell: Shared<OnceCell<T>>,
+}
+
+impl<T> Lazy<T> {
+    pub(crate) fn new(key: Key, handle: Shared<ContextHandle>) -> Self {
+        Self {
+            key,
+            handle,
+            cell: Shared::new(OnceCell::new()),
+        }
+    }
+
+    /// Returns the key of the instance to be resolved.
+    pub fn key(&self) -> &Key {
+        &self.key
+    }
+
+    /// Returns a reference to the instance if it has been resolved.
+    pub fn get(&self) -> Option<&T> {
+        self.cell.get()
+    }
+}
+
+impl<T: 'static> Lazy<T> {
+    /// Returns a reference to the instance, resolves it if it has not been resolved.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if no provider is registered for the key.
+    /// - Panics if there is a provider whose constructor is async.
+    /// - Panics if there is a provider that panics on construction.
+    /// - Panics if there is a provider whose constructor returns an error.
+    /// - Panics if the provider is not a [`Singleton`](crate::Scope::Singleton) or [`Transient`](crate::Scope::Transient).
+    /// - Panics if the construction of the instance resolves the instance itself through a handle of the same context.
+    ///
+    /// See [`Context::resolve_with_name`](crate::Context::resolve_with_name) for more details.
+    #[track_caller]
+    pub fn resolve(&self) -> &T {
+        unwrap_or_panic(self.try_resolve())
+    }
+
+    /// Non-panicking version of [`Lazy::resolve`].
+    #[track_caller]
+    pub fn try_resolve(&self) -> Result<&T, ResolveError> {
+        if let Some(instance) = self.cell.get() {
+            return Ok(instance);
+        }
+
+        let instance = self.handle.enter(&self.key, || {
+            Context::for_handle(&self.handle).try_resolve_by_key(self.key.clone())
+        })?;
+
+        Ok(self.cell.get_or_init(|| instance))
+    }
+
+    /// Async version of [`Lazy::resolve`].
+    ///
+    /// See [`Context::resolve_with_name_async`](crate::Context::resolve_with_name_async) for more details.
+    pub async fn resolve_async(&self) -> &T {
+        unwrap_or_panic(self.try_resolve_async().await)
+    }
+
+    /// Async version of [`Lazy::try_resolve`].
+    pub async fn try_resolve_async(&self) -> Result<&T, ResolveError> {
+        if let Some(instance) = self.cell.get() {
+            return Ok(instance);
+        }
+
+        let mut cx = Context::for_handle(&self.handle);
+        let future = cx.try_resolve_by_key_async(self.key.clone());
+
+        let instance = self.handle.enter_async(&self.key, Box::pin(future)).await?;
+
+        Ok(self.cell.get_or_init(|| instance))
+    }
+}
+
+impl<T> Clone for Lazy<T> {
+    fn clone(&self) -> Self {
+        Self {
+            key: self.key.clone(),
+            handle: Shared::clone(&self.handle),
+            cell: Shared::clone(&self.cell),
+        }
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Lazy<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Lazy")
+            .field("key", &self.key)
+            .field("instance", &self.cell.get())
+            .finish()
+    }
+}
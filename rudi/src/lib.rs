@@ -4,14 +4,32 @@
 #[cfg_attr(docsrs, doc(cfg(feature = "auto-register")))]
 #[cfg(feature = "auto-register")]
 mod auto_register;
+mod condition;
+#[cfg_attr(docsrs, doc(cfg(feature = "config")))]
+#[cfg(feature = "config")]
+mod config;
 mod context;
+mod custom_scope;
+mod decorator;
 mod definition;
+mod dependency;
+mod error;
+mod factory;
 mod future;
+mod graph;
+mod handle;
+mod lazy;
 mod macros;
+mod maybe_send;
 mod module;
+mod observer;
 mod provider;
 mod registry;
+mod report;
 mod single;
+#[cfg_attr(docsrs, doc(cfg(feature = "send")))]
+#[cfg(feature = "send")]
+pub mod sync;
 mod ty;
 
 pub use rudi_core::*;
@@ -22,5 +40,12 @@ pub use rudi_macro::*;
 #[cfg_attr(docsrs, doc(cfg(feature = "auto-register")))]
 #[cfg(feature = "auto-register")]
 pub use self::auto_register::*;
-pub(crate) use self::registry::*;
-pub use self::{context::*, definition::*, future::*, module::*, provider::*, single::*, ty::*};
+#[cfg_attr(docsrs, doc(cfg(feature = "config")))]
+#[cfg(feature = "config")]
+pub use self::config::*;
+pub use self::{
+    condition::*, context::*, custom_scope::*, decorator::*, definition::*, dependency::*,
+    error::*, factory::*, future::*, graph::*, lazy::*, maybe_send::*, module::*, observer::*,
+    provider::*, report::*, single::*, ty::*,
+};
+pub(crate) use self::{handle::*, registry::*};
@@ -90,3 +90,292 @@ macro_rules! components {
         ),+]
     };
 }
+
+/// Convert a set of types that implement [`DefaultDecorator`]
+/// to a set of [`DynDecorator`] instances
+///
+/// # Example
+///
+/// ```rust
+/// use rudi::{decorators, Context, Decorator, DynDecorator};
+///
+/// #[Decorator]
+/// fn Double(n: i32, _cx: &mut Context) -> i32 {
+///     n * 2
+/// }
+///
+/// # fn main() {
+/// let _: Vec<DynDecorator> = decorators![Double];
+/// # }
+/// ```
+///
+/// [`DefaultDecorator`]: crate::DefaultDecorator
+/// [`DynDecorator`]: crate::DynDecorator
+#[macro_export]
+macro_rules! decorators {
+    () => {
+        vec![]
+    };
+    ($($decorator:ty),+ $(,)?) => {
+        vec![$(
+            <$decorator as $crate::DefaultDecorator>::decorator()
+        ),+]
+    };
+}
+
+/// Register a `Provider` that will be collected by [`auto_registered_providers`](crate::auto_registered_providers).
+///
+/// If you have:
+///   - Enabled the `auto-register` feature (which is enabled by default).
+///   - Define [`Provider`](crate::Provider) using the [`#[Singleton]`](crate::Singleton), [`#[Transient]`](crate::Transient) or [`#[SingleOwner]`](crate::SingleOwner) macro.
+///   - [`#[Singleton]`](crate::Singleton), [`#[Transient]`](crate::Transient) or [`#[SingleOwner]`](crate::SingleOwner) does not use the `auto_register = false` attribute.
+///
+/// Then you don't need to use this macro to register `Provider`.
+///
+/// But if you use function define a [`Provider`](crate::Provider) and you want to use auto-registration,
+/// then you need to use this macro.
+///
+/// # Example
+///
+/// ```rust
+/// use rudi::{register_provider, singleton, Context, Provider};
+///
+/// fn foo() -> Provider<&'static str> {
+///     singleton(|_| "Hello").into()
+/// }
+///
+/// register_provider!(foo());
+///
+/// fn main() {
+///     let mut cx = Context::auto_register();
+///     assert!(cx.resolve_option::<&'static str>().is_some());
+/// }
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "auto-register")))]
+#[cfg(feature = "auto-register")]
+#[macro_export]
+macro_rules! register_provider {
+    ($provider:expr) => {
+        const _: () = {
+            fn register() -> $crate::DynProvider {
+                <$crate::DynProvider as ::core::convert::From<_>>::from($provider)
+            }
+
+            $crate::submit! {
+                $crate::ProviderRegister {
+                    register
+                }
+            }
+        };
+    };
+}
+
+/// Register a [`DynDecorator`](crate::DynDecorator) that will be collected by [`auto_registered_decorators`](crate::auto_registered_decorators).
+///
+/// A decorator defined with the [`#[Decorator]`](crate::Decorator) macro is registered automatically,
+/// unless the `auto_register = false` attribute is used.
+///
+/// # Example
+///
+/// ```rust
+/// use rudi::{register_decorator, Context, DynDecorator, Transient};
+///
+/// #[Transient]
+/// fn Number() -> i32 {
+///     1
+/// }
+///
+/// register_decorator!(DynDecorator::new("", |n: i32, _cx| n + 1));
+///
+/// fn main() {
+///     let mut cx = Context::auto_register();
+///     assert_eq!(cx.resolve::<i32>(), 2);
+/// }
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "auto-register")))]
+#[cfg(feature = "auto-register")]
+#[macro_export]
+macro_rules! register_decorator {
+    ($decorator:expr) => {
+        const _: () = {
+            fn register() -> $crate::DynDecorator {
+                $decorator
+            }
+
+            $crate::submit! {
+                $crate::DecoratorRegister {
+                    register
+                }
+            }
+        };
+    };
+}
+
+/// Generate a function to enable auto-registration.
+///
+/// In Rust, it is possible to use [`inventory`] to accomplish something like
+/// auto-registration, but there is still a problem, and it exists in Rudi as well.
+///
+/// Suppose you have two crates, one called `crate_1` and one called `crate_2`,
+/// and you define some auto-registration types in `crate_2`.
+///
+/// If it is just a dependency on `crate_2` in `crate_1`'s `Cargo.toml`, then using
+/// [`auto_registered_providers`](crate::auto_registered_providers) in `crate_1` will not collect the types defined in `crate_2`,
+/// you have to use a function (or type, or constant) in `crate_1` that is defined in `crate_2`
+/// in order to enable auto-registration.
+///
+/// So, there is this macro, which generates a function called `enable`, with no parameters
+/// and no return, just to be called by other crates to enable auto-registration.
+///
+/// At the same time, you can also call the enable functions of other crates that the current
+/// crate depends on in this macro, so that when the enable function of the current crate is
+/// called, the enable functions of other crates will be called together.
+///
+/// # Example
+///
+/// ```rust ignore
+/// // lib1/src/lib.rs
+/// use rudi::{enable, Transient};
+///
+/// enable! {}
+///
+/// #[Transient(name = "lib1")]
+/// fn Lib1() -> i32 {
+///     5
+/// }
+///
+/// // lib2/src/lib.rs
+/// use rudi::{enable, Transient};
+///
+/// enable! {
+///     lib1::enable();
+/// }
+///
+/// #[Transient(name = "lib2")]
+/// fn Lib2() -> i32 {
+///     5
+/// }
+///
+/// // bin/src/main.rs
+/// use rudi::Context;
+///
+/// fn main() {
+///     lib2::enable();
+///
+///     let mut cx = Context::auto_register();
+///     assert_eq!(cx.resolve_by_type::<i32>().into_iter().sum::<i32>(), 10);
+/// }
+/// ```
+#[cfg_attr(docsrs, doc(cfg(feature = "auto-register")))]
+#[cfg(feature = "auto-register")]
+#[macro_export]
+macro_rules! enable {
+    ($($body:tt)*) => {
+        /// Enable auto-registration.
+        pub fn enable() {
+            $($body)*
+        }
+    };
+}
+
+/// [`modules!`] of the [`sync`](crate::sync) flavor.
+#[doc(hidden)]
+#[cfg(feature = "send")]
+#[macro_export]
+macro_rules! __sync_modules {
+    () => {
+        vec![]
+    };
+    ($($module:ty),+ $(,)?) => {
+        vec![$(
+            $crate::sync::ResolveModule::new::<$module>()
+        ),+]
+    };
+}
+
+/// [`providers!`] of the [`sync`](crate::sync) flavor.
+#[doc(hidden)]
+#[cfg(feature = "send")]
+#[macro_export]
+macro_rules! __sync_providers {
+    () => {
+        vec![]
+    };
+    ($($provider:expr),+ $(,)?) => {
+        vec![$(
+            <$crate::sync::DynProvider as ::core::convert::From<_>>::from($provider)
+        ),+]
+    };
+}
+
+/// [`components!`] of the [`sync`](crate::sync) flavor.
+#[doc(hidden)]
+#[cfg(feature = "send")]
+#[macro_export]
+macro_rules! __sync_components {
+    () => {
+        vec![]
+    };
+    ($($component:ty),+ $(,)?) => {
+        vec![$(
+            <$crate::sync::DynProvider as ::core::convert::From<_>>::from(
+                <$component as $crate::sync::DefaultProvider>::provider()
+            )
+        ),+]
+    };
+}
+
+/// [`decorators!`] of the [`sync`](crate::sync) flavor.
+#[doc(hidden)]
+#[cfg(feature = "send")]
+#[macro_export]
+macro_rules! __sync_decorators {
+    () => {
+        vec![]
+    };
+    ($($decorator:ty),+ $(,)?) => {
+        vec![$(
+            <$decorator as $crate::sync::DefaultDecorator>::decorator()
+        ),+]
+    };
+}
+
+/// [`register_provider!`] of the [`sync`](crate::sync) flavor.
+#[doc(hidden)]
+#[cfg(all(feature = "send", feature = "auto-register"))]
+#[macro_export]
+macro_rules! __sync_register_provider {
+    ($provider:expr) => {
+        const _: () = {
+            fn register() -> $crate::sync::DynProvider {
+                <$crate::sync::DynProvider as ::core::convert::From<_>>::from($provider)
+            }
+
+            $crate::submit! {
+                $crate::sync::ProviderRegister {
+                    register
+                }
+            }
+        };
+    };
+}
+
+/// [`register_decorator!`] of the [`sync`](crate::sync) flavor.
+#[doc(hidden)]
+#[cfg(all(feature = "send", feature = "auto-register"))]
+#[macro_export]
+macro_rules! __sync_register_decorator {
+    ($decorator:expr) => {
+        const _: () = {
+            fn register() -> $crate::sync::DynDecorator {
+                $decorator
+            }
+
+            $crate::submit! {
+                $crate::sync::DecoratorRegister {
+                    register
+                }
+            }
+        };
+    };
+}
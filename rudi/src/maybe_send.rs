@@ -0,0 +1,60 @@
+use std::{any::Any, cell::RefCell, error::Error, future::Future, ops::DerefMut, pin::Pin};
+
+use crate::{ConditionContext, Context};
+
+/// The shared pointer used by the [`Context`], e.g. the parent of [`Context::child`].
+///
+/// It is [`Arc`](std::sync::Arc) in the `sync` module, which is enabled by the `send` feature.
+pub use std::rc::Rc as Shared;
+
+pub(crate) use std::cell::OnceCell;
+
+pub(crate) type DynAny = dyn Any;
+
+pub(crate) type Lock<T> = RefCell<T>;
+
+pub(crate) fn lock<T>(lock: &Lock<T>) -> impl DerefMut<Target = T> + '_ {
+    lock.borrow_mut()
+}
+
+/// An owned dynamically typed [`Future`] for use in cases where you can't
+/// statically type your result or need to add some indirection.
+///
+/// It is also [`Send`] in the `sync` module, which is enabled by the `send` feature.
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + 'a>>;
+
+/// A type-erased error returned by a fallible constructor.
+///
+/// It is also [`Send`] + [`Sync`] in the `sync` module, which is enabled by the `send` feature.
+pub type BoxError = Box<dyn Error>;
+
+pub(crate) type SharedError = Shared<dyn Error>;
+
+pub(crate) type Evaluate = Shared<dyn Fn(&ConditionContext) -> bool>;
+
+pub(crate) type SyncConstructor<T> = Shared<dyn Fn(&mut Context) -> Result<T, BoxError>>;
+
+pub(crate) type AsyncConstructor<T> =
+    Shared<dyn for<'a> Fn(&'a mut Context) -> BoxFuture<'a, Result<T, BoxError>>>;
+
+pub(crate) type SyncPostConstruct<T> = Shared<dyn Fn(&mut T, &mut Context)>;
+
+pub(crate) type AsyncPostConstruct<T> =
+    Shared<dyn for<'a> Fn(T, &'a mut Context) -> BoxFuture<'a, T>>;
+
+pub(crate) type Decorate<T> = Shared<dyn Fn(T, &mut Context) -> T>;
+
+/// A marker trait that is implemented for all types, and is equivalent to [`Send`] in the `sync` module, which is enabled by the `send` feature.
+///
+/// It is used to bound the futures returned by async constructors.
+pub trait MaybeSend {}
+
+impl<T: ?Sized> MaybeSend for T {}
+
+/// A marker trait that is implemented for all types, and is equivalent to [`Send`] + [`Sync`] in the `sync` module, which is enabled by the `send` feature.
+///
+/// It is used to bound the constructors stored in providers,
+/// and the instances stored in the [`Context`].
+pub trait MaybeSendSync {}
+
+impl<T: ?Sized> MaybeSendSync for T {}
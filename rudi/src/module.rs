@@ -1,4 +1,4 @@
-use crate::{DynProvider, Type};
+use super::{DynDecorator, DynProvider, Type};
 
 /// Represents a module.
 ///
@@ -53,6 +53,15 @@ pub trait Module {
 
     /// Included providers.
     fn providers() -> Vec<DynProvider>;
+
+    /// Included decorators, default is empty.
+    ///
+    /// The decorators are registered in the context when the module is loaded,
+    /// replacing the ones registered by a previous load of the module,
+    /// and are removed when the module is unloaded.
+    fn decorators() -> Vec<DynDecorator> {
+        Vec::new()
+    }
 }
 
 /// A type representing a Module, converted from a type that implements [`Module`].
@@ -61,6 +70,7 @@ pub struct ResolveModule {
     eager_create: bool,
     submodules: Option<Vec<ResolveModule>>,
     providers: Vec<DynProvider>,
+    decorators: Vec<DynDecorator>,
 }
 
 impl ResolveModule {
@@ -71,6 +81,7 @@ impl ResolveModule {
             eager_create: T::eager_create(),
             submodules: T::submodules(),
             providers: T::providers(),
+            decorators: T::decorators(),
         }
     }
 
@@ -88,6 +99,10 @@ impl ResolveModule {
         self.submodules.take()
     }
 
+    pub(crate) fn decorators(&mut self) -> Vec<DynDecorator> {
+        std::mem::take(&mut self.decorators)
+    }
+
     pub(crate) fn providers(self) -> Vec<DynProvider> {
         self.providers
     }
@@ -0,0 +1,105 @@
+use std::time::Duration;
+
+use super::{Definition, Key, MaybeSendSync, Type};
+
+/// An observer of the lifecycle of a [`Context`](crate::Context),
+/// e.g. for collecting metrics, writing audit logs, or spying in tests.
+///
+/// An observer is registered in the context with [`ContextOptions::observer`](crate::ContextOptions::observer),
+/// and is also notified of the events of the children of the context.
+///
+/// All the methods do nothing by default.
+///
+/// # Example
+///
+/// ```rust
+/// use std::{
+///     sync::{Arc, Mutex},
+///     time::Duration,
+/// };
+///
+/// use rudi::{Context, ContextObserver, Key, Singleton};
+///
+/// #[derive(Clone, Default)]
+/// struct Spy(Arc<Mutex<Vec<String>>>);
+///
+/// impl ContextObserver for Spy {
+///     fn on_construct_finished(&self, key: &Key, _elapsed: Duration, depth: usize) {
+///         self.0.lock().unwrap().push(format!("{} at depth {}", key.ty.name, depth));
+///     }
+/// }
+///
+/// #[derive(Clone)]
+/// #[Singleton]
+/// struct A;
+///
+/// #[derive(Clone)]
+/// #[Singleton]
+/// struct B(A);
+///
+/// # fn main() {
+/// let spy = Spy::default();
+///
+/// let mut cx = Context::options().observer(spy.clone()).auto_register();
+/// cx.resolve::<B>();
+///
+/// assert_eq!(
+///     *spy.0.lock().unwrap(),
+///     [
+///         format!("{} at depth 1", std::any::type_name::<A>()),
+///         format!("{} at depth 0", std::any::type_name::<B>()),
+///     ]
+/// );
+/// # }
+/// ```
+pub trait ContextObserver: MaybeSendSync + 'static {
+    /// Called when a provider whose key is not yet registered is inserted into the context.
+    fn on_provider_inserted(&self, definition: &Definition) {
+        let _ = definition;
+    }
+
+    /// Called when a provider overrides the provider with the same key,
+    /// see [`Context::allow_override`](crate::Context::allow_override).
+    fn on_provider_overridden(&self, definition: &Definition) {
+        let _ = definition;
+    }
+
+    /// Called when the [`condition`](crate::Provider::condition) of a provider is evaluated,
+    /// the provider is loaded into the context only if `met` is true.
+    fn on_condition_evaluated(&self, definition: &Definition, met: bool) {
+        let _ = (definition, met);
+    }
+
+    /// Called before the constructor of a provider runs.
+    ///
+    /// `depth` is the number of instances being constructed that depend on this one,
+    /// i.e. `0` for an instance resolved directly from the context.
+    fn on_construct_started(&self, key: &Key, depth: usize) {
+        let _ = (key, depth);
+    }
+
+    /// Called after the constructor of a provider returns, whether it succeeded or not.
+    ///
+    /// `elapsed` includes the time spent constructing the dependencies,
+    /// `depth` is the same as in [`ContextObserver::on_construct_started`].
+    fn on_construct_finished(&self, key: &Key, elapsed: Duration, depth: usize) {
+        let _ = (key, elapsed, depth);
+    }
+
+    /// Called when a constructed instance is cached in the context,
+    /// i.e. a [`Singleton`](crate::Scope::Singleton), a [`SingleOwner`](crate::Scope::SingleOwner),
+    /// or an instance of a [`CustomScope`](crate::CustomScope).
+    fn on_single_cached(&self, key: &Key) {
+        let _ = key;
+    }
+
+    /// Called when a module is loaded into the context.
+    fn on_module_loaded(&self, module: &Type) {
+        let _ = module;
+    }
+
+    /// Called when a module is unloaded from the context.
+    fn on_module_unloaded(&self, module: &Type) {
+        let _ = module;
+    }
+}
@@ -1,6 +1,11 @@
-use std::{any::Any, borrow::Cow, rc::Rc};
+use std::{borrow::Cow, convert::Infallible};
 
-use crate::{BoxFuture, Color, Context, Definition, FutureExt, Key, Scope};
+use super::{
+    AsyncConstructor, AsyncPostConstruct, BoxError, BoxFuture, Color, Condition, ConditionContext,
+    Context, CustomScope, CustomScopeId, Definition, Dependency, DependencyKind, DynAny, DynSingle,
+    FutureExt, Key, MaybeSend, MaybeSendSync, PresenceCondition, ResolveError, Scope, Shared,
+    Single, SyncConstructor, SyncPostConstruct, Type,
+};
 
 /// A trait for giving a type a default [`Provider`].
 ///
@@ -34,29 +39,56 @@ pub trait DefaultProvider {
 }
 
 pub(crate) enum Constructor<T> {
-    #[allow(clippy::type_complexity)]
-    Async(Rc<dyn for<'a> Fn(&'a mut Context) -> BoxFuture<'a, T>>),
-    Sync(Rc<dyn Fn(&mut Context) -> T>),
+    Async(AsyncConstructor<T>),
+    Sync(SyncConstructor<T>),
     None,
 }
 
 impl<T> Clone for Constructor<T> {
     fn clone(&self) -> Self {
         match self {
-            Self::Async(c) => Self::Async(Rc::clone(c)),
-            Self::Sync(c) => Self::Sync(Rc::clone(c)),
+            Self::Async(c) => Self::Async(Shared::clone(c)),
+            Self::Sync(c) => Self::Sync(Shared::clone(c)),
             Self::None => Self::None,
         }
     }
 }
 
+pub(crate) enum PostConstruct<T> {
+    Async(AsyncPostConstruct<T>),
+    Sync(SyncPostConstruct<T>),
+}
+
+impl<T> Clone for PostConstruct<T> {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Async(p) => Self::Async(Shared::clone(p)),
+            Self::Sync(p) => Self::Sync(Shared::clone(p)),
+        }
+    }
+}
+
+pub(crate) enum Dispose<T> {
+    Async(fn(T) -> BoxFuture<'static, ()>),
+    Sync(fn(T)),
+}
+
+impl<T> Clone for Dispose<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for Dispose<T> {}
+
 /// Represents the eager create function.
 #[derive(Clone)]
 pub enum EagerCreateFunction {
     /// async eager create function.
-    Async(for<'a> fn(&'a mut Context, Cow<'static, str>) -> BoxFuture<'a, ()>),
+    #[allow(clippy::type_complexity)]
+    Async(for<'a> fn(&'a mut Context, Key) -> BoxFuture<'a, Result<(), ResolveError>>),
     /// sync eager create function.
-    Sync(fn(&mut Context, Cow<'static, str>)),
+    Sync(fn(&mut Context, Key) -> Result<(), ResolveError>),
     /// no eager create function.
     None,
 }
@@ -73,6 +105,12 @@ pub enum EagerCreateFunction {
 ///   - [`singleton_async`](crate::singleton_async)
 ///   - [`transient_async`](crate::transient_async)
 ///   - [`single_owner_async`](crate::single_owner_async)
+///   - [`try_singleton`](crate::try_singleton)
+///   - [`try_transient`](crate::try_transient)
+///   - [`try_single_owner`](crate::try_single_owner)
+///   - [`try_singleton_async`](crate::try_singleton_async)
+///   - [`try_transient_async`](crate::try_transient_async)
+///   - [`try_single_owner_async`](crate::try_single_owner_async)
 /// - attribute macros
 ///   - [`Singleton`](crate::Singleton)
 ///   - [`Transient`](crate::Transient)
@@ -80,9 +118,13 @@ pub enum EagerCreateFunction {
 pub struct Provider<T> {
     definition: Definition,
     eager_create: bool,
-    condition: Option<fn(&Context) -> bool>,
+    condition: Option<Condition>,
     constructor: Constructor<T>,
     clone_instance: Option<fn(&T) -> T>,
+    erase_single: Option<fn(Single<T>) -> DynSingle>,
+    post_construct: Option<PostConstruct<T>>,
+    dispose: Option<Dispose<T>>,
+    dependencies: Option<Vec<Dependency>>,
     eager_create_function: EagerCreateFunction,
     binding_providers: Option<Vec<DynProvider>>,
     binding_definitions: Option<Vec<Definition>>,
@@ -104,9 +146,23 @@ impl<T> Provider<T> {
         self.binding_definitions.as_ref()
     }
 
+    /// Returns the dependencies declared by the provider.
+    pub fn dependencies(&self) -> &[Dependency] {
+        self.dependencies.as_deref().unwrap_or_default()
+    }
+
     /// Returns an option of the condition function.
+    ///
+    /// Returns `None` for a condition set by a closure taking a [`ConditionContext`],
+    /// use [`condition_with`](Self::condition_with) to get any kind of condition.
     pub fn condition(&self) -> Option<fn(&Context) -> bool> {
-        self.condition
+        self.condition.as_ref().and_then(Condition::function)
+    }
+
+    /// Returns an option of the condition, either set by a function taking a [`Context`]
+    /// or by a closure taking a [`ConditionContext`].
+    pub fn condition_with(&self) -> Option<&dyn Fn(&ConditionContext) -> bool> {
+        self.condition.as_ref().map(Condition::evaluate)
     }
 
     pub(crate) fn constructor(&self) -> Constructor<T> {
@@ -116,16 +172,48 @@ impl<T> Provider<T> {
     pub(crate) fn clone_instance(&self) -> Option<fn(&T) -> T> {
         self.clone_instance
     }
+
+    pub(crate) fn erase_single(&self) -> Option<fn(Single<T>) -> DynSingle> {
+        self.erase_single
+    }
+
+    pub(crate) fn post_construct(&self) -> Option<PostConstruct<T>> {
+        self.post_construct.clone()
+    }
+
+    pub(crate) fn dispose(&self) -> Option<Dispose<T>> {
+        self.dispose
+    }
+
+    /// Returns a provider that shares the constructor and the hooks of this one, without the binding providers.
+    fn share(&self) -> Self {
+        Provider {
+            definition: self.definition.clone(),
+            eager_create: self.eager_create,
+            condition: self.condition.clone(),
+            constructor: self.constructor.clone(),
+            clone_instance: self.clone_instance,
+            erase_single: self.erase_single,
+            post_construct: self.post_construct.clone(),
+            dispose: self.dispose,
+            dependencies: self.dependencies.clone(),
+            eager_create_function: self.eager_create_function.clone(),
+            binding_providers: None,
+            binding_definitions: self.binding_definitions.clone(),
+        }
+    }
 }
 
 impl<T: 'static> Provider<T> {
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn with_name(
         name: Cow<'static, str>,
         scope: Scope,
         eager_create: bool,
-        condition: Option<fn(&Context) -> bool>,
+        condition: Option<Condition>,
         constructor: Constructor<T>,
         clone_instance: Option<fn(&T) -> T>,
+        erase_single: Option<fn(Single<T>) -> DynSingle>,
         eager_create_function: EagerCreateFunction,
     ) -> Self {
         let definition = Definition::new::<T>(
@@ -145,6 +233,10 @@ impl<T: 'static> Provider<T> {
             condition,
             constructor,
             clone_instance,
+            erase_single,
+            post_construct: None,
+            dispose: None,
+            dependencies: None,
             eager_create_function,
             binding_providers: None,
             binding_definitions: None,
@@ -154,17 +246,37 @@ impl<T: 'static> Provider<T> {
     pub(crate) fn with_definition(
         definition: Definition,
         eager_create: bool,
-        condition: Option<fn(&Context) -> bool>,
+        condition: Option<Condition>,
         constructor: Constructor<T>,
         clone_instance: Option<fn(&T) -> T>,
+        erase_single: Option<fn(Single<T>) -> DynSingle>,
         eager_create_function: EagerCreateFunction,
     ) -> Self {
+        // a binding provider takes the instance of the provider it is bound from
+        let dependencies = definition
+            .origin
+            .map(|ty| Dependency {
+                key: Key {
+                    name: definition.key.name.clone(),
+                    ty,
+                    qualifier: definition.key.qualifier,
+                },
+                kind: DependencyKind::Required,
+                by_ref: false,
+            })
+            .into_iter()
+            .collect();
+
         Provider {
             definition,
             eager_create,
             condition,
             constructor,
             clone_instance,
+            erase_single,
+            post_construct: None,
+            dispose: None,
+            dependencies: Some(dependencies),
             eager_create_function,
             binding_providers: None,
             binding_definitions: None,
@@ -178,6 +290,10 @@ impl<T: 'static> Provider<T> {
             condition: None,
             constructor: Constructor::None,
             clone_instance: None,
+            erase_single: None,
+            post_construct: None,
+            dispose: None,
+            dependencies: None,
             eager_create_function: EagerCreateFunction::None,
             binding_providers: None,
             binding_definitions: None,
@@ -189,11 +305,13 @@ impl<T: 'static> Provider<T> {
 pub struct DynProvider {
     definition: Definition,
     eager_create: bool,
-    condition: Option<fn(&Context) -> bool>,
+    condition: Option<Condition>,
     eager_create_function: EagerCreateFunction,
     binding_providers: Option<Vec<DynProvider>>,
     binding_definitions: Option<Vec<Definition>>,
-    origin: Box<dyn Any>,
+    dependencies: Option<Vec<Dependency>>,
+    origin: Box<DynAny>,
+    share: fn(&DynAny) -> Box<DynAny>,
 }
 
 impl DynProvider {
@@ -212,20 +330,39 @@ impl DynProvider {
         self.binding_definitions.as_ref()
     }
 
+    /// Returns the dependencies declared by the provider.
+    pub fn dependencies(&self) -> &[Dependency] {
+        self.dependencies.as_deref().unwrap_or_default()
+    }
+
     /// Returns a reference of the origin [`Provider`].
     pub fn as_provider<T: 'static>(&self) -> Option<&Provider<T>> {
         self.origin.downcast_ref::<Provider<T>>()
     }
 
     /// Returns an option of the condition function.
+    ///
+    /// Returns `None` for a condition set by a closure taking a [`ConditionContext`],
+    /// use [`condition_with`](Self::condition_with) to get any kind of condition.
     pub fn condition(&self) -> Option<fn(&Context) -> bool> {
-        self.condition
+        self.condition.as_ref().and_then(Condition::function)
+    }
+
+    /// Returns an option of the condition, either set by a function taking a [`Context`]
+    /// or by a closure taking a [`ConditionContext`].
+    pub fn condition_with(&self) -> Option<&dyn Fn(&ConditionContext) -> bool> {
+        self.condition.as_ref().map(Condition::evaluate)
     }
 
     pub(crate) fn key(&self) -> &Key {
         &self.definition.key
     }
 
+    /// Returns whether the dependencies of the provider are declared, even if there are none.
+    pub(crate) fn declares_dependencies(&self) -> bool {
+        self.dependencies.is_some()
+    }
+
     pub(crate) fn eager_create_function(&self) -> EagerCreateFunction {
         self.eager_create_function.clone()
     }
@@ -233,6 +370,21 @@ impl DynProvider {
     pub(crate) fn binding_providers(&mut self) -> Option<Vec<DynProvider>> {
         self.binding_providers.take()
     }
+
+    /// Returns a provider that shares the constructor and the hooks of this one, without the binding providers.
+    pub(crate) fn share(&self) -> Self {
+        Self {
+            definition: self.definition.clone(),
+            eager_create: self.eager_create,
+            condition: self.condition.clone(),
+            eager_create_function: self.eager_create_function.clone(),
+            binding_providers: None,
+            binding_definitions: self.binding_definitions.clone(),
+            dependencies: self.dependencies.clone(),
+            origin: (self.share)(self.origin.as_ref()),
+            share: self.share,
+        }
+    }
 }
 
 impl<T: 'static> From<Provider<T>> for DynProvider {
@@ -240,70 +392,111 @@ impl<T: 'static> From<Provider<T>> for DynProvider {
         Self {
             definition: value.definition.clone(),
             eager_create: value.eager_create,
-            condition: value.condition,
+            condition: value.condition.clone(),
             eager_create_function: value.eager_create_function.clone(),
             binding_providers: value.binding_providers.take(),
             binding_definitions: value.binding_definitions.clone(),
+            dependencies: value.dependencies.clone(),
             origin: Box::new(value),
+            share: |origin| {
+                let provider = origin.downcast_ref::<Provider<T>>().unwrap_or_else(|| {
+                    unreachable!("the origin of a `DynProvider` must be a `Provider<T>`")
+                });
+
+                Box::new(provider.share())
+            },
         }
     }
 }
 
-fn sync_constructor<T, U, F>(name: Cow<'static, str>, transform: F) -> Rc<dyn Fn(&mut Context) -> U>
+fn sync_constructor<T, U, F>(key: Key, transform: F) -> SyncConstructor<U>
 where
     T: 'static,
-    F: Fn(T) -> U + 'static,
+    F: Fn(T) -> U + 'static + MaybeSendSync,
 {
-    let constructor = move |cx: &mut Context| {
-        let instance = cx.resolve_with_name(name.clone());
-        transform(instance)
+    let constructor = move |cx: &mut Context| -> Result<U, BoxError> {
+        let instance = cx.try_resolve_by_key(key.clone())?;
+        Ok(transform(instance))
     };
 
-    Rc::new(constructor)
+    Shared::new(constructor)
 }
 
-fn sync_eager_create_function<T: 'static>() -> fn(&mut Context, Cow<'static, str>) {
-    |cx, name| {
-        cx.just_create::<T>(name);
-    }
+fn sync_eager_create_function<T: 'static>() -> fn(&mut Context, Key) -> Result<(), ResolveError> {
+    |cx, key| cx.just_create::<T>(key)
 }
 
-#[allow(clippy::type_complexity)]
-fn async_constructor<T, U, F>(
-    name: Cow<'static, str>,
-    transform: F,
-) -> Rc<dyn for<'a> Fn(&'a mut Context) -> BoxFuture<'a, U>>
+fn async_constructor<T, U, F>(key: Key, transform: F) -> AsyncConstructor<U>
 where
     T: 'static,
-    F: Fn(T) -> U + 'static + Clone,
+    F: Fn(T) -> U + 'static + Clone + MaybeSendSync,
 {
     fn helper<'a, F, T, U>(
         cx: &'a mut Context,
-        name: Cow<'static, str>,
+        key: Key,
         transform: F,
-    ) -> BoxFuture<'a, U>
+    ) -> BoxFuture<'a, Result<U, BoxError>>
     where
         T: 'static,
-        F: Fn(T) -> U + 'static,
+        F: Fn(T) -> U + 'static + MaybeSendSync,
     {
         async move {
-            let instance = cx.resolve_with_name_async(name).await;
-            transform(instance)
+            let instance = cx.try_resolve_by_key_async(key).await?;
+            Ok::<_, BoxError>(transform(instance))
         }
         .boxed()
     }
 
-    Rc::new(move |cx| helper(cx, name.clone(), transform.clone()))
+    Shared::new(move |cx| helper(cx, key.clone(), transform.clone()))
 }
 
-fn async_eager_create_function<T: 'static>(
-) -> for<'a> fn(&'a mut Context, Cow<'static, str>) -> BoxFuture<'a, ()> {
-    |cx, name| {
-        async {
-            cx.just_create_async::<T>(name).await;
+fn fallible_async_constructor<T, E, C>(constructor: C) -> AsyncConstructor<T>
+where
+    T: 'static,
+    E: Into<BoxError> + 'static,
+    C: for<'a> Fn(&'a mut Context) -> BoxFuture<'a, Result<T, E>> + 'static + MaybeSendSync,
+{
+    fn helper<T, E>(future: BoxFuture<'_, Result<T, E>>) -> BoxFuture<'_, Result<T, BoxError>>
+    where
+        T: 'static,
+        E: Into<BoxError> + 'static,
+    {
+        async move { future.await.map_err(Into::into) }.boxed()
+    }
+
+    Shared::new(move |cx| helper(constructor(cx)))
+}
+
+fn async_post_construct<T, F>(post_construct: F) -> AsyncPostConstruct<T>
+where
+    T: 'static + MaybeSend,
+    F: for<'a> Fn(&'a mut T, &'a mut Context) -> BoxFuture<'a, ()> + 'static + MaybeSendSync,
+{
+    fn helper<'a, T, F>(
+        mut instance: T,
+        cx: &'a mut Context,
+        post_construct: Shared<F>,
+    ) -> BoxFuture<'a, T>
+    where
+        T: 'static + MaybeSend,
+        F: for<'b> Fn(&'b mut T, &'b mut Context) -> BoxFuture<'b, ()> + 'static + MaybeSendSync,
+    {
+        async move {
+            post_construct(&mut instance, cx).await;
+            instance
         }
         .boxed()
     }
+
+    let post_construct = Shared::new(post_construct);
+
+    Shared::new(move |instance, cx| helper(instance, cx, Shared::clone(&post_construct)))
+}
+
+#[allow(clippy::type_complexity)]
+fn async_eager_create_function<T: 'static>(
+) -> for<'a> fn(&'a mut Context, Key) -> BoxFuture<'a, Result<(), ResolveError>> {
+    |cx, key| cx.just_create_async::<T>(key).boxed()
 }
 
 macro_rules! define_provider_common {
@@ -320,8 +513,17 @@ macro_rules! define_provider_common {
             constructor: Constructor<T>,
             name: Cow<'static, str>,
             eager_create: bool,
-            condition: Option<fn(&Context) -> bool>,
-            bind_closures: Vec<Box<dyn FnOnce(Definition, bool, Option<fn(&Context) -> bool>) -> DynProvider>>,
+            condition: Option<Condition>,
+            order: i32,
+            primary: bool,
+            qualifier: Option<Type>,
+            profiles: Vec<Cow<'static, str>>,
+            presence: Vec<PresenceCondition>,
+            scope: Scope,
+            post_construct: Option<PostConstruct<T>>,
+            dispose: Option<Dispose<T>>,
+            dependencies: Option<Vec<Dependency>>,
+            bind_closures: Vec<Box<dyn FnOnce(Definition, bool, Option<Condition>) -> DynProvider>>,
         }
 
         impl<T> $provider<T> {
@@ -334,6 +536,15 @@ macro_rules! define_provider_common {
                 self
             }
 
+            /// Sets the marker type that qualifies the provider, in addition to the name.
+            ///
+            /// Unlike a name, a qualifier is checked by the compiler,
+            /// the qualified instance is resolved by [`Context::resolve_qualified`] and its variants.
+            pub fn qualifier<Q: 'static>(mut self) -> Self {
+                self.qualifier = Some(Type::new::<Q>());
+                self
+            }
+
             /// Sets whether the provider is eager to create.
             pub fn eager_create(mut self, eager_create: bool) -> Self {
                 self.eager_create = eager_create;
@@ -341,8 +552,89 @@ macro_rules! define_provider_common {
             }
 
             /// Sets whether or not to insert the provider into the [`Context`] based on the condition.
+            ///
+            /// The condition may be evaluated several times, until the conditions of all the providers agree,
+            /// see [`Context::flush`] for more details.
+            /// Since it reads the whole [`Context`], it is evaluated again whenever any provider is inserted or removed,
+            /// use [`condition_with`](Self::condition_with) for a condition that only depends on the providers it queries.
             pub fn condition(mut self, condition: Option<fn(&Context) -> bool>) -> Self {
-                self.condition = condition;
+                self.condition = condition.map(Condition::from_function);
+                self
+            }
+
+            /// Sets the condition of the provider as a closure taking a [`ConditionContext`],
+            /// which can capture runtime state and query the environment variables, see [`condition`](Self::condition).
+            ///
+            /// The condition is evaluated again only when the providers of the types it queries change,
+            /// unless it accesses the [`Context`] the [`ConditionContext`] dereferences to.
+            pub fn condition_with<F>(mut self, condition: F) -> Self
+            where
+                F: Fn(&ConditionContext) -> bool + 'static + MaybeSendSync,
+            {
+                self.condition = Some(Condition::from_closure(condition));
+                self
+            }
+
+            /// Sets the priority of the provider among the providers of the same type, default is `0`.
+            ///
+            /// The instances resolved by type, e.g. by [`Context::resolve_by_type`],
+            /// are sorted by the priority in ascending order, then by registration order.
+            pub fn order(mut self, order: i32) -> Self {
+                self.order = order;
+                self
+            }
+
+            /// Sets whether the provider is the primary provider of its type.
+            ///
+            /// When no provider is registered for the type with the default name `""`,
+            /// the methods of the [`Context`] that take the default name, e.g. [`Context::resolve`],
+            /// fall back to the primary provider.
+            ///
+            /// The providers produced by [`bind`](Self::bind) are never primary.
+            /// Several primary providers of the same type in a [`Context`] are reported
+            /// as [`ResolveErrorKind::MultiplePrimaryProviders`](crate::ResolveErrorKind::MultiplePrimaryProviders)
+            /// when the context is flushed, see [`Context::try_flush`].
+            pub fn primary(mut self, primary: bool) -> Self {
+                self.primary = primary;
+                self
+            }
+
+            /// Sets the profiles in which the provider is active, default is all profiles.
+            ///
+            /// A provider with profiles is only inserted into the [`Context`] when one of them is active,
+            /// see [`ContextOptions::profiles`](crate::ContextOptions::profiles).
+            pub fn profiles<I, P>(mut self, profiles: I) -> Self
+            where
+                I: IntoIterator<Item = P>,
+                P: Into<Cow<'static, str>>,
+            {
+                self.profiles = profiles.into_iter().map(Into::into).collect();
+                self
+            }
+
+            /// Sets the provider to be inserted into the [`Context`] only when no provider of type `U` is registered,
+            /// e.g. a default implementation that a library ships and an application can replace.
+            ///
+            /// Presence conditions are checked after all the unconditional providers and the providers with
+            /// a [`condition`](Self::condition) are loaded, so the result does not depend on the order of the modules.
+            /// The providers bound from the provider, see [`bind`](Self::bind), are not taken into account.
+            pub fn on_missing<U: 'static>(mut self) -> Self {
+                self.presence.push(PresenceCondition::Missing(Type::new::<U>()));
+                self
+            }
+
+            /// Sets the provider to be inserted into the [`Context`] only when a provider of type `U` is registered,
+            /// see [`on_missing`](Self::on_missing) for when it is checked.
+            pub fn on_present<U: 'static>(mut self) -> Self {
+                self.presence.push(PresenceCondition::Present(Type::new::<U>()));
+                self
+            }
+
+            /// Sets the dependencies of the provider, which are checked by [`Context::validate`].
+            ///
+            /// The attribute macros, e.g. [`#[Singleton]`](crate::Singleton), set them from the fields or arguments.
+            pub fn dependencies(mut self, dependencies: Vec<Dependency>) -> Self {
+                self.dependencies = Some(dependencies);
                 self
             }
         }
@@ -360,7 +652,9 @@ macro_rules! define_provider_sync {
         $provider:ident,
         $scope:expr,
         $function:ident,
+        $try_function:ident,
         $clone_instance:expr,
+        $erase_single:expr,
         $(+ $bound:ident)*
     ) => {
         #[doc = concat!("create a [`", stringify!($provider), "`] instance")]
@@ -379,17 +673,85 @@ macro_rules! define_provider_sync {
         /// ```
         pub fn $function<T, C>(constructor: C) -> $provider<T>
         where
-            C: Fn(&mut Context) -> T + 'static,
+            C: Fn(&mut Context) -> T + 'static + MaybeSendSync,
+        {
+            $try_function(move |cx| Ok::<_, Infallible>(constructor(cx)))
+        }
+
+        #[doc = concat!("create a [`", stringify!($provider), "`] instance with a fallible constructor")]
+        ///
+        /// If the constructor returns an error, the error will be returned by the non-panicking methods of the [`Context`],
+        /// e.g. [`Context::try_resolve`] and [`Context::try_create`](crate::Context::try_create),
+        /// and the panicking methods will panic with it.
+        ///
+        /// An error of type [`ResolveError`] is returned as is, so that the errors of the dependencies can be propagated with `?`.
+        ///
+        /// # Example
+        ///
+        /// ```rust
+        #[doc = concat!("use rudi::{", stringify!($try_function), ", ", stringify!($provider), "};")]
+        ///
+        /// #[derive(Clone)]
+        /// struct A(i32);
+        ///
+        /// fn main() {
+        #[doc = concat!("    let _: ", stringify!($provider), "<A> = ", stringify!($try_function), "(|cx| cx.try_resolve().map(A));")]
+        /// }
+        /// ```
+        pub fn $try_function<T, E, C>(constructor: C) -> $provider<T>
+        where
+            C: Fn(&mut Context) -> Result<T, E> + 'static + MaybeSendSync,
+            E: Into<BoxError>,
         {
             $provider {
-                constructor: Constructor::Sync(Rc::new(constructor)),
+                constructor: Constructor::Sync(Shared::new(move |cx: &mut Context| {
+                    constructor(cx).map_err(Into::into)
+                })),
                 name: Cow::Borrowed(""),
                 eager_create: false,
                 condition: None,
+                order: 0,
+                primary: false,
+                qualifier: None,
+                profiles: Vec::new(),
+                presence: Vec::new(),
+                scope: $scope,
+                post_construct: None,
+                dispose: None,
+                dependencies: None,
                 bind_closures: Vec::new(),
             }
         }
 
+        impl<T> $provider<T> {
+            /// Sets a post-construct hook of the provider, which is called with the instance
+            /// and the [`Context`] after the instance is constructed, and before it is cached or returned.
+            ///
+            /// The hook can be used for setup that needs the injected dependencies,
+            /// e.g. warming caches or registering the instance with another singleton.
+            ///
+            /// # Example
+            ///
+            /// ```rust
+            #[doc = concat!("use rudi::{", stringify!($function), ", ", stringify!($provider), "};")]
+            ///
+            /// #[derive(Clone)]
+            /// struct A(i32);
+            ///
+            /// fn main() {
+            #[doc = concat!("    let _: ", stringify!($provider), "<A> = ", stringify!($function), "(|_| A(1))")]
+            ///         .post_construct(|a, _cx| a.0 += 1);
+            /// }
+            /// ```
+            pub fn post_construct<F>(mut self, post_construct: F) -> Self
+            where
+                F: Fn(&mut T, &mut Context) + 'static + MaybeSendSync,
+            {
+                self.post_construct = Some(PostConstruct::Sync(Shared::new(post_construct)));
+                self
+            }
+        }
+
         impl<T: 'static> $provider<T> {
             /// Create a provider of type [`Provider<U>`], save it to the current provider.
             ///
@@ -404,20 +766,20 @@ macro_rules! define_provider_sync {
             /// # Example
             ///
             /// ```rust
-            /// use std::{fmt::Debug, rc::Rc, sync::Arc};
+            /// use std::{fmt::Debug, sync::Arc};
             ///
             #[doc = concat!("use rudi::{", stringify!($function), ", Provider, ", stringify!($provider), "};")]
             ///
             /// #[derive(Clone, Debug)]
             /// struct A(i32);
             ///
-            /// fn into_debug(a: A) -> Rc<dyn Debug> {
-            ///     Rc::new(a)
+            /// fn into_debug(a: A) -> Arc<dyn Debug + Send + Sync> {
+            ///     Arc::new(a)
             /// }
             ///
             /// fn main() {
             #[doc = concat!("    let p: ", stringify!($provider), "<A> = ", stringify!($function), "(|cx| A(cx.resolve()))")]
-            ///         .bind(Rc::new)
+            ///         .bind(Some)
             ///         .bind(Arc::new)
             ///         .bind(Box::new)
             ///         .bind(into_debug);
@@ -430,17 +792,18 @@ macro_rules! define_provider_sync {
             pub fn bind<U, F>(mut self, transform: F) -> Self
             where
                 U: 'static $(+ $bound)*,
-                F: Fn(T) -> U + 'static,
+                F: Fn(T) -> U + 'static + MaybeSendSync,
             {
-                let bind_closure = |definition: Definition, eager_create: bool, condition: Option<fn(&Context) -> bool>| {
-                    let name = definition.key.name.clone();
+                let bind_closure = |definition: Definition, eager_create: bool, condition: Option<Condition>| {
+                    let key = definition.key.clone();
 
                     Provider::with_definition(
                         definition.bind::<U>(),
                         eager_create,
                         condition,
-                        Constructor::Sync(sync_constructor(name, transform)),
+                        Constructor::Sync(sync_constructor(key, transform)),
                         $clone_instance,
+                        $erase_single,
                         EagerCreateFunction::Sync(
                             sync_eager_create_function::<U>()
                         ),
@@ -462,21 +825,41 @@ macro_rules! define_provider_sync {
                     name,
                     eager_create,
                     condition,
+                    order,
+                    primary,
+                    qualifier,
+                    profiles,
+                    presence,
+                    scope,
+                    post_construct,
+                    dispose,
+                    dependencies,
                     bind_closures,
                 } = value;
 
                 let mut provider = Provider::with_name(
                     name,
-                    $scope,
+                    scope,
                     eager_create,
-                    condition,
+                    condition.clone(),
                     constructor,
                     $clone_instance,
+                    $erase_single,
                     EagerCreateFunction::Sync(
                         sync_eager_create_function::<T>()
                     ),
                 );
 
+                provider.definition.order = order;
+                provider.definition.primary = primary;
+                provider.definition.key.qualifier = qualifier;
+                provider.definition.profiles = profiles;
+                provider.definition.conditional |= !presence.is_empty();
+                provider.definition.presence = presence;
+                provider.post_construct = post_construct;
+                provider.dispose = dispose;
+                provider.dependencies = dependencies;
+
                 if bind_closures.is_empty() {
                     return provider;
                 }
@@ -485,7 +868,7 @@ macro_rules! define_provider_sync {
 
                 let (definitions, providers) = bind_closures.into_iter()
                     .map(|bind_closure| {
-                        let provider = bind_closure(definition.clone(), eager_create, condition);
+                        let provider = bind_closure(definition.clone(), eager_create, condition.clone());
                         (provider.definition.clone(), provider)
                     })
                     .unzip();
@@ -504,7 +887,9 @@ macro_rules! define_provider_async {
         $provider:ident,
         $scope:expr,
         $function:ident,
+        $try_function:ident,
         $clone_instance:expr,
+        $erase_single:expr,
         $(+ $bound:ident)*
     ) => {
         #[doc = concat!("Create a [`", stringify!($provider), "`] instance")]
@@ -524,17 +909,111 @@ macro_rules! define_provider_async {
         /// ```
         pub fn $function<T, C>(constructor: C) -> $provider<T>
         where
-            C: for<'a> Fn(&'a mut Context) -> BoxFuture<'a, T> + 'static,
+            C: for<'a> Fn(&'a mut Context) -> BoxFuture<'a, T> + 'static + MaybeSendSync,
+            T: 'static,
+        {
+            $try_function(move |cx| {
+                let future = constructor(cx);
+                async move { Ok::<_, Infallible>(future.await) }.boxed()
+            })
+        }
+
+        #[doc = concat!("Create a [`", stringify!($provider), "`] instance with a fallible constructor")]
+        ///
+        #[doc = concat!("See [`", stringify!($try_function), "`] for more details.")]
+        ///
+        /// # Example
+        ///
+        /// ```rust
+        #[doc = concat!("use rudi::{", stringify!($try_function), ", FutureExt, ", stringify!($provider), "};")]
+        ///
+        /// #[derive(Clone)]
+        /// struct A(i32);
+        ///
+        /// fn main() {
+        #[doc = concat!("    let _: ", stringify!($provider), "<A> =")]
+        #[doc = concat!("        ", stringify!($try_function), "(|cx| async { cx.try_resolve_async().await.map(A) }.boxed());")]
+        /// }
+        /// ```
+        pub fn $try_function<T, E, C>(constructor: C) -> $provider<T>
+        where
+            C: for<'a> Fn(&'a mut Context) -> BoxFuture<'a, Result<T, E>> + 'static + MaybeSendSync,
+            E: Into<BoxError> + 'static,
+            T: 'static,
         {
             $provider {
-                constructor: Constructor::Async(Rc::new(constructor)),
+                constructor: Constructor::Async(fallible_async_constructor(constructor)),
                 name: Cow::Borrowed(""),
                 eager_create: false,
                 condition: None,
+                order: 0,
+                primary: false,
+                qualifier: None,
+                profiles: Vec::new(),
+                presence: Vec::new(),
+                scope: $scope,
+                post_construct: None,
+                dispose: None,
+                dependencies: None,
                 bind_closures: Vec::new(),
             }
         }
 
+        impl<T> $provider<T> {
+            /// Sets a post-construct hook of the provider, which is called with the instance
+            /// and the [`Context`] after the instance is constructed, and before it is cached or returned.
+            ///
+            /// The hook can be used for setup that needs the injected dependencies,
+            /// e.g. warming caches or registering the instance with another singleton.
+            ///
+            /// # Example
+            ///
+            /// ```rust
+            #[doc = concat!("use rudi::{", stringify!($function), ", FutureExt, ", stringify!($provider), "};")]
+            ///
+            /// #[derive(Clone)]
+            /// struct A(i32);
+            ///
+            /// fn main() {
+            #[doc = concat!("    let _: ", stringify!($provider), "<A> = ", stringify!($function), "(|_| async { A(1) }.boxed())")]
+            ///         .post_construct(|a, _cx| a.0 += 1);
+            /// }
+            /// ```
+            pub fn post_construct<F>(mut self, post_construct: F) -> Self
+            where
+                F: Fn(&mut T, &mut Context) + 'static + MaybeSendSync,
+            {
+                self.post_construct = Some(PostConstruct::Sync(Shared::new(post_construct)));
+                self
+            }
+
+            /// Sets an async post-construct hook of the provider.
+            ///
+            /// See [`post_construct`](Self::post_construct) for more details.
+            ///
+            /// # Example
+            ///
+            /// ```rust
+            #[doc = concat!("use rudi::{", stringify!($function), ", FutureExt, ", stringify!($provider), "};")]
+            ///
+            /// #[derive(Clone)]
+            /// struct A(i32);
+            ///
+            /// fn main() {
+            #[doc = concat!("    let _: ", stringify!($provider), "<A> = ", stringify!($function), "(|_| async { A(1) }.boxed())")]
+            ///         .post_construct_async(|a, _cx| async { a.0 += 1 }.boxed());
+            /// }
+            /// ```
+            pub fn post_construct_async<F>(mut self, post_construct: F) -> Self
+            where
+                T: 'static + MaybeSend,
+                F: for<'a> Fn(&'a mut T, &'a mut Context) -> BoxFuture<'a, ()> + 'static + MaybeSendSync,
+            {
+                self.post_construct = Some(PostConstruct::Async(async_post_construct(post_construct)));
+                self
+            }
+        }
+
         impl<T: 'static> $provider<T> {
             /// Create a provider of type [`Provider<U>`], save it to the current provider.
             ///
@@ -549,21 +1028,21 @@ macro_rules! define_provider_async {
             /// # Example
             ///
             /// ```rust
-            /// use std::{fmt::Debug, rc::Rc, sync::Arc};
+            /// use std::{fmt::Debug, sync::Arc};
             ///
             #[doc = concat!("use rudi::{", stringify!($function), ", FutureExt, Provider, ", stringify!($provider), "};")]
             ///
             /// #[derive(Clone, Debug)]
             /// struct A(i32);
             ///
-            /// fn into_debug(a: A) -> Rc<dyn Debug> {
-            ///     Rc::new(a)
+            /// fn into_debug(a: A) -> Arc<dyn Debug + Send + Sync> {
+            ///     Arc::new(a)
             /// }
             ///
             /// fn main() {
             #[doc = concat!("    let p: ", stringify!($provider), "<A> =")]
             #[doc = concat!("        ", stringify!($function), "(|cx| async { A(cx.resolve_async().await) }.boxed())")]
-            ///             .bind(Rc::new)
+            ///             .bind(Some)
             ///             .bind(Arc::new)
             ///             .bind(Box::new)
             ///             .bind(into_debug);
@@ -576,17 +1055,18 @@ macro_rules! define_provider_async {
             pub fn bind<U, F>(mut self, transform: F) -> Self
             where
                 U: 'static $(+ $bound)*,
-                F: Fn(T) -> U + 'static + Clone,
+                F: Fn(T) -> U + 'static + Clone + MaybeSendSync,
             {
-                let bind_closure = |definition: Definition, eager_create: bool, condition: Option<fn(&Context) -> bool>| {
-                    let name = definition.key.name.clone();
+                let bind_closure = |definition: Definition, eager_create: bool, condition: Option<Condition>| {
+                    let key = definition.key.clone();
 
                     Provider::with_definition(
                         definition.bind::<U>(),
                         eager_create,
                         condition,
-                        Constructor::Async(async_constructor(name, transform)),
+                        Constructor::Async(async_constructor(key, transform)),
                         $clone_instance,
+                        $erase_single,
                         EagerCreateFunction::Async(
                             async_eager_create_function::<U>()
                         ),
@@ -608,21 +1088,41 @@ macro_rules! define_provider_async {
                     name,
                     eager_create,
                     condition,
+                    order,
+                    primary,
+                    qualifier,
+                    profiles,
+                    presence,
+                    scope,
+                    post_construct,
+                    dispose,
+                    dependencies,
                     bind_closures,
                 } = value;
 
                 let mut provider = Provider::with_name(
                     name,
-                    $scope,
+                    scope,
                     eager_create,
-                    condition,
+                    condition.clone(),
                     constructor,
                     $clone_instance,
+                    $erase_single,
                     EagerCreateFunction::Async(
                         async_eager_create_function::<T>()
                     ),
                 );
 
+                provider.definition.order = order;
+                provider.definition.primary = primary;
+                provider.definition.key.qualifier = qualifier;
+                provider.definition.profiles = profiles;
+                provider.definition.conditional |= !presence.is_empty();
+                provider.definition.presence = presence;
+                provider.post_construct = post_construct;
+                provider.dispose = dispose;
+                provider.dependencies = dependencies;
+
                 if bind_closures.is_empty() {
                     return provider;
                 }
@@ -631,7 +1131,7 @@ macro_rules! define_provider_async {
 
                 let (definitions, providers) = bind_closures.into_iter()
                     .map(|bind_closure| {
-                        let provider = bind_closure(definition.clone(), eager_create, condition);
+                        let provider = bind_closure(definition.clone(), eager_create, condition.clone());
                         (provider.definition.clone(), provider)
                     })
                     .unzip();
@@ -645,27 +1145,94 @@ macro_rules! define_provider_async {
     };
 }
 
-define_provider_common!(SingletonProvider, singleton, Some(Clone::clone), + Clone);
+define_provider_common!(SingletonProvider, singleton, Some(Clone::clone), + Clone + MaybeSendSync);
 define_provider_common!(TransientProvider, transient, None,);
-define_provider_common!(SingleOwnerProvider, single_owner, None,);
-define_provider_common!(SingletonAsyncProvider, singleton_async, Some(Clone::clone), + Clone);
+define_provider_common!(SingleOwnerProvider, single_owner, None, + MaybeSendSync);
+define_provider_common!(SingletonAsyncProvider, singleton_async, Some(Clone::clone), + Clone + MaybeSendSync);
 define_provider_common!(TransientAsyncProvider, transient_async, None,);
-define_provider_common!(SingleOwnerAsyncProvider, single_owner_async, None,);
+define_provider_common!(SingleOwnerAsyncProvider, single_owner_async, None, + MaybeSendSync);
 
-define_provider_sync!(SingletonProvider, Scope::Singleton, singleton, Some(Clone::clone), + Clone);
-define_provider_sync!(TransientProvider, Scope::Transient, transient, None,);
-define_provider_sync!(SingleOwnerProvider, Scope::SingleOwner, single_owner, None,);
+define_provider_sync!(SingletonProvider, Scope::Singleton, singleton, try_singleton, Some(Clone::clone), Some(DynSingle::from), + Clone + MaybeSendSync);
+define_provider_sync!(
+    TransientProvider,
+    Scope::Transient,
+    transient,
+    try_transient,
+    None,
+    None,
+);
+define_provider_sync!(
+    SingleOwnerProvider,
+    Scope::SingleOwner,
+    single_owner,
+    try_single_owner,
+    None,
+    Some(DynSingle::from),
+    + MaybeSendSync
+);
 
-define_provider_async!(SingletonAsyncProvider, Scope::Singleton, singleton_async, Some(Clone::clone), + Clone);
+define_provider_async!(SingletonAsyncProvider, Scope::Singleton, singleton_async, try_singleton_async, Some(Clone::clone), Some(DynSingle::from), + Clone + MaybeSendSync);
 define_provider_async!(
     TransientAsyncProvider,
     Scope::Transient,
     transient_async,
+    try_transient_async,
+    None,
     None,
 );
 define_provider_async!(
     SingleOwnerAsyncProvider,
     Scope::SingleOwner,
     single_owner_async,
+    try_single_owner_async,
     None,
+    Some(DynSingle::from),
+    + MaybeSendSync
 );
+
+macro_rules! define_provider_custom_scope {
+    ($provider:ident) => {
+        impl<T> $provider<T> {
+            /// Sets a [`CustomScope`] of the provider, instead of [`Scope::Singleton`].
+            ///
+            /// The custom scope must be registered in the [`Context`] with [`ContextOptions::custom_scope`](crate::ContextOptions::custom_scope).
+            pub fn scope<S: CustomScope>(mut self) -> Self {
+                self.scope = Scope::Custom(CustomScopeId::of::<S>());
+                self
+            }
+        }
+    };
+}
+
+define_provider_custom_scope!(SingletonProvider);
+define_provider_custom_scope!(SingletonAsyncProvider);
+
+macro_rules! define_provider_dispose {
+    ($provider:ident) => {
+        impl<T> $provider<T> {
+            /// Sets a dispose hook of the provider, which is called with the instance
+            /// when the instance is removed by [`Context::shutdown`], [`Context::shutdown_async`],
+            /// [`Context::unload_modules`] or [`Context::unload_modules_async`].
+            pub fn on_dispose(mut self, dispose: fn(T)) -> Self {
+                self.dispose = Some(Dispose::Sync(dispose));
+                self
+            }
+
+            /// Sets an async dispose hook of the provider.
+            ///
+            /// The instances with an async dispose hook can only be removed by
+            /// [`Context::shutdown_async`] or [`Context::unload_modules_async`].
+            ///
+            /// See [`on_dispose`](Self::on_dispose) for more details.
+            pub fn on_dispose_async(mut self, dispose: fn(T) -> BoxFuture<'static, ()>) -> Self {
+                self.dispose = Some(Dispose::Async(dispose));
+                self
+            }
+        }
+    };
+}
+
+define_provider_dispose!(SingletonProvider);
+define_provider_dispose!(SingleOwnerProvider);
+define_provider_dispose!(SingletonAsyncProvider);
+define_provider_dispose!(SingleOwnerAsyncProvider);
@@ -1,10 +1,12 @@
 use std::collections::HashMap;
 
-use crate::{DynProvider, DynSingle, Key, Provider};
+use super::{CachedInstance, DynProvider, DynSingle, Key, Provider};
 
 #[derive(Default)]
 pub(crate) struct SingleRegistry {
     registry: HashMap<Key, DynSingle>,
+    // keys in the order in which the singles were inserted
+    order: Vec<Key>,
 }
 
 impl SingleRegistry {
@@ -17,29 +19,141 @@ impl SingleRegistry {
         // because when inserting a provider and a single with the same key into the context,
         // the provider must be inserted first, followed by the single,
         // and the checking of `allow_override` has already been done when the provider is inserted.
-        self.registry.insert(key, single);
-    }
+        if self.registry.insert(key.clone(), single).is_some() {
+            self.order.retain(|k| k != &key);
+        }
 
-    pub(crate) fn get_owned<T: 'static>(&self, key: &Key) -> Option<T> {
-        self.registry.get(key)?.as_single::<T>()?.get_owned()
+        self.order.push(key);
     }
 
-    pub(crate) fn get_ref<T: 'static>(&self, key: &Key) -> Option<&T> {
-        Some(self.registry.get(key)?.as_single::<T>()?.get_ref())
+    pub(crate) fn get(&self, key: &Key) -> Option<&DynSingle> {
+        self.registry.get(key)
     }
 
     pub(crate) fn contains(&self, key: &Key) -> bool {
         self.registry.contains_key(key)
     }
 
-    pub(crate) fn remove(&mut self, key: &Key) -> Option<DynSingle> {
-        self.registry.remove(key)
+    /// Removes all the singles, in insertion order.
+    pub(crate) fn drain(&mut self) -> Vec<(Key, DynSingle)> {
+        let mut removed = self.remove_rev(|_| true);
+        removed.reverse();
+        removed
+    }
+
+    /// Removes the singles whose keys match the predicate, in reverse insertion order.
+    pub(crate) fn remove_rev(&mut self, mut f: impl FnMut(&Key) -> bool) -> Vec<(Key, DynSingle)> {
+        let mut removed = Vec::new();
+
+        self.order.retain(|key| {
+            if !f(key) {
+                return true;
+            }
+
+            if let Some(single) = self.registry.remove(key) {
+                removed.push((key.clone(), single));
+            }
+
+            false
+        });
+
+        removed.reverse();
+        removed
+    }
+}
+
+impl Clone for SingleRegistry {
+    fn clone(&self) -> Self {
+        Self {
+            registry: self
+                .registry
+                .iter()
+                .map(|(key, single)| (key.clone(), single.share()))
+                .collect(),
+            order: self.order.clone(),
+        }
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct ScopedRegistry {
+    registry: HashMap<Key, HashMap<u64, (DynSingle, CachedInstance)>>,
+    // keys and partitions in the order in which the singles were inserted
+    order: Vec<(Key, u64)>,
+}
+
+impl ScopedRegistry {
+    pub(crate) fn get(&self, key: &Key, partition: u64) -> Option<&(DynSingle, CachedInstance)> {
+        self.registry.get(key)?.get(&partition)
+    }
+
+    pub(crate) fn insert(&mut self, key: Key, partition: u64, single: DynSingle) {
+        let old = self
+            .registry
+            .entry(key.clone())
+            .or_default()
+            .insert(partition, (single, CachedInstance::new()));
+
+        if old.is_some() {
+            self.order.retain(|(k, p)| k != &key || *p != partition);
+        }
+
+        self.order.push((key, partition));
+    }
+
+    pub(crate) fn remove(&mut self, key: &Key, partition: u64) {
+        if let Some(partitions) = self.registry.get_mut(key) {
+            if partitions.remove(&partition).is_some() {
+                self.order.retain(|(k, p)| k != key || *p != partition);
+            }
+        }
+    }
+
+    /// Removes the singles whose keys match the predicate, in reverse insertion order.
+    pub(crate) fn remove_rev(&mut self, mut f: impl FnMut(&Key) -> bool) -> Vec<(Key, DynSingle)> {
+        let mut removed = Vec::new();
+
+        self.order.retain(|(key, partition)| {
+            if !f(key) {
+                return true;
+            }
+
+            if let Some((single, _)) = self
+                .registry
+                .get_mut(key)
+                .and_then(|partitions| partitions.remove(partition))
+            {
+                removed.push((key.clone(), single));
+            }
+
+            false
+        });
+
+        self.registry.retain(|_, partitions| !partitions.is_empty());
+
+        removed.reverse();
+        removed
     }
 }
 
 #[derive(Default)]
 pub(crate) struct ProviderRegistry {
     registry: HashMap<Key, DynProvider>,
+    // the keys in insertion order, an overriding provider takes the place of the overridden one
+    order: Vec<Key>,
+}
+
+impl Clone for ProviderRegistry {
+    fn clone(&self) -> Self {
+        Self {
+            registry: self
+                .registry
+                .iter()
+                .map(|(key, provider)| (key.clone(), provider.share()))
+                .collect(),
+            order: self.order.clone(),
+        }
+    }
 }
 
 impl ProviderRegistry {
@@ -47,25 +161,39 @@ impl ProviderRegistry {
         &self.registry
     }
 
+    /// Returns the providers in insertion order.
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&Key, &DynProvider)> {
+        self.order.iter().map(|key| (key, &self.registry[key]))
+    }
+
+    /// Inserts the provider, returns whether it overrides a provider with the same key.
     #[track_caller]
-    pub(crate) fn insert(&mut self, provider: DynProvider, allow_override: bool) {
+    pub(crate) fn insert(&mut self, provider: DynProvider, allow_override: bool) -> bool {
         let definition = provider.definition();
         let key = provider.key().clone();
 
-        if !self.registry.contains_key(&key) {
+        let overridden = if !self.registry.contains_key(&key) {
             #[cfg(feature = "tracing")]
             tracing::debug!("(+) insert new: {:?}", definition);
+            false
         } else if allow_override {
             #[cfg(feature = "tracing")]
             tracing::warn!("(!) override by `key`: {:?}", definition);
+            true
         } else {
             panic!(
                 "already existing a provider with the same `key`: {:?}",
                 definition
             );
+        };
+
+        if !overridden {
+            self.order.push(key.clone());
         }
 
         self.registry.insert(key, provider);
+
+        overridden
     }
 
     pub(crate) fn get<T: 'static>(&self, key: &Key) -> Option<&Provider<T>> {
@@ -77,6 +205,21 @@ impl ProviderRegistry {
     }
 
     pub(crate) fn remove(&mut self, key: &Key) -> Option<DynProvider> {
-        self.registry.remove(key)
+        let provider = self.registry.remove(key)?;
+        self.order.retain(|k| k != key);
+        Some(provider)
+    }
+
+    /// Inserts the provider without any check, returns the provider with the same key it replaces,
+    /// the inserted provider takes the place of the replaced one.
+    pub(crate) fn replace(&mut self, provider: DynProvider) -> Option<DynProvider> {
+        let key = provider.key().clone();
+        let replaced = self.registry.insert(key.clone(), provider);
+
+        if replaced.is_none() {
+            self.order.push(key);
+        }
+
+        replaced
     }
 }
@@ -0,0 +1,209 @@
+use std::{
+    collections::HashSet,
+    fmt::{self, Display, Formatter},
+    time::{Duration, Instant},
+};
+
+use crate::{graph::scope_label, Definition, Key};
+
+/// A report of the instances created while a [`Context`](crate::Context) was created or flushed,
+/// e.g. by [`Context::create`](crate::Context::create) or [`Context::flush`](crate::Context::flush),
+/// and the registered providers that have never been resolved.
+///
+/// Returned by [`Context::startup_report`](crate::Context::startup_report).
+///
+/// The [`Display`] implementation renders the report as a human-readable table.
+///
+/// # Example
+///
+/// ```rust
+/// use rudi::{Context, Singleton, Transient};
+///
+/// #[derive(Clone)]
+/// #[Singleton(eager_create)]
+/// struct Database;
+///
+/// #[derive(Clone)]
+/// #[Singleton(eager_create)]
+/// struct Repository(Database);
+///
+/// #[Transient]
+/// struct Unused;
+///
+/// # fn main() {
+/// let cx = Context::auto_register();
+///
+/// let report = cx.startup_report();
+/// assert_eq!(report.created().len(), 2);
+/// assert_eq!(report.never_resolved().len(), 1);
+///
+/// println!("{}", report);
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct StartupReport {
+    created: Vec<CreatedInstance>,
+    never_resolved: Vec<Definition>,
+}
+
+impl StartupReport {
+    /// Returns the created instances, in the order in which their construction finished,
+    /// i.e. an instance comes after its dependencies.
+    pub fn created(&self) -> &[CreatedInstance] {
+        &self.created
+    }
+
+    /// Returns the definitions of the registered providers that have never been resolved.
+    pub fn never_resolved(&self) -> &[Definition] {
+        &self.never_resolved
+    }
+
+    /// Returns the total construction time of the created instances.
+    pub fn total_time(&self) -> Duration {
+        self.created
+            .iter()
+            .filter(|instance| instance.depth == 0)
+            .map(|instance| instance.total_time)
+            .sum()
+    }
+}
+
+impl Display for StartupReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "{} instances created in {:?}:",
+            self.created.len(),
+            self.total_time()
+        )?;
+
+        for instance in &self.created {
+            writeln!(
+                f,
+                "  #{} {} [{}] own: {:?}, total: {:?}",
+                instance.order,
+                key_label(&instance.definition.key),
+                scope_label(&instance.definition),
+                instance.own_time,
+                instance.total_time,
+            )?;
+        }
+
+        write!(f, "{} providers never resolved:", self.never_resolved.len())?;
+
+        for definition in &self.never_resolved {
+            write!(
+                f,
+                "\n  {} [{}]",
+                key_label(&definition.key),
+                scope_label(definition)
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Represents an instance created while a [`Context`](crate::Context) was created or flushed.
+#[derive(Clone, Debug)]
+pub struct CreatedInstance {
+    /// The definition of the provider that created the instance.
+    pub definition: Definition,
+    /// The order in which the construction finished, starting from `1`.
+    pub order: usize,
+    /// The number of instances being constructed that depend on this one,
+    /// i.e. `0` for an instance created eagerly.
+    pub depth: usize,
+    /// The construction time, excluding the time spent constructing the dependencies.
+    pub own_time: Duration,
+    /// The construction time, including the time spent constructing the dependencies.
+    pub total_time: Duration,
+}
+
+/// Records the construction times of the instances, used by [`Context::startup_report`](crate::Context::startup_report).
+#[derive(Default)]
+pub(crate) struct StartupRecorder {
+    pub(crate) recording: bool,
+    // the time spent constructing the dependencies of each instance being constructed
+    nested: Vec<Duration>,
+    created: Vec<CreatedInstance>,
+    resolved: HashSet<Key>,
+}
+
+impl StartupRecorder {
+    pub(crate) fn start(&mut self) -> Instant {
+        self.nested.push(Duration::ZERO);
+        Instant::now()
+    }
+
+    /// Returns the construction time of the instance, excluding and including the time spent constructing its dependencies.
+    pub(crate) fn finish(&mut self, start: Instant) -> (Duration, Duration) {
+        let total_time = start.elapsed();
+        let nested = self.nested.pop().unwrap_or_default();
+
+        if let Some(parent) = self.nested.last_mut() {
+            *parent += total_time;
+        }
+
+        (total_time.saturating_sub(nested), total_time)
+    }
+
+    pub(crate) fn record(
+        &mut self,
+        definition: Definition,
+        depth: usize,
+        own_time: Duration,
+        total_time: Duration,
+    ) {
+        self.created.push(CreatedInstance {
+            definition,
+            order: self.created.len() + 1,
+            depth,
+            own_time,
+            total_time,
+        });
+    }
+
+    /// Appends the instances created and the keys resolved by another recorder.
+    pub(crate) fn merge(&mut self, other: StartupRecorder) {
+        for mut instance in other.created {
+            instance.order = self.created.len() + 1;
+            self.created.push(instance);
+        }
+
+        self.resolved.extend(other.resolved);
+    }
+
+    pub(crate) fn resolve(&mut self, key: &Key) {
+        if !self.resolved.contains(key) {
+            self.resolved.insert(key.clone());
+        }
+    }
+
+    pub(crate) fn is_resolved(&self, key: &Key) -> bool {
+        self.resolved.contains(key)
+    }
+
+    pub(crate) fn report(&self, never_resolved: Vec<Definition>) -> StartupReport {
+        StartupReport {
+            created: self.created.clone(),
+            never_resolved,
+        }
+    }
+}
+
+fn key_label(key: &Key) -> String {
+    let mut label = key.ty.name.to_string();
+
+    match (key.name.is_empty(), key.qualifier) {
+        (true, None) => {}
+        (false, None) => label.push_str(&format!(" (name: {:?})", key.name)),
+        (true, Some(qualifier)) => label.push_str(&format!(" (qualifier: {})", qualifier.name)),
+        (false, Some(qualifier)) => label.push_str(&format!(
+            " (name: {:?}, qualifier: {})",
+            key.name, qualifier.name
+        )),
+    }
+
+    label
+}
@@ -1,14 +1,24 @@
-use std::any::Any;
+use super::{BoxFuture, Color, Dispose, DynAny, MaybeSendSync, Shared};
 
 /// Represents a [`Singleton`](crate::Scope::Singleton) or [`SingleOwner`](crate::Scope::SingleOwner) instance.
 pub struct Single<T> {
     instance: T,
     clone: Option<fn(&T) -> T>,
+    dispose: Option<Dispose<T>>,
 }
 
 impl<T> Single<T> {
     pub(crate) fn new(instance: T, clone: Option<fn(&T) -> T>) -> Self {
-        Self { instance, clone }
+        Self {
+            instance,
+            clone,
+            dispose: None,
+        }
+    }
+
+    pub(crate) fn with_dispose(mut self, dispose: Option<Dispose<T>>) -> Self {
+        self.dispose = dispose;
+        self
     }
 
     /// Returns the owned instance.
@@ -24,7 +34,9 @@ impl<T> Single<T> {
 
 /// Represents a [`Single`] that erased its type.
 pub struct DynSingle {
-    origin: Box<dyn Any>,
+    origin: Shared<DynAny>,
+    dispose_color: Option<Color>,
+    dispose: fn(Shared<DynAny>) -> Option<BoxFuture<'static, ()>>,
 }
 
 impl DynSingle {
@@ -32,12 +44,56 @@ impl DynSingle {
     pub fn as_single<T: 'static>(&self) -> Option<&Single<T>> {
         self.origin.downcast_ref::<Single<T>>()
     }
+
+    pub(crate) fn dispose_color(&self) -> Option<Color> {
+        self.dispose_color
+    }
+
+    /// Runs the dispose hook of the instance, unless the instance is still shared,
+    /// e.g. by a handle that is resolving an instance on demand.
+    pub(crate) fn dispose(self) -> Option<BoxFuture<'static, ()>> {
+        (self.dispose)(self.origin)
+    }
+
+    /// Returns a [`DynSingle`] that shares the instance of this one, so the registries holding it can be copied on write.
+    pub(crate) fn share(&self) -> DynSingle {
+        DynSingle {
+            origin: Shared::clone(&self.origin),
+            dispose_color: self.dispose_color,
+            dispose: self.dispose,
+        }
+    }
 }
 
-impl<T: 'static> From<Single<T>> for DynSingle {
+impl<T: 'static + MaybeSendSync> From<Single<T>> for DynSingle {
     fn from(value: Single<T>) -> Self {
         Self {
-            origin: Box::new(value),
+            dispose_color: value.dispose.map(|dispose| match dispose {
+                Dispose::Async(_) => Color::Async,
+                Dispose::Sync(_) => Color::Sync,
+            }),
+            dispose: dispose_single::<T>,
+            origin: Shared::new(value),
+        }
+    }
+}
+
+fn dispose_single<T: 'static + MaybeSendSync>(
+    origin: Shared<DynAny>,
+) -> Option<BoxFuture<'static, ()>> {
+    let origin = origin
+        .downcast::<Single<T>>()
+        .unwrap_or_else(|_| unreachable!("the origin of a `DynSingle` must be a `Single<T>`"));
+
+    let Single {
+        instance, dispose, ..
+    } = Shared::try_unwrap(origin).ok()?;
+
+    match dispose? {
+        Dispose::Async(dispose) => Some(dispose(instance)),
+        Dispose::Sync(dispose) => {
+            dispose(instance);
+            None
         }
     }
 }
@@ -0,0 +1,107 @@
+//! A thread-safe flavor of the API, enabled by the `send` feature.
+//!
+//! It has the same items as the root of the crate, but its [`Context`] stores providers and instances behind [`Arc`](std::sync::Arc),
+//! so the constructors, the instances and the futures returned by async constructors must be [`Send`] + [`Sync`],
+//! and the [`Context`] can be moved between threads and shared by the tasks of a multi-threaded runtime
+//! through a [`SyncContext`]. The [`Context`] of the root of the crate is not affected by the `send` feature.
+//!
+//! The attribute macros generate providers for this flavor with `#[di(rudi_path = rudi::sync)]`.
+//!
+//! # Example
+//!
+//! ```rust
+//! use rudi::{
+//!     sync::{components, modules, Context, DynProvider, Module, SyncContext},
+//!     Singleton,
+//! };
+//!
+//! #[derive(Clone)]
+//! #[Singleton(auto_register = false)]
+//! #[di(rudi_path = rudi::sync)]
+//! struct A;
+//!
+//! struct MyModule;
+//!
+//! impl Module for MyModule {
+//!     fn providers() -> Vec<DynProvider> {
+//!         components![A]
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let cx = SyncContext::new(Context::create(modules![MyModule]));
+//!
+//!     tokio::spawn({
+//!         let cx = cx.clone();
+//!         async move { cx.resolve::<A>().await }
+//!     })
+//!     .await
+//!     .unwrap();
+//!
+//!     assert!(cx.read().await.contains_single::<A>());
+//! }
+//! ```
+
+// the modules of the root of the crate are compiled again for this flavor
+#![allow(clippy::duplicate_mod)]
+
+#[cfg(feature = "auto-register")]
+#[path = "auto_register.rs"]
+mod auto_register;
+#[path = "condition.rs"]
+mod condition;
+#[path = "context.rs"]
+mod context;
+#[path = "custom_scope.rs"]
+mod custom_scope;
+#[path = "decorator.rs"]
+mod decorator;
+#[path = "dependency.rs"]
+mod dependency;
+#[path = "error.rs"]
+mod error;
+#[path = "factory.rs"]
+mod factory;
+#[path = "future.rs"]
+mod future;
+#[path = "handle.rs"]
+mod handle;
+#[path = "lazy.rs"]
+mod lazy;
+mod maybe_send;
+#[path = "module.rs"]
+mod module;
+#[path = "observer.rs"]
+mod observer;
+#[path = "provider.rs"]
+mod provider;
+#[path = "registry.rs"]
+mod registry;
+#[path = "single.rs"]
+mod single;
+#[path = "sync_context.rs"]
+mod sync_context;
+
+pub use rudi_core::*;
+
+#[cfg(feature = "config")]
+pub use crate::config::*;
+pub use crate::{
+    __sync_components as components, __sync_decorators as decorators, __sync_modules as modules,
+    __sync_providers as providers, definition::*, graph::*, report::*, ty::*,
+};
+#[cfg(feature = "auto-register")]
+pub use crate::{
+    __sync_register_decorator as register_decorator, __sync_register_provider as register_provider,
+    enable,
+};
+
+#[cfg(feature = "auto-register")]
+pub use self::auto_register::*;
+pub use self::{
+    condition::*, context::*, custom_scope::*, decorator::*, dependency::*, error::*, factory::*,
+    future::*, lazy::*, maybe_send::*, module::*, observer::*, provider::*, single::*,
+    sync_context::*,
+};
+pub(crate) use self::{handle::*, registry::*};
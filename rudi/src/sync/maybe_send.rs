@@ -0,0 +1,62 @@
+use std::{
+    any::Any,
+    error::Error,
+    future::Future,
+    ops::DerefMut,
+    pin::Pin,
+    sync::{Mutex, PoisonError},
+};
+
+use super::{ConditionContext, Context};
+
+/// The shared pointer used by the [`Context`], e.g. the parent of [`Context::child`].
+pub use std::sync::Arc as Shared;
+
+pub(crate) use std::sync::OnceLock as OnceCell;
+
+pub(crate) type DynAny = dyn Any + Send + Sync;
+
+pub(crate) type Lock<T> = Mutex<T>;
+
+pub(crate) fn lock<T>(lock: &Lock<T>) -> impl DerefMut<Target = T> + '_ {
+    lock.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// An owned dynamically typed [`Future`] for use in cases where you can't
+/// statically type your result or need to add some indirection.
+pub type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// A type-erased error returned by a fallible constructor.
+pub type BoxError = Box<dyn Error + Send + Sync>;
+
+pub(crate) type SharedError = Shared<dyn Error + Send + Sync>;
+
+pub(crate) type Evaluate = Shared<dyn Fn(&ConditionContext) -> bool + Send + Sync>;
+
+pub(crate) type SyncConstructor<T> =
+    Shared<dyn Fn(&mut Context) -> Result<T, BoxError> + Send + Sync>;
+
+pub(crate) type AsyncConstructor<T> =
+    Shared<dyn for<'a> Fn(&'a mut Context) -> BoxFuture<'a, Result<T, BoxError>> + Send + Sync>;
+
+pub(crate) type SyncPostConstruct<T> = Shared<dyn Fn(&mut T, &mut Context) + Send + Sync>;
+
+pub(crate) type AsyncPostConstruct<T> =
+    Shared<dyn for<'a> Fn(T, &'a mut Context) -> BoxFuture<'a, T> + Send + Sync>;
+
+pub(crate) type Decorate<T> = Shared<dyn Fn(T, &mut Context) -> T + Send + Sync>;
+
+/// A marker trait that is equivalent to [`Send`].
+///
+/// It is used to bound the futures returned by async constructors.
+pub trait MaybeSend: Send {}
+
+impl<T: ?Sized + Send> MaybeSend for T {}
+
+/// A marker trait that is equivalent to [`Send`] + [`Sync`].
+///
+/// It is used to bound the constructors stored in providers,
+/// and the instances stored in the [`Context`].
+pub trait MaybeSendSync: Send + Sync {}
+
+impl<T: ?Sized + Send + Sync> MaybeSendSync for T {}
@@ -0,0 +1,324 @@
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    ops::{Deref, DerefMut},
+    sync::{Arc, Mutex as StdMutex},
+};
+
+use async_lock::{Mutex, MutexGuardArc, RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+use super::{context::unwrap_or_panic, Context, Key, ResolveError, Scope, Shared, Single};
+
+/// A thread-safe handle of a [`Context`], which can be cloned and shared between threads and tasks.
+///
+/// All the resolving methods are async, and can be called concurrently from multiple tasks:
+/// - If the instance of a [`Singleton`](crate::Scope::Singleton) has been created, it is cloned under a shared lock.
+/// - Otherwise, if all the [`dependencies`](crate::Provider::dependencies) of the instance that are cached in the context
+///   have been created, the instance is created under a shared lock, concurrently with other such instances,
+///   then it is cached under a short exclusive lock. The instances of the same key are created one at a time,
+///   so the constructor of a [`Singleton`](crate::Scope::Singleton) is called only once.
+/// - Otherwise, e.g. the provider does not declare its dependencies, the instance is created under an exclusive lock.
+///
+/// Use [`SyncContext::read`] or [`SyncContext::write`] to access the other methods of the [`Context`].
+///
+/// # Example
+///
+/// ```rust
+/// use rudi::{
+///     sync::{Context, SyncContext},
+///     Singleton,
+/// };
+///
+/// #[derive(Clone)]
+/// #[Singleton]
+/// #[di(rudi_path = rudi::sync)]
+/// struct A;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let cx = SyncContext::new(Context::auto_register());
+///
+///     let handles = (0..4)
+///         .map(|_| {
+///             let cx = cx.clone();
+///             tokio::spawn(async move { cx.resolve::<A>().await })
+///         })
+///         .collect::<Vec<_>>();
+///
+///     for handle in handles {
+///         handle.await.unwrap();
+///     }
+///
+///     assert!(cx.read().await.contains_single::<A>());
+/// }
+/// ```
+#[derive(Clone)]
+pub struct SyncContext {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    // only shared with the children that create instances concurrently,
+    // which are dropped before the shared lock of the context is released
+    cx: RwLock<Shared<Context>>,
+    // held shared by the concurrent creations until their instances are cached,
+    // and exclusively by the creations that may create any instance
+    creating: RwLock<()>,
+    keys: StdMutex<HashMap<Key, Arc<Mutex<()>>>>,
+}
+
+impl SyncContext {
+    /// Create a new [`SyncContext`] from a [`Context`].
+    pub fn new(cx: Context) -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                cx: RwLock::new(Shared::new(cx)),
+                creating: RwLock::new(()),
+                keys: Default::default(),
+            }),
+        }
+    }
+
+    /// Locks the [`Context`] with shared read access.
+    pub async fn read(&self) -> SyncContextReadGuard<'_> {
+        SyncContextReadGuard(self.inner.cx.read().await)
+    }
+
+    /// Locks the [`Context`] with exclusive write access.
+    pub async fn write(&self) -> SyncContextWriteGuard<'_> {
+        SyncContextWriteGuard(self.inner.cx.write().await)
+    }
+
+    /// Returns a [`Singleton`](crate::Scope::Singleton) or [`Transient`](crate::Scope::Transient) instance based on the given type and default name `""`.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if no provider is registered for the given type and default name `""`.
+    /// - Panics if there is a provider that panics on construction.
+    /// - Panics if there is a provider whose constructor returns an error.
+    /// - Panics if the provider is not a [`Singleton`](crate::Scope::Singleton) or [`Transient`](crate::Scope::Transient).
+    ///
+    /// See [`Context::resolve_async`] for more details.
+    pub async fn resolve<T: 'static>(&self) -> T {
+        unwrap_or_panic(self.try_resolve().await)
+    }
+
+    /// Returns a [`Singleton`](crate::Scope::Singleton) or [`Transient`](crate::Scope::Transient) instance based on the given type and name.
+    ///
+    /// # Panics
+    ///
+    /// - Panics if no provider is registered for the given type and name.
+    /// - Panics if there is a provider that panics on construction.
+    /// - Panics if there is a provider whose constructor returns an error.
+    /// - Panics if the provider is not a [`Singleton`](crate::Scope::Singleton) or [`Transient`](crate::Scope::Transient).
+    ///
+    /// See [`Context::resolve_with_name_async`] for more details.
+    pub async fn resolve_with_name<T: 'static>(&self, name: impl Into<Cow<'static, str>>) -> T {
+        unwrap_or_panic(self.try_resolve_with_name(name).await)
+    }
+
+    /// Non-panicking version of [`SyncContext::resolve`].
+    pub async fn try_resolve<T: 'static>(&self) -> Result<T, ResolveError> {
+        self.try_resolve_with_name("").await
+    }
+
+    /// Non-panicking version of [`SyncContext::resolve_with_name`].
+    pub async fn try_resolve_with_name<T: 'static>(
+        &self,
+        name: impl Into<Cow<'static, str>>,
+    ) -> Result<T, ResolveError> {
+        self.try_resolve_by_key(Key::new::<T>(name.into()), false)
+            .await
+            .map(|instance| {
+                instance.expect("unreachable: a missing instance is returned as an error")
+            })
+    }
+
+    /// Returns an optional [`Singleton`](crate::Scope::Singleton) or [`Transient`](crate::Scope::Transient) instance based on the given type and default name `""`.
+    ///
+    /// See [`Context::resolve_option_async`] for more details.
+    pub async fn resolve_option<T: 'static>(&self) -> Option<T> {
+        unwrap_or_panic(self.try_resolve_option().await)
+    }
+
+    /// Returns an optional [`Singleton`](crate::Scope::Singleton) or [`Transient`](crate::Scope::Transient) instance based on the given type and name.
+    ///
+    /// See [`Context::resolve_option_with_name_async`] for more details.
+    pub async fn resolve_option_with_name<T: 'static>(
+        &self,
+        name: impl Into<Cow<'static, str>>,
+    ) -> Option<T> {
+        unwrap_or_panic(self.try_resolve_option_with_name(name).await)
+    }
+
+    /// Non-panicking version of [`SyncContext::resolve_option`].
+    pub async fn try_resolve_option<T: 'static>(&self) -> Result<Option<T>, ResolveError> {
+        self.try_resolve_option_with_name("").await
+    }
+
+    /// Non-panicking version of [`SyncContext::resolve_option_with_name`].
+    pub async fn try_resolve_option_with_name<T: 'static>(
+        &self,
+        name: impl Into<Cow<'static, str>>,
+    ) -> Result<Option<T>, ResolveError> {
+        self.try_resolve_by_key(Key::new::<T>(name.into()), true)
+            .await
+    }
+
+    /// Returns a collection of [`Singleton`](crate::Scope::Singleton) and [`Transient`](crate::Scope::Transient) instances of the given type.
+    ///
+    /// See [`Context::resolve_by_type_async`] for more details.
+    pub async fn resolve_by_type<T: 'static>(&self) -> Vec<T> {
+        unwrap_or_panic(self.try_resolve_by_type().await)
+    }
+
+    /// Non-panicking version of [`SyncContext::resolve_by_type`].
+    pub async fn try_resolve_by_type<T: 'static>(&self) -> Result<Vec<T>, ResolveError> {
+        let keys = self.read().await.keys::<T>();
+
+        let mut instances = Vec::with_capacity(keys.len());
+
+        for key in keys {
+            if let Some(instance) = self.try_resolve_by_key(key, true).await? {
+                instances.push(instance);
+            }
+        }
+
+        Ok(instances)
+    }
+
+    async fn try_resolve_by_key<T: 'static>(
+        &self,
+        key: Key,
+        option: bool,
+    ) -> Result<Option<T>, ResolveError> {
+        let (key, scope) = {
+            let cx = self.read().await;
+            let key = cx.key(key);
+
+            if let Some(instance) = cx.find_single(&key).and_then(Single::get_owned) {
+                return Ok(Some(instance));
+            }
+
+            let scope = cx
+                .find_provider::<T>(&key)
+                .map(|provider| provider.definition().scope);
+
+            (key, scope)
+        };
+
+        // the instances of the other scopes are not cached in the context, so they can be created at the same time
+        if !matches!(scope, Some(Scope::Singleton | Scope::SingleOwner)) {
+            return self.create(key, option).await;
+        }
+
+        let guard = self.lock_key(&key).await;
+        let result = self.create(key.clone(), option).await;
+        drop(guard);
+
+        self.unlock_key(&key);
+
+        result
+    }
+
+    async fn create<T: 'static>(&self, key: Key, option: bool) -> Result<Option<T>, ResolveError> {
+        let creating = self.inner.creating.read().await;
+        let cx = self.inner.cx.read().await;
+
+        if let Some(instance) = cx.find_single(&key).and_then(Single::get_owned) {
+            return Ok(Some(instance));
+        }
+
+        if !cx.is_independent(&key) {
+            drop(cx);
+            drop(creating);
+
+            let _creating = self.inner.creating.write().await;
+            return resolve(&mut *self.write().await, key, option).await;
+        }
+
+        let mut child = cx.inner_child(Vec::new());
+        let result = resolve(&mut child, key, option).await;
+
+        child.detach();
+        drop(cx);
+
+        let absorbed = self.write().await.absorb(child);
+        let instance = result?;
+
+        absorbed.map(|_| instance)
+    }
+
+    async fn lock_key(&self, key: &Key) -> MutexGuardArc<()> {
+        let mutex = Arc::clone(
+            self.inner
+                .keys
+                .lock()
+                .unwrap()
+                .entry(key.clone())
+                .or_default(),
+        );
+
+        mutex.lock_arc().await
+    }
+
+    fn unlock_key(&self, key: &Key) {
+        let mut keys = self.inner.keys.lock().unwrap();
+
+        // no other task is waiting for the mutex
+        if keys
+            .get(key)
+            .is_some_and(|mutex| Arc::strong_count(mutex) == 1)
+        {
+            keys.remove(key);
+        }
+    }
+}
+
+impl From<Context> for SyncContext {
+    fn from(cx: Context) -> Self {
+        Self::new(cx)
+    }
+}
+
+/// A guard of the [`Context`] of a [`SyncContext`] with shared read access, returned by [`SyncContext::read`].
+pub struct SyncContextReadGuard<'a>(RwLockReadGuard<'a, Shared<Context>>);
+
+impl Deref for SyncContextReadGuard<'_> {
+    type Target = Context;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+/// A guard of the [`Context`] of a [`SyncContext`] with exclusive write access, returned by [`SyncContext::write`].
+pub struct SyncContextWriteGuard<'a>(RwLockWriteGuard<'a, Shared<Context>>);
+
+impl Deref for SyncContextWriteGuard<'_> {
+    type Target = Context;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for SyncContextWriteGuard<'_> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        Shared::get_mut(&mut self.0).expect(
+            "unreachable: the children sharing the context are dropped before its shared lock is released",
+        )
+    }
+}
+
+async fn resolve<T: 'static>(
+    cx: &mut Context,
+    key: Key,
+    option: bool,
+) -> Result<Option<T>, ResolveError> {
+    if option {
+        cx.try_resolve_option_by_key_async(key).await
+    } else {
+        cx.try_resolve_by_key_async(key).await.map(Some)
+    }
+}
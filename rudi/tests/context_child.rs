@@ -0,0 +1,136 @@
+use std::cell::Cell;
+
+use rudi::{
+    modules, providers, single_owner, singleton, singleton_async, transient, Context, DynProvider,
+    FutureExt, Module, Shared,
+};
+
+#[derive(Clone)]
+struct Greeting(String);
+
+struct ParentModule;
+
+impl Module for ParentModule {
+    fn providers() -> Vec<DynProvider> {
+        providers![
+            singleton(|_| "parent".to_string()).eager_create(true),
+            singleton(|cx| Greeting(cx.resolve())),
+            transient(|_| 42i32),
+        ]
+    }
+}
+
+struct ChildModule;
+
+impl Module for ChildModule {
+    fn providers() -> Vec<DynProvider> {
+        providers![singleton(|_| "child".to_string())]
+    }
+}
+
+#[test]
+fn child_resolves_providers_of_parent() {
+    let parent = Shared::new(Context::create(modules![ParentModule]));
+
+    let mut child = parent.child(modules![]);
+
+    assert!(child.parent().is_some());
+    assert!(child.provider_registry().is_empty());
+    assert!(child.contains_provider::<i32>());
+    assert!(child.contains_single::<String>());
+    assert_eq!(child.get_single::<String>(), "parent");
+    assert_eq!(child.resolve::<i32>(), 42);
+    assert_eq!(child.resolve::<Greeting>().0, "parent");
+}
+
+#[test]
+fn child_overrides_providers_of_parent() {
+    let parent = Shared::new(Context::create(modules![ParentModule]));
+
+    let mut child = parent.child(modules![ChildModule]);
+
+    assert!(!child.contains_single::<String>());
+    assert_eq!(child.resolve::<String>(), "child");
+    assert_eq!(child.resolve::<Greeting>().0, "child");
+    assert_eq!(child.resolve_by_type::<String>(), vec!["child".to_string()]);
+
+    assert_eq!(parent.get_single::<String>(), "parent");
+    assert!(!parent.contains_single::<Greeting>());
+}
+
+#[test]
+fn singleton_of_parent_provider_is_cached_in_child() {
+    thread_local! {
+        static COUNT: Cell<usize> = const { Cell::new(0) };
+    }
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![
+                singleton(|_| COUNT.with(|count| count.replace(count.get() + 1))),
+                single_owner(|_| 1u8)
+            ]
+        }
+    }
+
+    let parent = Shared::new(Context::create(modules![MyModule]));
+
+    let mut a = parent.child(modules![]);
+    let mut b = parent.child(modules![]);
+
+    assert_eq!(a.resolve::<usize>(), 0);
+    assert_eq!(a.resolve::<usize>(), 0);
+    assert_eq!(b.resolve::<usize>(), 1);
+
+    a.just_create_single::<u8>();
+    assert!(a.single_registry().len() == 2);
+    assert!(!b.contains_single::<u8>());
+    assert!(!parent.contains_single::<usize>());
+
+    drop(a);
+    assert!(!parent.contains_single::<u8>());
+}
+
+#[test]
+fn grandchild() {
+    let parent = Shared::new(Context::create(modules![ParentModule]));
+    let child = Shared::new(parent.child(modules![ChildModule]));
+    let mut grandchild = child.child(modules![]);
+
+    assert_eq!(grandchild.resolve::<String>(), "child");
+    assert_eq!(grandchild.resolve::<i32>(), 42);
+    assert_eq!(grandchild.get_providers_by_type::<String>().len(), 1);
+    assert!(grandchild.parent().unwrap().parent().is_some());
+}
+
+#[test]
+fn child_inherits_options() {
+    let parent = Shared::new(Context::options().eager_create(true).create(modules![]));
+
+    let child = parent.child(modules![ChildModule]);
+
+    assert!(child.eager_create());
+    assert!(child.contains_single::<String>());
+}
+
+#[tokio::test]
+async fn child_async() {
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![singleton_async(
+                |cx| async { cx.resolve_async::<i32>().await as i64 }.boxed()
+            )
+            .eager_create(true)]
+        }
+    }
+
+    let parent = Shared::new(Context::create(modules![ParentModule]));
+
+    let child = parent.child_async(modules![MyModule]).await;
+
+    assert_eq!(child.get_single::<i64>(), &42);
+}
@@ -0,0 +1,191 @@
+use std::cell::RefCell;
+
+use rudi::{
+    modules, providers, single_owner, singleton, singleton_async, Context, CustomScope,
+    DynProvider, FutureExt, Module, SingleOwner, Singleton,
+};
+
+thread_local! {
+    static DISPOSED: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+fn record(name: impl Into<String>) {
+    DISPOSED.with(|disposed| disposed.borrow_mut().push(name.into()));
+}
+
+fn take_disposed() -> Vec<String> {
+    DISPOSED.with(|disposed| disposed.take())
+}
+
+#[derive(Clone)]
+struct Pool;
+
+#[derive(Clone)]
+struct Repository(#[allow(dead_code)] Pool);
+
+struct Service(#[allow(dead_code)] Repository);
+
+struct MyModule;
+
+impl Module for MyModule {
+    fn providers() -> Vec<DynProvider> {
+        providers![
+            singleton(|_| Pool).on_dispose(|_| record("pool")),
+            singleton(|cx| Repository(cx.resolve())).on_dispose(|_| record("repository")),
+            single_owner(|cx| Service(cx.resolve())).on_dispose(|_| record("service")),
+            singleton(|_| 42i32),
+        ]
+    }
+}
+
+#[test]
+fn shutdown_in_reverse_creation_order() {
+    take_disposed();
+
+    let mut cx = Context::create(modules![MyModule]);
+
+    cx.just_create_single::<Service>();
+    cx.resolve::<i32>();
+
+    cx.shutdown();
+
+    assert_eq!(take_disposed(), ["service", "repository", "pool"]);
+    assert!(cx.single_registry().is_empty());
+    assert!(cx.contains_provider::<Pool>());
+
+    cx.resolve::<Pool>();
+    assert!(cx.contains_single::<Pool>());
+}
+
+#[test]
+fn unload_modules_disposes_removed_instances() {
+    struct OtherModule;
+
+    impl Module for OtherModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![singleton(|_| "other").on_dispose(record)]
+        }
+    }
+
+    take_disposed();
+
+    let mut cx = Context::create(modules![MyModule, OtherModule]);
+
+    cx.resolve::<&'static str>();
+    cx.resolve::<Repository>();
+
+    cx.unload_modules(modules![MyModule]);
+
+    assert_eq!(take_disposed(), ["repository", "pool"]);
+    assert!(cx.contains_single::<&'static str>());
+
+    cx.shutdown();
+
+    assert_eq!(take_disposed(), ["other"]);
+}
+
+#[tokio::test]
+async fn shutdown_async() {
+    struct AsyncModule;
+
+    impl Module for AsyncModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![
+                singleton_async(|_| async { Pool }.boxed()).on_dispose_async(|_| async {
+                    tokio::task::yield_now().await;
+                    record("pool");
+                }
+                .boxed()),
+                singleton(|_| 1u8).on_dispose(|_| record("u8")),
+            ]
+        }
+    }
+
+    take_disposed();
+
+    let mut cx = Context::create_async(modules![AsyncModule]).await;
+
+    cx.resolve_async::<Pool>().await;
+    cx.resolve::<u8>();
+
+    cx.shutdown_async().await;
+
+    assert_eq!(take_disposed(), ["u8", "pool"]);
+}
+
+#[test]
+#[should_panic(expected = "unable to call an async dispose hook in a sync context")]
+fn sync_shutdown_with_async_dispose_hook() {
+    struct AsyncModule;
+
+    impl Module for AsyncModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![singleton(|_| Pool).on_dispose_async(|_| async {}.boxed())]
+        }
+    }
+
+    let mut cx = Context::create(modules![AsyncModule]);
+    cx.resolve::<Pool>();
+    cx.shutdown();
+}
+
+#[test]
+fn shutdown_custom_scoped_instances() {
+    struct MyScope;
+
+    impl CustomScope for MyScope {}
+
+    struct ScopedModule;
+
+    impl Module for ScopedModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![singleton(|_| 1u16)
+                .scope::<MyScope>()
+                .on_dispose(|_| record("scoped"))]
+        }
+    }
+
+    take_disposed();
+
+    let mut cx = Context::options()
+        .custom_scope(MyScope)
+        .create(modules![ScopedModule]);
+
+    cx.resolve::<u16>();
+    cx.shutdown();
+
+    assert_eq!(take_disposed(), ["scoped"]);
+}
+
+#[test]
+fn dispose_with_macro() {
+    #[derive(Clone)]
+    #[Singleton(dispose = Self::close, auto_register = false)]
+    struct A;
+
+    impl A {
+        fn close(self) {
+            record("a");
+        }
+    }
+
+    #[SingleOwner(dispose = |_| record("b"), auto_register = false)]
+    struct B(#[allow(dead_code)] A);
+
+    struct MacroModule;
+
+    impl Module for MacroModule {
+        fn providers() -> Vec<DynProvider> {
+            rudi::components![A, B]
+        }
+    }
+
+    take_disposed();
+
+    let mut cx = Context::create(modules![MacroModule]);
+
+    cx.just_create_single::<B>();
+    cx.shutdown();
+
+    assert_eq!(take_disposed(), ["b", "a"]);
+}
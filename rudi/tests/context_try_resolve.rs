@@ -0,0 +1,175 @@
+use std::{any::TypeId, cell::RefCell};
+
+use rudi::{
+    modules, providers, single_owner, singleton, transient, transient_async, Context, DynProvider,
+    FutureExt, Module, ResolveError, ResolveErrorKind, Scope,
+};
+
+#[test]
+fn try_resolve_not_found_provider() {
+    let mut cx = Context::default();
+
+    let err = cx.try_resolve::<i32>().unwrap_err();
+
+    assert!(matches!(err.kind(), ResolveErrorKind::NotFoundProvider(_)));
+    assert_eq!(err.key().name, "");
+    assert!(err.definition().is_none());
+    assert!(err.dependency_chain().is_empty());
+    assert!(err.to_string().starts_with("no provider registered for"));
+}
+
+#[test]
+fn try_resolve_wrong_scope() {
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![single_owner(|_| 42).name("a")]
+        }
+    }
+
+    let mut cx = Context::create(modules![MyModule]);
+
+    let err = cx.try_resolve_with_name::<i32>("a").unwrap_err();
+
+    match err.kind() {
+        ResolveErrorKind::NotSingletonOrTransient(definition) => {
+            assert_eq!(definition.scope, Scope::SingleOwner);
+        }
+        _ => panic!("unexpected error: {}", err),
+    }
+
+    assert_eq!(err.definition().unwrap().key.name, "a");
+}
+
+#[test]
+fn try_resolve_async_in_sync() {
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![transient_async(|_| async { 42 }.boxed())]
+        }
+    }
+
+    let mut cx = Context::create(modules![MyModule]);
+
+    let err = cx.try_resolve::<i32>().unwrap_err();
+
+    assert!(matches!(
+        err.kind(),
+        ResolveErrorKind::AsyncConstructorInSync(_)
+    ));
+}
+
+#[test]
+fn try_resolve_dependency_chain() {
+    thread_local! {
+        static ERROR: RefCell<Option<ResolveError>> = const { RefCell::new(None) };
+    }
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![transient(|cx| {
+                if let Err(e) = cx.try_resolve::<u8>() {
+                    ERROR.with(|error| *error.borrow_mut() = Some(e));
+                }
+
+                1i32
+            })]
+        }
+    }
+
+    let mut cx = Context::create(modules![MyModule]);
+
+    assert_eq!(cx.try_resolve::<i32>().unwrap(), 1);
+
+    let error = ERROR.with(|error| error.borrow_mut().take()).unwrap();
+
+    assert!(matches!(
+        error.kind(),
+        ResolveErrorKind::NotFoundProvider(_)
+    ));
+    assert_eq!(error.dependency_chain().len(), 1);
+    assert_eq!(error.dependency_chain()[0].ty.id, TypeId::of::<i32>());
+}
+
+#[test]
+fn try_resolve_circular_dependency() {
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![singleton(|cx| match cx.try_resolve::<String>() {
+                Ok(s) => s,
+                Err(e) => match e.kind() {
+                    ResolveErrorKind::CircularDependency(_) => {
+                        assert_eq!(e.dependency_chain().len(), 2);
+                        e.to_string()
+                    }
+                    _ => panic!("unexpected error: {}", e),
+                },
+            })]
+        }
+    }
+
+    let mut cx = Context::create(modules![MyModule]);
+
+    let message = cx.try_resolve::<String>().unwrap();
+    assert!(message.starts_with("circular dependency detected"));
+    assert!(message.contains(" --> "));
+
+    assert!(cx.dependency_chain().is_empty());
+}
+
+#[test]
+fn try_get_single() {
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![singleton(|_| 42).name("a")]
+        }
+    }
+
+    let mut cx = Context::create(modules![MyModule]);
+
+    let err = cx.try_get_single_with_name::<i32>("a").unwrap_err();
+    assert!(matches!(err.kind(), ResolveErrorKind::NotFoundSingle(_)));
+
+    cx.just_create_single_with_name::<i32>("a");
+    assert_eq!(cx.try_get_single_with_name::<i32>("a").unwrap(), &42);
+    assert!(cx.try_get_single::<i32>().is_err());
+}
+
+#[tokio::test]
+async fn try_resolve_async() {
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![
+                transient_async(|_| async { 42 }.boxed()),
+                single_owner(|_| 1i64)
+            ]
+        }
+    }
+
+    let mut cx = Context::create(modules![MyModule]);
+
+    assert_eq!(cx.try_resolve_async::<i32>().await.unwrap(), 42);
+
+    let err = cx.try_resolve_async::<i64>().await.unwrap_err();
+    assert!(matches!(
+        err.kind(),
+        ResolveErrorKind::NotSingletonOrTransient(_)
+    ));
+
+    let err = cx
+        .try_resolve_with_name_async::<i32>("a")
+        .await
+        .unwrap_err();
+    assert!(matches!(err.kind(), ResolveErrorKind::NotFoundProvider(_)));
+}
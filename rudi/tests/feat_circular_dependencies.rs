@@ -0,0 +1,342 @@
+use rudi::{
+    components, modules, transient, Context, Dependency, DependencyKind, DynProvider, Lazy, Module,
+    ResolveErrorKind, Shared, Transient,
+};
+
+#[derive(Clone)]
+struct A;
+
+#[derive(Clone)]
+struct B;
+
+#[derive(Clone)]
+struct C;
+
+#[derive(Clone)]
+struct D;
+
+#[derive(Clone)]
+struct E;
+
+#[Transient(auto_register = false)]
+fn NewA(_b: B) -> A {
+    A
+}
+
+#[Transient(auto_register = false)]
+fn NewB(_a: A) -> B {
+    B
+}
+
+#[Transient(auto_register = false)]
+fn NewC(_d: D) -> C {
+    C
+}
+
+#[Transient(auto_register = false)]
+fn NewD(_e: E) -> D {
+    D
+}
+
+#[Transient(auto_register = false)]
+fn NewE(_c: C) -> E {
+    E
+}
+
+#[test]
+fn report_every_cycle() {
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            components![NewA, NewB, NewC, NewD, NewE]
+        }
+    }
+
+    let err = Context::options()
+        .eager_create(false)
+        .try_create(modules![MyModule])
+        .err()
+        .unwrap();
+
+    let ResolveErrorKind::CircularDependencies(cycles) = err.kind() else {
+        panic!("unexpected error: {}", err);
+    };
+
+    let names = cycles
+        .iter()
+        .map(|cycle| cycle.iter().map(|key| key.ty.name).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        names,
+        [
+            vec![
+                "feat_circular_dependencies::A",
+                "feat_circular_dependencies::B",
+                "feat_circular_dependencies::A",
+            ],
+            vec![
+                "feat_circular_dependencies::C",
+                "feat_circular_dependencies::D",
+                "feat_circular_dependencies::E",
+                "feat_circular_dependencies::C",
+            ],
+        ]
+    );
+
+    let message = err.to_string();
+    assert!(message.contains(" --> "));
+    assert_eq!(message.matches('[').count(), 2);
+}
+
+#[test]
+fn load_modules_defers_check() {
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            components![NewA, NewB]
+        }
+    }
+
+    let mut cx = Context::default();
+    cx.load_modules(modules![MyModule]);
+
+    let err = cx.try_flush().unwrap_err();
+    assert!(matches!(
+        err.kind(),
+        ResolveErrorKind::CircularDependencies(_)
+    ));
+}
+
+#[test]
+fn try_load_modules() {
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            components![NewA, NewB]
+        }
+    }
+
+    let mut cx = Context::default();
+
+    let err = cx.try_load_modules(modules![MyModule]).unwrap_err();
+    assert!(matches!(
+        err.kind(),
+        ResolveErrorKind::CircularDependencies(_)
+    ));
+    assert!(cx.contains_provider::<A>());
+}
+
+#[test]
+fn long_chain() {
+    const LEN: usize = 100_000;
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            (0..LEN)
+                .map(|i| {
+                    transient(|_| 0usize)
+                        .name(i.to_string())
+                        .dependencies(vec![Dependency::new::<usize>(
+                            ((i + 1) % LEN).to_string(),
+                            DependencyKind::Required,
+                        )])
+                        .into()
+                })
+                .collect()
+        }
+    }
+
+    let mut cx = Context::default();
+
+    let err = cx.try_load_modules(modules![MyModule]).unwrap_err();
+    let ResolveErrorKind::CircularDependencies(cycles) = err.kind() else {
+        panic!("unexpected error: {}", err);
+    };
+    assert_eq!(cycles.len(), 1);
+    assert_eq!(cycles[0].len(), LEN + 1);
+}
+
+#[test]
+fn cycle_across_modules() {
+    struct ModuleA;
+
+    impl Module for ModuleA {
+        fn providers() -> Vec<DynProvider> {
+            components![NewA]
+        }
+    }
+
+    struct ModuleB;
+
+    impl Module for ModuleB {
+        fn providers() -> Vec<DynProvider> {
+            components![NewB]
+        }
+    }
+
+    let parent = Shared::new(Context::create(modules![ModuleA]));
+
+    let err = parent.try_child(modules![ModuleB]).err().unwrap();
+    assert!(matches!(
+        err.kind(),
+        ResolveErrorKind::CircularDependencies(_)
+    ));
+}
+
+#[test]
+fn self_loop() {
+    #[Transient(auto_register = false)]
+    fn Number(n: i32) -> i32 {
+        n
+    }
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            components![Number]
+        }
+    }
+
+    let err = Context::options()
+        .try_create(modules![MyModule])
+        .err()
+        .unwrap();
+
+    let ResolveErrorKind::CircularDependencies(cycles) = err.kind() else {
+        panic!("unexpected error: {}", err);
+    };
+
+    assert_eq!(cycles.len(), 1);
+    assert_eq!(cycles[0].len(), 2);
+}
+
+#[test]
+fn lazy_breaks_cycle() {
+    #[derive(Clone)]
+    #[Transient(auto_register = false)]
+    struct X(#[di(lazy)] Lazy<i32>);
+
+    #[Transient(auto_register = false)]
+    fn Y(_x: X) -> i32 {
+        42
+    }
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            components![X, Y]
+        }
+    }
+
+    let mut cx = Context::create(modules![MyModule]);
+    assert!(cx.resolve::<X>().0.get().is_none());
+}
+
+#[test]
+fn conditional_provider() {
+    #[Transient(condition = |_| true, auto_register = false)]
+    fn X(_b: B) -> A {
+        A
+    }
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            components![X, NewB]
+        }
+    }
+
+    let err = Context::options()
+        .try_create(modules![MyModule])
+        .err()
+        .unwrap();
+    assert!(matches!(
+        err.kind(),
+        ResolveErrorKind::CircularDependencies(_)
+    ));
+}
+
+#[test]
+fn report_one_cycle_for_each_group() {
+    #[Transient(auto_register = false)]
+    fn X(_b: B, _c: C) -> A {
+        A
+    }
+
+    #[Transient(auto_register = false)]
+    fn Y(_c: C) -> B {
+        B
+    }
+
+    #[Transient(auto_register = false)]
+    fn Z(_a: A, #[di(vec)] _a_vec: Vec<A>) -> C {
+        C
+    }
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            components![X, Y, Z]
+        }
+    }
+
+    let err = Context::options()
+        .eager_create(false)
+        .try_create(modules![MyModule])
+        .err()
+        .unwrap();
+
+    let ResolveErrorKind::CircularDependencies(cycles) = err.kind() else {
+        panic!("unexpected error: {}", err);
+    };
+
+    let names = cycles
+        .iter()
+        .map(|cycle| cycle.iter().map(|key| key.ty.name).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+
+    // `A --> B --> C --> A` is not reported again, since `A --> C --> A` already covers the group
+    assert_eq!(
+        names,
+        [vec![
+            "feat_circular_dependencies::A",
+            "feat_circular_dependencies::C",
+            "feat_circular_dependencies::A",
+        ]]
+    );
+}
+
+#[test]
+fn unmet_conditional_provider() {
+    #[Transient(condition = |_| true, auto_register = false)]
+    fn MetA() -> A {
+        A
+    }
+
+    #[Transient(condition = |_| false, auto_register = false)]
+    fn UnmetA(_b: B) -> A {
+        A
+    }
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            components![MetA, UnmetA, NewB]
+        }
+    }
+
+    let mut cx = Context::options().try_create(modules![MyModule]).unwrap();
+
+    cx.resolve::<B>();
+}
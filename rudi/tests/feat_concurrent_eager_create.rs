@@ -0,0 +1,346 @@
+use std::{
+    future::{self, Future},
+    num::ParseIntError,
+    panic,
+    pin::Pin,
+    sync::atomic::{AtomicUsize, Ordering},
+    task::{Context as TaskContext, Poll, Waker},
+};
+
+use rudi::{
+    modules, providers, singleton, singleton_async, try_singleton_async, Context, Dependency,
+    DependencyKind, DynProvider, FutureExt, Module, ResolveErrorKind,
+};
+
+/// Resolves to true once `count` constructors have started, or to false after polling too many times.
+struct Rendezvous {
+    started: &'static AtomicUsize,
+    count: usize,
+    polls: usize,
+}
+
+impl Rendezvous {
+    fn new(started: &'static AtomicUsize, count: usize) -> Self {
+        started.fetch_add(1, Ordering::SeqCst);
+
+        Self {
+            started,
+            count,
+            polls: 0,
+        }
+    }
+}
+
+impl Future for Rendezvous {
+    type Output = bool;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut TaskContext<'_>) -> Poll<Self::Output> {
+        if self.started.load(Ordering::SeqCst) >= self.count {
+            return Poll::Ready(true);
+        }
+
+        if self.polls >= 100 {
+            return Poll::Ready(false);
+        }
+
+        self.polls += 1;
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
+
+#[tokio::test]
+async fn independent_constructors_run_concurrently() {
+    static STARTED: AtomicUsize = AtomicUsize::new(0);
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn eager_create() -> bool {
+            true
+        }
+
+        fn providers() -> Vec<DynProvider> {
+            providers![
+                singleton_async(|_| Rendezvous::new(&STARTED, 2).boxed())
+                    .name("a")
+                    .dependencies(vec![]),
+                singleton_async(|_| Rendezvous::new(&STARTED, 2).boxed())
+                    .name("b")
+                    .dependencies(vec![]),
+            ]
+        }
+    }
+
+    let cx = Context::options()
+        .concurrent_eager_create(true)
+        .create_async(modules![MyModule])
+        .await;
+
+    assert!(cx.concurrent_eager_create());
+    assert!(*cx.get_single_with_name::<bool>("a"));
+    assert!(*cx.get_single_with_name::<bool>("b"));
+}
+
+#[tokio::test]
+async fn undeclared_dependencies_are_created_one_by_one() {
+    static STARTED: AtomicUsize = AtomicUsize::new(0);
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn eager_create() -> bool {
+            true
+        }
+
+        fn providers() -> Vec<DynProvider> {
+            providers![
+                singleton_async(|_| Rendezvous::new(&STARTED, 2).boxed()).name("a"),
+                singleton_async(|_| Rendezvous::new(&STARTED, 2).boxed()).name("b"),
+            ]
+        }
+    }
+
+    let cx = Context::options()
+        .concurrent_eager_create(true)
+        .create_async(modules![MyModule])
+        .await;
+
+    assert!(!*cx.get_single_with_name::<bool>("a"));
+    assert!(*cx.get_single_with_name::<bool>("b"));
+}
+
+#[tokio::test]
+async fn instance_created_twice_is_an_error() {
+    struct LazyModule;
+
+    impl Module for LazyModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![singleton(|_| 1u8)]
+        }
+    }
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn eager_create() -> bool {
+            true
+        }
+
+        fn providers() -> Vec<DynProvider> {
+            providers![
+                // both resolve `u8`, but declare no dependencies
+                singleton_async(|cx| async { u16::from(cx.resolve::<u8>()) }.boxed())
+                    .dependencies(vec![]),
+                singleton_async(|cx| async { u32::from(cx.resolve::<u8>()) }.boxed())
+                    .dependencies(vec![]),
+            ]
+        }
+    }
+
+    let Err(err) = Context::options()
+        .concurrent_eager_create(true)
+        .try_create_async(modules![LazyModule, MyModule])
+        .await
+    else {
+        panic!("the instance of `u8` is created twice");
+    };
+
+    assert!(
+        matches!(err.kind(), ResolveErrorKind::CreatedConcurrently(key) if key.ty.name == "u8")
+    );
+}
+
+#[tokio::test]
+async fn same_instances_as_sequential_creation() {
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn eager_create() -> bool {
+            true
+        }
+
+        fn providers() -> Vec<DynProvider> {
+            providers![
+                singleton_async(|_| async { 1u8 }.boxed()),
+                singleton(|_| 2u16),
+                singleton_async(|cx| async { u32::from(cx.resolve::<u16>()) }.boxed())
+                    .dependencies(vec![Dependency::new::<u16>("", DependencyKind::Required)]),
+                singleton_async(|_| async { 4u64 }.boxed()),
+            ]
+        }
+    }
+
+    let sequential = Context::create_async(modules![MyModule]).await;
+    let concurrent = Context::options()
+        .concurrent_eager_create(true)
+        .create_async(modules![MyModule])
+        .await;
+
+    for cx in [&sequential, &concurrent] {
+        assert_eq!(*cx.get_single::<u8>(), 1);
+        assert_eq!(*cx.get_single::<u16>(), 2);
+        assert_eq!(*cx.get_single::<u32>(), 2);
+        assert_eq!(*cx.get_single::<u64>(), 4);
+    }
+
+    let created = |cx: &Context| {
+        cx.startup_report()
+            .created()
+            .iter()
+            .map(|instance| instance.definition.key.ty.name)
+            .collect::<Vec<_>>()
+    };
+
+    assert_eq!(created(&sequential), ["u8", "u16", "u32", "u64"]);
+    assert_eq!(created(&concurrent), ["u8", "u16", "u32", "u64"]);
+}
+
+#[tokio::test]
+async fn dependent_waits_for_its_dependency() {
+    static STARTED: AtomicUsize = AtomicUsize::new(0);
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn eager_create() -> bool {
+            true
+        }
+
+        fn providers() -> Vec<DynProvider> {
+            providers![
+                singleton_async(|_| async {
+                    STARTED.fetch_add(1, Ordering::SeqCst);
+                    1u8
+                }
+                .boxed()),
+                singleton_async(|cx| async {
+                    // `u8` has been created before this constructor starts
+                    let started = STARTED.load(Ordering::SeqCst);
+                    u16::from(cx.resolve_async::<u8>().await) + started as u16
+                }
+                .boxed())
+                .dependencies(vec![Dependency::new::<u8>("", DependencyKind::Required)]),
+            ]
+        }
+    }
+
+    let cx = Context::options()
+        .concurrent_eager_create(true)
+        .create_async(modules![MyModule])
+        .await;
+
+    assert_eq!(*cx.get_single::<u16>(), 2);
+    assert_eq!(STARTED.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn error_of_failed_provider_is_returned() {
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn eager_create() -> bool {
+            true
+        }
+
+        fn providers() -> Vec<DynProvider> {
+            providers![
+                singleton_async(|_| async { 1u8 }.boxed()).dependencies(vec![]),
+                try_singleton_async(|_| async { "forty-two".parse::<i32>() }.boxed())
+                    .dependencies(vec![]),
+                singleton_async(|_| async { 3u64 }.boxed()).dependencies(vec![]),
+            ]
+        }
+    }
+
+    let err = Context::options()
+        .concurrent_eager_create(true)
+        .try_create_async(modules![MyModule])
+        .await
+        .err()
+        .unwrap();
+
+    match err.kind() {
+        ResolveErrorKind::ConstructorFailed(definition, source) => {
+            assert_eq!(definition.key.ty.name, "i32");
+            assert!(source.downcast_ref::<ParseIntError>().is_some());
+        }
+        _ => panic!("unexpected error: {}", err),
+    }
+}
+
+#[test]
+fn panic_names_the_provider() {
+    fn connect() -> u16 {
+        panic!("no connection")
+    }
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn eager_create() -> bool {
+            true
+        }
+
+        fn providers() -> Vec<DynProvider> {
+            providers![
+                singleton_async(|_| async { 1u8 }.boxed()).dependencies(vec![]),
+                singleton_async(|_| async { connect() }.boxed()).dependencies(vec![]),
+            ]
+        }
+    }
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .build()
+        .unwrap();
+
+    let payload = panic::catch_unwind(|| {
+        runtime.block_on(
+            Context::options()
+                .concurrent_eager_create(true)
+                .create_async(modules![MyModule]),
+        )
+    })
+    .err()
+    .unwrap();
+
+    let message = payload.downcast_ref::<String>().unwrap();
+    assert!(message.starts_with("panicked while eagerly creating"));
+    assert!(message.contains("\"u16\""));
+    assert!(message.ends_with("no connection"));
+}
+
+#[test]
+fn context_is_kept_when_creation_is_cancelled() {
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn eager_create() -> bool {
+            true
+        }
+
+        fn providers() -> Vec<DynProvider> {
+            providers![
+                singleton_async(|_| async { 1u8 }.boxed()).dependencies(vec![]),
+                singleton_async(|_| future::pending::<u16>().boxed()).dependencies(vec![]),
+            ]
+        }
+    }
+
+    let mut cx = Context::options()
+        .concurrent_eager_create(true)
+        .singleton(true)
+        .create(modules![]);
+
+    cx.load_modules(modules![MyModule]);
+
+    let mut flush = Box::pin(cx.flush_async());
+    let poll = flush
+        .as_mut()
+        .poll(&mut TaskContext::from_waker(Waker::noop()));
+    assert!(poll.is_pending());
+    drop(flush);
+
+    assert!(cx.get_single::<bool>());
+    assert_eq!(cx.provider_registry().len(), 3);
+}
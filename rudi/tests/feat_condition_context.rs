@@ -0,0 +1,250 @@
+use std::{cell::Cell, env};
+
+use rudi::{
+    components, modules, providers, singleton, transient, ConditionContext, Context, DynProvider,
+    Module, Singleton, Transient,
+};
+
+thread_local! {
+    static VERBOSE: Cell<bool> = const { Cell::new(false) };
+}
+
+#[test]
+fn closure_captures_runtime_state() {
+    struct LogModule;
+
+    impl Module for LogModule {
+        fn providers() -> Vec<DynProvider> {
+            // e.g. parsed from the command line arguments
+            let verbose = VERBOSE.get();
+
+            providers![transient(|_| "debug").condition_with(move |_| verbose)]
+        }
+    }
+
+    let cx = Context::create(modules![LogModule]);
+    assert!(!cx.contains_provider::<&str>());
+
+    VERBOSE.set(true);
+
+    let mut cx = Context::create(modules![LogModule]);
+    assert_eq!(cx.resolve::<&str>(), "debug");
+}
+
+#[test]
+fn env_var() {
+    struct EnvModule;
+
+    impl Module for EnvModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![
+                transient(|_| 1).condition_with(|cx| {
+                    cx.env_var("RUDI_FEAT_CONDITION_CONTEXT").as_deref() == Some("on")
+                }),
+                transient(|_| "missing").condition_with(|cx| cx
+                    .env_var("RUDI_FEAT_CONDITION_CONTEXT_MISSING")
+                    .is_none()),
+            ]
+        }
+    }
+
+    env::set_var("RUDI_FEAT_CONDITION_CONTEXT", "on");
+
+    let cx = Context::create(modules![EnvModule]);
+
+    assert!(cx.contains_provider::<i32>());
+    assert!(cx.contains_provider::<&str>());
+}
+
+#[test]
+fn created_singletons() {
+    #[derive(Clone)]
+    struct Settings {
+        cache: bool,
+    }
+
+    struct SettingsModule;
+
+    impl Module for SettingsModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![
+                singleton(|_| Settings { cache: true }).eager_create(true),
+                singleton(|_| Settings { cache: false })
+                    .name("lazy")
+                    .eager_create(false),
+                transient(|_| "cache").condition_with(|cx| {
+                    cx.get_single_option::<Settings>()
+                        .is_some_and(|settings| settings.cache)
+                }),
+                transient(|_| 42)
+                    .condition_with(|cx| cx.contains_single_with_name::<Settings>("lazy")),
+            ]
+        }
+    }
+
+    let cx = Context::create(modules![SettingsModule]);
+
+    assert!(cx.contains_provider::<&str>());
+    assert!(!cx.contains_provider::<i32>());
+}
+
+#[test]
+fn registry_queries() {
+    struct RegistryModule;
+
+    impl Module for RegistryModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![
+                transient(|_| 1).name("one"),
+                transient(|_| 2).name("two"),
+                transient(|_| "two")
+                    .condition_with(|cx| cx.get_providers_by_type::<i32>().len() == 2),
+                transient(|_| 3u8).condition_with(|cx| {
+                    cx.get_provider_with_name::<i32>("one")
+                        .is_some_and(|provider| provider.definition().key.name == "one")
+                }),
+                transient(|_| 4u16)
+                    .condition_with(|cx| cx.contains_provider_with_name::<i32>("three")),
+            ]
+        }
+    }
+
+    let cx = Context::create(modules![RegistryModule]);
+
+    assert!(cx.contains_provider::<&str>());
+    assert!(cx.contains_provider::<u8>());
+    assert!(!cx.contains_provider::<u16>());
+}
+
+#[test]
+fn profiles() {
+    struct ProfileModule;
+
+    impl Module for ProfileModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![transient(|_| 1).condition_with(|cx| cx.profiles().len() > 1)]
+        }
+    }
+
+    let cx = Context::options()
+        .profiles(["dev", "test"])
+        .create(modules![ProfileModule]);
+
+    assert!(cx.contains_provider::<i32>());
+}
+
+#[test]
+fn condition_attribute() {
+    fn is_dev(cx: &ConditionContext) -> bool {
+        cx.is_profile_active("dev")
+    }
+
+    #[Singleton(condition = is_dev, auto_register = false)]
+    fn DevUrl() -> String {
+        "sqlite::memory:".to_string()
+    }
+
+    #[Transient(condition = |cx| !cx.contains_provider::<String>(), auto_register = false)]
+    fn Fallback() -> &'static str {
+        "fallback"
+    }
+
+    struct AttributeModule;
+
+    impl Module for AttributeModule {
+        fn providers() -> Vec<DynProvider> {
+            components![DevUrl, Fallback]
+        }
+    }
+
+    let mut cx = Context::options()
+        .profiles(["dev"])
+        .create(modules![AttributeModule]);
+
+    assert_eq!(cx.resolve::<String>(), "sqlite::memory:");
+    assert!(!cx.contains_provider::<&str>());
+    assert!(cx
+        .get_provider::<String>()
+        .unwrap()
+        .condition_with()
+        .is_some());
+
+    let mut cx = Context::create(modules![AttributeModule]);
+
+    assert_eq!(cx.resolve::<&str>(), "fallback");
+}
+
+#[test]
+fn context_function_attribute() {
+    fn is_dev(cx: &Context) -> bool {
+        cx.is_profile_active("dev")
+    }
+
+    fn has_url(cx: &Context, name: &'static str) -> bool {
+        cx.contains_provider_with_name::<String>(name)
+    }
+
+    #[Singleton(condition = is_dev, auto_register = false)]
+    fn DevUrl() -> String {
+        "sqlite::memory:".to_string()
+    }
+
+    #[Transient(condition = |cx| has_url(cx, ""), auto_register = false)]
+    fn Pool() -> &'static str {
+        "pool"
+    }
+
+    struct AttributeModule;
+
+    impl Module for AttributeModule {
+        fn providers() -> Vec<DynProvider> {
+            components![DevUrl, Pool]
+        }
+    }
+
+    let mut cx = Context::options()
+        .profiles(["dev"])
+        .create(modules![AttributeModule]);
+
+    assert_eq!(cx.resolve::<&str>(), "pool");
+
+    let cx = Context::create(modules![AttributeModule]);
+
+    assert!(!cx.contains_provider::<String>());
+    assert!(!cx.contains_provider::<&str>());
+}
+
+#[test]
+fn context_function_builder() {
+    fn has_u32(cx: &Context) -> bool {
+        cx.contains_provider::<u32>()
+    }
+
+    struct ChainModule;
+
+    impl Module for ChainModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![
+                // read through the `Context`, so they are evaluated again when `u32` is met
+                transient(|_| 3u8).condition(Some(has_u32)),
+                transient(|_| 4i8).condition_with(|cx| has_u32(cx)),
+                transient(|_| 1u32).condition_with(|cx| cx.contains_provider::<u16>()),
+                transient(|_| 2u16).condition(None),
+            ]
+        }
+    }
+
+    let cx = Context::create(modules![ChainModule]);
+
+    assert!(cx.contains_provider::<u32>());
+    assert!(cx.contains_provider::<u8>());
+    assert!(cx.contains_provider::<i8>());
+
+    let provider = cx.get_provider::<u8>().unwrap();
+    assert!(provider.condition().is_some_and(|condition| condition(&cx)));
+    assert!(provider.condition_with().is_some());
+
+    let provider = cx.get_provider::<i8>().unwrap();
+    assert!(provider.condition().is_none());
+    assert!(provider.condition_with().is_some());
+}
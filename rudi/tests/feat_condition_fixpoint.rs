@@ -0,0 +1,236 @@
+use std::cell::Cell;
+
+use rudi::{
+    components, modules, providers, singleton, transient, Context, DynProvider, Module,
+    ResolveErrorKind, Shared, Transient,
+};
+
+struct Cache;
+
+struct Metrics;
+
+struct MetricsModule;
+
+impl Module for MetricsModule {
+    fn providers() -> Vec<DynProvider> {
+        providers![transient(|_| Metrics).condition(Some(|cx| cx.contains_provider::<Cache>()))]
+    }
+}
+
+struct CacheModule;
+
+impl Module for CacheModule {
+    fn providers() -> Vec<DynProvider> {
+        providers![transient(|_| Cache).condition(Some(|_| true))]
+    }
+}
+
+#[test]
+fn result_does_not_depend_on_module_order() {
+    for modules in [
+        modules![MetricsModule, CacheModule],
+        modules![CacheModule, MetricsModule],
+    ] {
+        let cx = Context::create(modules);
+
+        assert!(cx.contains_provider::<Cache>());
+        assert!(cx.contains_provider::<Metrics>());
+    }
+}
+
+#[test]
+fn default_yields_to_conditional_provider() {
+    struct DefaultModule;
+
+    impl Module for DefaultModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![singleton(|_| "memory")
+                .condition(Some(|cx| !cx.contains_provider::<Shared<str>>()))
+                .bind(Shared::<str>::from)]
+        }
+    }
+
+    struct RedisModule;
+
+    impl Module for RedisModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![transient(|_| Shared::<str>::from("redis")).condition(Some(|_| true))]
+        }
+    }
+
+    let mut cx = Context::create(modules![DefaultModule]);
+    assert_eq!(&*cx.resolve::<Shared<str>>(), "memory");
+
+    for modules in [
+        modules![DefaultModule, RedisModule],
+        modules![RedisModule, DefaultModule],
+    ] {
+        let mut cx = Context::create(modules);
+
+        assert_eq!(&*cx.resolve::<Shared<str>>(), "redis");
+        assert!(!cx.contains_provider::<&str>());
+    }
+}
+
+#[test]
+fn chained_conditions() {
+    struct ChainModule;
+
+    impl Module for ChainModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![
+                transient(|_| 3u8).condition(Some(|cx| cx.contains_provider::<u16>())),
+                transient(|_| 2u16).condition(Some(|cx| cx.contains_provider::<u32>())),
+                transient(|_| 1u32).condition(Some(|_| true)),
+            ]
+        }
+    }
+
+    let mut cx = Context::create(modules![ChainModule]);
+
+    assert_eq!(cx.resolve::<u8>(), 3);
+    assert_eq!(cx.conditional_providers().len(), 0);
+}
+
+#[test]
+fn contradictory_conditions_are_reported() {
+    struct SelfDefeatingModule;
+
+    impl Module for SelfDefeatingModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![
+                transient(|_| 1u8).condition(Some(|cx| !cx.contains_provider::<u16>())),
+                transient(|_| 2u16).condition(Some(|cx| cx.contains_provider::<u8>())),
+                transient(|_| 3u32).condition(Some(|_| true)),
+            ]
+        }
+    }
+
+    let err = Context::try_create(modules![SelfDefeatingModule])
+        .err()
+        .unwrap();
+
+    let ResolveErrorKind::ContradictoryConditions(definitions) = err.kind() else {
+        panic!("unexpected error: {}", err);
+    };
+
+    assert_eq!(definitions.len(), 2);
+    assert!(definitions[0].key.ty.name.contains("u8"));
+    assert!(definitions[1].key.ty.name.contains("u16"));
+    assert!(err
+        .to_string()
+        .starts_with("contradictory conditions detected"));
+}
+
+#[test]
+fn ambiguous_conditions_are_reported() {
+    struct ExclusiveModule;
+
+    impl Module for ExclusiveModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![
+                transient(|_| 1u8).condition(Some(|cx| !cx.contains_provider::<u16>())),
+                transient(|_| 2u16).condition(Some(|cx| !cx.contains_provider::<u8>())),
+                transient(|_| 3u32).condition(Some(|_| true)),
+            ]
+        }
+    }
+
+    let err = Context::try_create(modules![ExclusiveModule])
+        .err()
+        .unwrap();
+
+    let ResolveErrorKind::AmbiguousConditions(definitions) = err.kind() else {
+        panic!("unexpected error: {}", err);
+    };
+
+    assert_eq!(definitions.len(), 2);
+    assert!(definitions[0].key.ty.name.contains("u8"));
+    assert!(definitions[1].key.ty.name.contains("u16"));
+    assert!(err.to_string().starts_with("ambiguous conditions detected"));
+}
+
+#[test]
+fn only_affected_conditions_are_evaluated_again() {
+    thread_local! {
+        static EVALUATIONS: Cell<usize> = const { Cell::new(0) };
+    }
+
+    fn count() {
+        EVALUATIONS.with(|evaluations| evaluations.set(evaluations.get() + 1));
+    }
+
+    struct ChainModule;
+
+    impl Module for ChainModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![
+                transient(|_| 3u8).condition_with(|cx| {
+                    count();
+                    cx.contains_provider::<u16>()
+                }),
+                transient(|_| 2u16).condition_with(|cx| {
+                    count();
+                    cx.contains_provider::<u32>()
+                }),
+                transient(|_| 1u32).condition_with(|_| {
+                    count();
+                    true
+                }),
+                transient(|_| "unrelated").condition_with(|_| {
+                    count();
+                    true
+                }),
+            ]
+        }
+    }
+
+    EVALUATIONS.with(|evaluations| evaluations.set(0));
+
+    let cx = Context::create(modules![ChainModule]);
+
+    assert!(cx.contains_provider::<u8>());
+    // 4 in the first round, then `u16` after `u32` is met, then `u8` after `u16` is met
+    assert_eq!(EVALUATIONS.with(Cell::get), 6);
+}
+
+#[test]
+#[should_panic(expected = "contradictory conditions detected")]
+fn contradictory_conditions_panic() {
+    struct SelfDefeatingModule;
+
+    impl Module for SelfDefeatingModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![
+                transient(|_| 1u8).condition(Some(|cx| !cx.contains_provider::<u16>())),
+                transient(|_| 2u16).condition(Some(|cx| cx.contains_provider::<u8>())),
+            ]
+        }
+    }
+
+    Context::create(modules![SelfDefeatingModule]);
+}
+
+#[test]
+fn condition_attribute() {
+    fn has_cache(cx: &Context) -> bool {
+        cx.contains_provider::<Cache>()
+    }
+
+    #[Transient(condition = has_cache, auto_register = false)]
+    fn Name() -> &'static str {
+        "cached"
+    }
+
+    struct AttributeModule;
+
+    impl Module for AttributeModule {
+        fn providers() -> Vec<DynProvider> {
+            components![Name]
+        }
+    }
+
+    let mut cx = Context::create(modules![AttributeModule, CacheModule]);
+
+    assert_eq!(cx.resolve::<&'static str>(), "cached");
+}
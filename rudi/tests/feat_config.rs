@@ -0,0 +1,313 @@
+#![cfg(feature = "config")]
+
+use std::{env, fs, path::PathBuf};
+
+use rudi::{
+    components, modules, providers, transient, Config, ConfigError, Context, DynProvider, Module,
+    ResolveErrorKind, Shared, Singleton, Transient,
+};
+use serde::Deserialize;
+
+fn write_file(name: &str, content: &str) -> PathBuf {
+    let path = env::temp_dir().join(format!("rudi-feat-config-{}", name));
+    fs::write(&path, content).unwrap();
+    path
+}
+
+#[test]
+fn layered_sources() {
+    let toml = write_file(
+        "layered.toml",
+        r#"
+[database]
+url = "postgres://localhost"
+port = 5432
+
+[database.pool]
+min = 1
+max = 8
+"#,
+    );
+    let json = write_file(
+        "layered.json",
+        r#"{ "database": { "pool": { "max": 16 } } }"#,
+    );
+
+    env::set_var("LAYERED_DATABASE__URL", "postgres://env");
+
+    let cx = Context::options()
+        .config_file(&toml)
+        .config_file(&json)
+        .config_env("LAYERED")
+        .config_values([("database.pool.min", 2)])
+        .create(modules![]);
+
+    let config = cx.config();
+    assert_eq!(
+        config.get::<String>("database.url").unwrap(),
+        "postgres://env"
+    );
+    assert_eq!(config.get::<u16>("database.port").unwrap(), 5432);
+    assert_eq!(config.get::<u32>("database.pool.min").unwrap(), 2);
+    assert_eq!(config.get::<u32>("database.pool.max").unwrap(), 16);
+    assert!(config.contains("database.pool"));
+    assert!(!config.contains("database.user"));
+    assert_eq!(config.get_option::<String>("database.user").unwrap(), None);
+}
+
+#[test]
+fn string_values_are_parsed() {
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Server {
+        port: u16,
+        tls: bool,
+        hosts: Vec<String>,
+    }
+
+    let cx = Context::options()
+        .config_values([
+            ("server.port", "8080"),
+            ("server.tls", "true"),
+            ("server.hosts", r#"["a", "b"]"#),
+            ("server.name", "8080"),
+        ])
+        .create(modules![]);
+
+    let config = cx.config();
+    assert_eq!(config.get::<String>("server.name").unwrap(), "8080");
+    assert_eq!(
+        config.get::<Vec<String>>("server.hosts").unwrap(),
+        ["a", "b"]
+    );
+    assert_eq!(
+        config.get::<Server>("server").unwrap(),
+        Server {
+            port: 8080,
+            tls: true,
+            hosts: vec!["a".to_string(), "b".to_string()],
+        }
+    );
+
+    let err = config.get::<u16>("server.tls").unwrap_err();
+    assert!(matches!(err, ConfigError::Invalid(key, _) if key == "server.tls"));
+}
+
+#[test]
+#[should_panic(expected = "failed to read the configuration file")]
+fn missing_file_panics() {
+    let path = env::temp_dir().join("rudi-feat-config-missing.toml");
+
+    Context::options().config_file(path).create(modules![]);
+}
+
+#[test]
+#[should_panic(expected = "unsupported format")]
+fn unsupported_file_panics() {
+    let path = write_file("unsupported.yaml", "a: 1");
+
+    Context::options().config_file(path).create(modules![]);
+}
+
+#[test]
+fn field_and_argument_attribute() {
+    #[derive(Clone)]
+    #[Singleton(auto_register = false)]
+    struct Database {
+        #[di(config = "database.url")]
+        url: String,
+        #[di(config = "database.port", default = 5432)]
+        port: u16,
+        #[di(config = "database.user", option)]
+        user: Option<String>,
+        #[di(config = "database.password", default)]
+        password: String,
+    }
+
+    #[Transient(auto_register = false)]
+    fn Address(#[di(ref)] database: &Database, #[di(config = "app.name")] name: String) -> String {
+        format!("{}@{}:{}", name, database.url, database.port)
+    }
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            components![Database, Address]
+        }
+    }
+
+    let mut cx = Context::options()
+        .config_values([
+            ("database.url", "localhost"),
+            ("database.user", "admin"),
+            ("app.name", "rudi"),
+        ])
+        .create(modules![MyModule]);
+
+    assert!(cx.validate().is_ok());
+    assert!(cx
+        .get_provider::<Database>()
+        .unwrap()
+        .dependencies()
+        .is_empty());
+
+    let Database {
+        url,
+        port,
+        user,
+        password,
+    } = cx.resolve::<Database>();
+    assert_eq!(url, "localhost");
+    assert_eq!(port, 5432);
+    assert_eq!(user.as_deref(), Some("admin"));
+    assert_eq!(password, "");
+
+    assert_eq!(cx.resolve::<String>(), "rudi@localhost:5432");
+}
+
+#[test]
+fn errors_name_the_provider() {
+    #[Transient(auto_register = false)]
+    struct Redis {
+        #[di(config = "redis.url")]
+        #[allow(dead_code)]
+        url: String,
+        #[di(config = "redis.pool", default = 8)]
+        #[allow(dead_code)]
+        pool: u8,
+    }
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            components![Redis]
+        }
+    }
+
+    let mut cx = Context::create(modules![MyModule]);
+
+    let err = cx.try_resolve::<Redis>().err().unwrap();
+    let ResolveErrorKind::ConstructorFailed(definition, source) = err.kind() else {
+        panic!("unexpected error: {}", err);
+    };
+    assert!(definition.key.ty.name.ends_with("Redis"));
+    assert_eq!(
+        source.to_string(),
+        "missing configuration value for: `redis.url`"
+    );
+
+    let mut cx = Context::options()
+        .config_values([("redis.url", "redis://localhost"), ("redis.pool", "many")])
+        .create(modules![MyModule]);
+
+    let err = cx.try_resolve::<Redis>().err().unwrap();
+    assert!(err.to_string().contains("Redis"));
+    assert!(err
+        .to_string()
+        .contains("invalid configuration value for: `redis.pool`"));
+}
+
+#[test]
+fn config_prefix_attribute() {
+    #[derive(Debug, Clone, PartialEq, Deserialize)]
+    #[Singleton(config_prefix = "redis", auto_register = false)]
+    struct RedisConfig {
+        url: String,
+        #[serde(default)]
+        pool: u8,
+    }
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            components![RedisConfig]
+        }
+    }
+
+    let mut cx = Context::options()
+        .config_values([("redis.url", "redis://localhost")])
+        .create(modules![MyModule]);
+
+    assert_eq!(
+        cx.resolve::<RedisConfig>(),
+        RedisConfig {
+            url: "redis://localhost".to_string(),
+            pool: 0,
+        }
+    );
+
+    let mut cx = Context::create(modules![MyModule]);
+
+    let err = cx.try_resolve::<RedisConfig>().err().unwrap();
+    assert!(matches!(
+        err.kind(),
+        ResolveErrorKind::ConstructorFailed(definition, _) if definition.key.ty.name.ends_with("RedisConfig")
+    ));
+}
+
+#[test]
+fn condition_reads_config() {
+    struct CacheModule;
+
+    impl Module for CacheModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![transient(|_| "redis").condition_with(|cx| {
+                cx.config()
+                    .get_option::<bool>("cache.enabled")
+                    .is_ok_and(|enabled| enabled == Some(true))
+            })]
+        }
+    }
+
+    let cx = Context::options()
+        .config_values([("cache.enabled", "true")])
+        .create(modules![CacheModule]);
+    assert!(cx.contains_provider::<&str>());
+
+    let cx = Context::create(modules![CacheModule]);
+    assert!(!cx.contains_provider::<&str>());
+}
+
+#[test]
+fn child_shares_config() {
+    let parent = Context::options()
+        .config_values([("app.name", "rudi")])
+        .create(modules![]);
+    let child = Shared::new(parent).child(modules![]);
+
+    assert_eq!(child.config().get::<String>("app.name").unwrap(), "rudi");
+}
+
+#[test]
+fn default_config_is_empty() {
+    let config = Config::default();
+
+    assert!(!config.contains("app"));
+    assert!(matches!(
+        config.get::<String>("app.name"),
+        Err(ConfigError::Missing(key)) if key == "app.name"
+    ));
+}
+
+#[tokio::test]
+async fn field_attribute_async() {
+    #[Transient(async, auto_register = false)]
+    struct Port(#[di(config = "server.port")] u16);
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            components![Port]
+        }
+    }
+
+    let mut cx = Context::options()
+        .config_values([("server.port", 8080)])
+        .create(modules![MyModule]);
+
+    let Port(port) = cx.resolve_async().await;
+    assert_eq!(port, 8080);
+}
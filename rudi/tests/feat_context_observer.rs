@@ -0,0 +1,149 @@
+use std::{sync::Mutex, time::Duration};
+
+use rudi::{
+    modules, providers, singleton, transient, Context, ContextObserver, Definition, DynProvider,
+    Key, Module, Shared, Type,
+};
+
+#[derive(Clone, Default)]
+struct Spy(Shared<Mutex<Vec<String>>>);
+
+impl Spy {
+    fn take(&self) -> Vec<String> {
+        std::mem::take(&mut self.0.lock().unwrap())
+    }
+}
+
+impl ContextObserver for Spy {
+    fn on_provider_inserted(&self, definition: &Definition) {
+        self.0
+            .lock()
+            .unwrap()
+            .push(format!("inserted {}", definition.key.ty.name));
+    }
+
+    fn on_provider_overridden(&self, definition: &Definition) {
+        self.0
+            .lock()
+            .unwrap()
+            .push(format!("overridden {}", definition.key.ty.name));
+    }
+
+    fn on_condition_evaluated(&self, definition: &Definition, met: bool) {
+        self.0
+            .lock()
+            .unwrap()
+            .push(format!("condition {} {}", definition.key.ty.name, met));
+    }
+
+    fn on_construct_started(&self, key: &Key, depth: usize) {
+        self.0
+            .lock()
+            .unwrap()
+            .push(format!("started {} {}", key.ty.name, depth));
+    }
+
+    fn on_construct_finished(&self, key: &Key, _elapsed: Duration, depth: usize) {
+        self.0
+            .lock()
+            .unwrap()
+            .push(format!("finished {} {}", key.ty.name, depth));
+    }
+
+    fn on_single_cached(&self, key: &Key) {
+        self.0
+            .lock()
+            .unwrap()
+            .push(format!("cached {}", key.ty.name));
+    }
+
+    fn on_module_loaded(&self, module: &Type) {
+        self.0
+            .lock()
+            .unwrap()
+            .push(format!("loaded {}", module.name));
+    }
+
+    fn on_module_unloaded(&self, module: &Type) {
+        self.0
+            .lock()
+            .unwrap()
+            .push(format!("unloaded {}", module.name));
+    }
+}
+
+struct MyModule;
+
+impl Module for MyModule {
+    fn providers() -> Vec<DynProvider> {
+        providers![
+            transient(|_| 1u8),
+            singleton(|cx| u16::from(cx.resolve::<u8>())),
+            transient(|_| 1u32).condition(Some(|_| false)),
+        ]
+    }
+}
+
+#[test]
+fn lifecycle_events() {
+    let spy = Spy::default();
+
+    let mut cx = Context::options()
+        .observer(spy.clone())
+        .create(modules![MyModule]);
+
+    assert_eq!(
+        spy.take(),
+        [
+            "inserted u8",
+            "inserted u16",
+            "loaded feat_context_observer::MyModule",
+            "condition u32 false",
+        ]
+    );
+
+    cx.resolve::<u16>();
+    cx.resolve::<u16>();
+
+    assert_eq!(
+        spy.take(),
+        [
+            "started u16 0",
+            "started u8 1",
+            "finished u8 1",
+            "finished u16 0",
+            "cached u16",
+        ]
+    );
+
+    cx.unload_modules(modules![MyModule]);
+
+    assert_eq!(spy.take(), ["unloaded feat_context_observer::MyModule"]);
+}
+
+#[test]
+fn provider_overridden() {
+    let spy = Spy::default();
+
+    let mut cx = Context::options().observer(spy.clone()).create(modules![]);
+
+    cx.insert_singleton(1i8);
+    cx.insert_singleton(2i8);
+
+    assert_eq!(spy.take(), ["inserted i8", "overridden i8"]);
+}
+
+#[test]
+fn child_notifies_ancestors() {
+    let spy = Spy::default();
+
+    let parent = Context::options()
+        .observer(spy.clone())
+        .create(modules![MyModule]);
+    spy.take();
+
+    let mut child = Shared::new(parent).child(modules![]);
+    child.resolve::<u8>();
+
+    assert_eq!(spy.take(), ["started u8 0", "finished u8 0"]);
+}
@@ -0,0 +1,248 @@
+use std::{
+    cell::Cell,
+    panic::{self, AssertUnwindSafe},
+};
+
+use rudi::{
+    modules, providers, singleton, CachedInstance, Context, CustomScope, CustomScopeId,
+    DynProvider, Key, Module, ResolveModule, Scope, ScopeDecision, Shared, Singleton,
+};
+
+thread_local! {
+    static TENANT: Cell<u64> = const { Cell::new(0) };
+    static COUNT: Cell<usize> = const { Cell::new(0) };
+}
+
+fn next() -> usize {
+    COUNT.with(|count| count.replace(count.get() + 1))
+}
+
+struct PerTenant;
+
+impl CustomScope for PerTenant {
+    fn partition(&self, _: &Context, _: &Key) -> u64 {
+        TENANT.with(Cell::get)
+    }
+}
+
+struct Evicting;
+
+impl CustomScope for Evicting {
+    fn decide(&self, _: &Key, cached: Option<&CachedInstance>) -> ScopeDecision {
+        match cached {
+            Some(_) => ScopeDecision::Evict,
+            None => ScopeDecision::Create,
+        }
+    }
+}
+
+struct AlwaysCreate;
+
+impl CustomScope for AlwaysCreate {
+    fn decide(&self, _: &Key, _: Option<&CachedInstance>) -> ScopeDecision {
+        ScopeDecision::Create
+    }
+}
+
+#[test]
+fn partition() {
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![singleton(|_| next()).scope::<PerTenant>()]
+        }
+    }
+
+    COUNT.with(|count| count.set(0));
+
+    let mut cx = Context::options()
+        .custom_scope(PerTenant)
+        .create(modules![MyModule]);
+
+    assert!(matches!(
+        cx.get_provider::<usize>().unwrap().definition().scope,
+        Scope::Custom(_)
+    ));
+
+    TENANT.with(|tenant| tenant.set(1));
+    assert_eq!(cx.resolve::<usize>(), 0);
+    assert_eq!(cx.resolve::<usize>(), 0);
+
+    TENANT.with(|tenant| tenant.set(2));
+    assert_eq!(cx.resolve::<usize>(), 1);
+
+    TENANT.with(|tenant| tenant.set(1));
+    assert_eq!(cx.resolve::<usize>(), 0);
+
+    assert!(!cx.contains_single::<usize>());
+}
+
+#[test]
+fn evict() {
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![singleton(|_| next()).scope::<Evicting>()]
+        }
+    }
+
+    COUNT.with(|count| count.set(0));
+
+    let mut cx = Context::options()
+        .custom_scope(Evicting)
+        .create(modules![MyModule]);
+
+    assert_eq!(cx.resolve::<usize>(), 0);
+    assert_eq!(cx.resolve::<usize>(), 1);
+    assert_eq!(cx.resolve::<usize>(), 2);
+}
+
+#[test]
+fn create() {
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![singleton(|_| next()).scope::<AlwaysCreate>()]
+        }
+    }
+
+    COUNT.with(|count| count.set(0));
+
+    let mut cx = Context::options()
+        .custom_scope(AlwaysCreate)
+        .create(modules![MyModule]);
+
+    assert_eq!(cx.resolve::<usize>(), 0);
+    assert_eq!(cx.resolve::<usize>(), 1);
+}
+
+#[test]
+fn child_inherits_custom_scopes() {
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![singleton(|_| next()).scope::<PerTenant>()]
+        }
+    }
+
+    COUNT.with(|count| count.set(0));
+
+    let parent = Shared::new(
+        Context::options()
+            .custom_scope(PerTenant)
+            .create(modules![]),
+    );
+
+    let mut child = parent.child(modules![MyModule]);
+
+    assert_eq!(child.resolve::<usize>(), 0);
+    assert_eq!(child.resolve::<usize>(), 0);
+}
+
+#[test]
+#[should_panic(expected = "no custom scope registered for")]
+fn missing_custom_scope() {
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![singleton(|_| 1).scope::<PerTenant>()]
+        }
+    }
+
+    Context::create(modules![MyModule]);
+}
+
+#[test]
+fn custom_scopes_are_keyed_by_type() {
+    let (registered_scope, registered_id, registered_module) = {
+        struct Local;
+
+        impl CustomScope for Local {}
+
+        struct LocalModule;
+
+        impl Module for LocalModule {
+            fn providers() -> Vec<DynProvider> {
+                providers![singleton(|_| 1u8).scope::<Local>()]
+            }
+        }
+
+        (
+            Local,
+            CustomScopeId::of::<Local>(),
+            ResolveModule::new::<LocalModule>(),
+        )
+    };
+
+    let (other_id, other_module) = {
+        struct Local;
+
+        impl CustomScope for Local {}
+
+        struct LocalModule;
+
+        impl Module for LocalModule {
+            fn providers() -> Vec<DynProvider> {
+                providers![singleton(|_| 2u16).scope::<Local>()]
+            }
+        }
+
+        (
+            CustomScopeId::of::<Local>(),
+            ResolveModule::new::<LocalModule>(),
+        )
+    };
+
+    assert_eq!(registered_id.name(), other_id.name());
+    assert_ne!(registered_id, other_id);
+
+    let mut cx = Context::options()
+        .custom_scope(registered_scope)
+        .create(vec![registered_module]);
+
+    assert_eq!(
+        cx.get_provider::<u8>().unwrap().definition().scope,
+        Scope::Custom(registered_id)
+    );
+    assert_eq!(cx.resolve::<u8>(), 1);
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| {
+        cx.load_modules(vec![other_module]);
+    }));
+    assert!(result.is_err());
+}
+
+#[test]
+fn custom_scope_with_macro() {
+    #[derive(Clone)]
+    #[Singleton(scope = PerTenant, auto_register = false)]
+    struct A(#[di(name = "count")] usize);
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            let mut providers = providers![rudi::transient(|_| next()).name("count")];
+            providers.extend(rudi::components![A]);
+            providers
+        }
+    }
+
+    COUNT.with(|count| count.set(0));
+
+    let mut cx = Context::options()
+        .custom_scope(PerTenant)
+        .create(modules![MyModule]);
+
+    TENANT.with(|tenant| tenant.set(1));
+    assert_eq!(cx.resolve::<A>().0, 0);
+    assert_eq!(cx.resolve::<A>().0, 0);
+
+    TENANT.with(|tenant| tenant.set(2));
+    assert_eq!(cx.resolve::<A>().0, 1);
+}
@@ -0,0 +1,228 @@
+use std::cell::Cell;
+
+use rudi::{
+    components, decorators, modules, providers, singleton, transient, transient_async, Context,
+    Decorator, DynDecorator, DynProvider, FutureExt, MaybeSendSync, Module, Shared, Transient,
+};
+
+#[test]
+fn applied_in_registration_order() {
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![transient(|_| String::from("a"))]
+        }
+    }
+
+    let mut cx = Context::create(modules![MyModule]);
+    cx.decorate("", |s: String, _cx| s + "b");
+    cx.decorate("", |s: String, _cx| s + "c");
+
+    assert_eq!(cx.resolve::<String>(), "abc");
+    assert_eq!(cx.resolve::<String>(), "abc");
+}
+
+#[test]
+fn singleton_is_decorated_once() {
+    thread_local! {
+        static COUNT: Cell<u32> = const { Cell::new(0) };
+    }
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![singleton(|_| 1)]
+        }
+
+        fn decorators() -> Vec<DynDecorator> {
+            vec![DynDecorator::new("", |n: i32, _cx| {
+                COUNT.with(|count| count.set(count.get() + 1));
+                n + 1
+            })]
+        }
+    }
+
+    let mut cx = Context::create(modules![MyModule]);
+
+    assert_eq!(cx.resolve::<i32>(), 2);
+    assert_eq!(cx.resolve::<i32>(), 2);
+    assert_eq!(*cx.get_single::<i32>(), 2);
+    assert_eq!(COUNT.with(Cell::get), 1);
+}
+
+#[test]
+fn decorate_bound_provider() {
+    trait Greet: MaybeSendSync {
+        fn greet(&self) -> String;
+    }
+
+    struct Greeter;
+
+    impl Greet for Greeter {
+        fn greet(&self) -> String {
+            String::from("hello")
+        }
+    }
+
+    struct Loud(Shared<dyn Greet>);
+
+    impl Greet for Loud {
+        fn greet(&self) -> String {
+            self.0.greet().to_uppercase()
+        }
+    }
+
+    fn into_greet(greeter: Greeter) -> Shared<dyn Greet> {
+        Shared::new(greeter)
+    }
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![transient(|_| Greeter).bind(into_greet)]
+        }
+    }
+
+    let mut cx = Context::create(modules![MyModule]);
+    cx.decorate("", |greet: Shared<dyn Greet>, _cx| -> Shared<dyn Greet> {
+        Shared::new(Loud(greet))
+    });
+
+    assert_eq!(cx.resolve::<Shared<dyn Greet>>().greet(), "HELLO");
+}
+
+#[test]
+fn decorator_macro() {
+    #[Transient(name = "number", auto_register = false)]
+    fn Number() -> i32 {
+        1
+    }
+
+    #[Decorator(name = "number", auto_register = false)]
+    fn Double(n: i32, cx: &mut Context) -> i32 {
+        n * cx.resolve::<i32>()
+    }
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            let mut providers = providers![transient(|_| 2)];
+            providers.extend(components![Number]);
+            providers
+        }
+
+        fn decorators() -> Vec<DynDecorator> {
+            decorators![Double]
+        }
+    }
+
+    let mut cx = Context::create(modules![MyModule]);
+
+    assert_eq!(cx.resolve_with_name::<i32>("number"), 2);
+    assert_eq!(cx.resolve::<i32>(), 2);
+}
+
+#[test]
+fn auto_registered_decorator() {
+    #[Transient(name = "auto")]
+    fn Text() -> &'static str {
+        "text"
+    }
+
+    #[Decorator(name = "auto")]
+    fn Shout(_: &'static str, _cx: &mut Context) -> &'static str {
+        "TEXT"
+    }
+
+    let mut cx = Context::auto_register();
+
+    assert_eq!(cx.resolve_with_name::<&'static str>("auto"), "TEXT");
+}
+
+#[test]
+fn factory_is_decorated() {
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![transient(|_| 1u8)]
+        }
+    }
+
+    let mut cx = Context::create(modules![MyModule]);
+    cx.decorate("", |n: u8, _cx| n + 1);
+
+    let factory = cx.factory::<u8>();
+    assert_eq!(factory.create(), 2);
+}
+
+#[test]
+fn ancestors_first() {
+    struct ParentModule;
+
+    impl Module for ParentModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![transient(|_| String::from("a"))]
+        }
+    }
+
+    let mut parent = Context::create(modules![ParentModule]);
+    parent.decorate("", |s: String, _cx| s + "p");
+
+    let mut child = Shared::new(parent).child(modules![]);
+    child.decorate("", |s: String, _cx| s + "c");
+
+    assert_eq!(child.resolve::<String>(), "apc");
+}
+
+#[test]
+fn reload_and_unload_module() {
+    struct ProviderModule;
+
+    impl Module for ProviderModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![transient(|_| String::from("a"))]
+        }
+    }
+
+    struct DecoratorModule;
+
+    impl Module for DecoratorModule {
+        fn providers() -> Vec<DynProvider> {
+            Vec::new()
+        }
+
+        fn decorators() -> Vec<DynDecorator> {
+            vec![DynDecorator::new("", |s: String, _cx| s + "b")]
+        }
+    }
+
+    let mut cx = Context::create(modules![ProviderModule, DecoratorModule]);
+    cx.decorate("", |s: String, _cx| s + "c");
+
+    cx.load_modules(modules![DecoratorModule]);
+    assert_eq!(cx.resolve::<String>(), "acb");
+
+    cx.unload_modules(modules![DecoratorModule]);
+    assert_eq!(cx.resolve::<String>(), "ac");
+}
+
+#[tokio::test]
+async fn decorate_async_provider() {
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![transient_async(|_| async { 1u16 }.boxed())]
+        }
+    }
+
+    let mut cx = Context::create(modules![MyModule]);
+    cx.decorate("", |n: u16, _cx| n * 10);
+
+    assert_eq!(cx.resolve_async::<u16>().await, 10);
+}
@@ -0,0 +1,158 @@
+use std::any;
+
+use rudi::{components, modules, Context, DynProvider, Module, Shared, Singleton, Transient};
+
+trait Greet: Send + Sync {}
+
+#[derive(Clone)]
+#[Singleton(binds = [Self::into_greet], auto_register = false)]
+struct Greeter;
+
+impl Greet for Greeter {}
+
+impl Greeter {
+    fn into_greet(self) -> Shared<dyn Greet> {
+        Shared::new(self)
+    }
+}
+
+#[allow(dead_code)]
+#[derive(Clone)]
+#[Transient(name = "hello", auto_register = false)]
+struct Hello(#[di(option)] Option<u8>, Shared<dyn Greet>);
+
+#[allow(dead_code)]
+#[derive(Clone)]
+#[Singleton(async, auto_register = false)]
+struct App(#[di(name = "hello")] Hello, Greeter);
+
+struct MyModule;
+
+impl Module for MyModule {
+    fn providers() -> Vec<DynProvider> {
+        components![Greeter, Hello, App]
+    }
+}
+
+#[tokio::test]
+async fn edges() {
+    let mut cx = Context::create(modules![MyModule]);
+
+    assert!(cx.dependency_graph().edges().is_empty());
+
+    cx.resolve_async::<App>().await;
+
+    let graph = cx.dependency_graph();
+
+    assert_eq!(graph.nodes().len(), 4);
+
+    let mut edges = graph
+        .edges()
+        .iter()
+        .map(|(from, to)| (from.ty.name, to.ty.name))
+        .collect::<Vec<_>>();
+    edges.sort();
+
+    let mut expected = vec![
+        (
+            any::type_name::<Shared<dyn Greet>>(),
+            any::type_name::<Greeter>(),
+        ),
+        (any::type_name::<App>(), any::type_name::<Hello>()),
+        (any::type_name::<App>(), any::type_name::<Greeter>()),
+        (any::type_name::<Hello>(), any::type_name::<u8>()),
+        (
+            any::type_name::<Hello>(),
+            any::type_name::<Shared<dyn Greet>>(),
+        ),
+    ];
+    expected.sort();
+
+    assert_eq!(edges, expected);
+}
+
+#[tokio::test]
+async fn dot_and_mermaid() {
+    let mut cx = Context::create(modules![MyModule]);
+
+    cx.resolve_async::<App>().await;
+
+    let graph = cx.dependency_graph();
+
+    let dot = graph.to_dot();
+    assert!(dot.starts_with("digraph rudi {"));
+    assert!(dot.contains("Singleton, async"));
+    assert!(dot.contains(r#"name: \"hello\"\nTransient, sync"#));
+    assert!(dot.contains(&format!("bind from: {}", any::type_name::<Greeter>())));
+    assert!(dot.contains("not registered\", style=dashed"));
+    assert_eq!(dot.matches(" -> ").count(), 5);
+
+    let mermaid = graph.to_mermaid();
+    assert!(mermaid.starts_with("flowchart LR"));
+    assert!(mermaid.contains("name: #quot;hello#quot;<br/>Transient, sync"));
+    assert!(mermaid.contains("#lt;dyn "));
+    assert_eq!(mermaid.matches(" --> ").count(), 5);
+}
+
+#[test]
+fn child_context() {
+    #[Transient(auto_register = false)]
+    struct A;
+
+    #[Transient(auto_register = false)]
+    struct B(#[allow(dead_code)] A);
+
+    struct ParentModule;
+
+    impl Module for ParentModule {
+        fn providers() -> Vec<DynProvider> {
+            components![A]
+        }
+    }
+
+    struct ChildModule;
+
+    impl Module for ChildModule {
+        fn providers() -> Vec<DynProvider> {
+            components![B]
+        }
+    }
+
+    let parent = Shared::new(Context::create(modules![ParentModule]));
+    let mut child = parent.child(modules![ChildModule]);
+
+    child.resolve::<B>();
+
+    let graph = child.dependency_graph();
+    assert_eq!(graph.nodes().len(), 2);
+    assert_eq!(graph.edges().len(), 1);
+    assert!(parent.dependency_graph().edges().is_empty());
+}
+
+#[test]
+fn dot_escapes_backslashes() {
+    #[Transient(name = "dir\\", auto_register = false)]
+    struct Dir;
+
+    #[Transient(auto_register = false)]
+    struct Walker(
+        #[di(name = "dir\\")]
+        #[allow(dead_code)]
+        Dir,
+    );
+
+    struct EscapeModule;
+
+    impl Module for EscapeModule {
+        fn providers() -> Vec<DynProvider> {
+            components![Dir, Walker]
+        }
+    }
+
+    let mut cx = Context::create(modules![EscapeModule]);
+
+    cx.resolve::<Walker>();
+
+    let dot = cx.dependency_graph().to_dot();
+    assert!(dot.contains(r#"name: \"dir\\\\\"\nTransient, sync"#));
+}
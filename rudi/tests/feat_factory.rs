@@ -0,0 +1,264 @@
+use std::cell::Cell;
+
+use rudi::{
+    components, modules, providers, singleton, transient, transient_async, AsyncFactory, Context,
+    DynProvider, Factory, FutureExt, Module, ResolveErrorKind, Singleton, Transient,
+};
+
+thread_local! {
+    static COUNT: Cell<usize> = const { Cell::new(0) };
+}
+
+fn next() -> usize {
+    COUNT.with(|count| count.replace(count.get() + 1))
+}
+
+#[test]
+fn create_fresh_transients() {
+    #[Transient(auto_register = false)]
+    struct Request(#[di(name = "id")] usize);
+
+    #[derive(Clone)]
+    #[Singleton(auto_register = false)]
+    struct Client(#[di(factory)] Factory<Request>);
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            let mut providers = providers![transient(|_| next()).name("id")];
+            providers.extend(components![Request, Client]);
+            providers
+        }
+    }
+
+    COUNT.with(|count| count.set(0));
+
+    let mut cx = Context::create(modules![MyModule]);
+
+    let client = cx.resolve::<Client>();
+
+    assert_eq!(client.0.create().0, 0);
+    assert_eq!(client.0.create().0, 1);
+
+    let client = cx.get_single::<Client>().clone();
+    assert_eq!(client.0.create().0, 2);
+}
+
+#[test]
+fn named_factory() {
+    #[Transient(name = "id", auto_register = false)]
+    fn Id() -> usize {
+        next()
+    }
+
+    #[Singleton(auto_register = false)]
+    fn Ids(#[di(name = "id", factory)] ids: Factory<usize>) -> Factory<usize> {
+        ids
+    }
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            components![Id, Ids]
+        }
+    }
+
+    COUNT.with(|count| count.set(0));
+
+    let mut cx = Context::create(modules![MyModule]);
+
+    let factory = cx.resolve::<Factory<usize>>();
+    assert_eq!(factory.definition().key.name, "id");
+    assert_eq!(factory.create(), 0);
+    assert_eq!(factory.create(), 1);
+
+    let factory = cx.factory_with_name::<usize>("id");
+    assert_eq!(factory.create(), 2);
+}
+
+#[test]
+fn not_transient() {
+    #[derive(Clone)]
+    struct A;
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![singleton(|_| A), transient_async(|_| async { 1 }.boxed())]
+        }
+    }
+
+    let mut cx = Context::create(modules![MyModule]);
+
+    let err = cx.try_factory::<A>().unwrap_err();
+    assert!(matches!(err.kind(), ResolveErrorKind::NotTransient(_)));
+
+    let err = cx.try_factory::<i32>().unwrap_err();
+    assert!(matches!(
+        err.kind(),
+        ResolveErrorKind::AsyncConstructorInSync(_)
+    ));
+
+    let err = cx.try_factory::<u8>().unwrap_err();
+    assert!(matches!(err.kind(), ResolveErrorKind::NotFoundProvider(_)));
+}
+
+#[test]
+fn outlive_context() {
+    #[derive(Clone)]
+    #[Singleton(auto_register = false)]
+    struct Config(#[di(name = "id")] usize);
+
+    #[Transient(auto_register = false)]
+    struct Request(Config);
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            let mut providers = providers![transient(|_| next()).name("id")];
+            providers.extend(components![Config, Request]);
+            providers
+        }
+    }
+
+    COUNT.with(|count| count.set(0));
+
+    let mut cx = Context::create(modules![MyModule]);
+
+    let factory = cx.factory::<Request>();
+    drop(cx);
+
+    assert_eq!(factory.create().0 .0, 0);
+    assert_eq!(factory.create().0 .0, 0);
+}
+
+#[test]
+fn post_construct() {
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![transient(|_| next()).post_construct(|n, _| *n *= 10)]
+        }
+    }
+
+    COUNT.with(|count| count.set(1));
+
+    let mut cx = Context::create(modules![MyModule]);
+
+    let factory = cx.factory::<usize>();
+    assert_eq!(factory.create(), 10);
+    assert_eq!(factory.clone().create(), 20);
+}
+
+#[tokio::test]
+async fn async_factory() {
+    #[Transient(auto_register = false)]
+    async fn Number() -> usize {
+        next()
+    }
+
+    #[Singleton(auto_register = false)]
+    async fn Numbers(#[di(factory)] numbers: AsyncFactory<usize>) -> AsyncFactory<usize> {
+        numbers
+    }
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            components![Number, Numbers]
+        }
+    }
+
+    COUNT.with(|count| count.set(0));
+
+    let mut cx = Context::create(modules![MyModule]);
+
+    let factory = cx.resolve_async::<AsyncFactory<usize>>().await;
+    assert_eq!(factory.create().await, 0);
+    assert_eq!(factory.create().await, 1);
+}
+
+#[test]
+fn create_from_constructor_run_by_factory() {
+    #[Transient(auto_register = false)]
+    fn Batch(#[di(name = "id", factory)] ids: Factory<usize>) -> Vec<usize> {
+        vec![ids.create(), ids.create()]
+    }
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            let mut providers = providers![transient(|_| next()).name("id")];
+            providers.extend(components![Batch]);
+            providers
+        }
+    }
+
+    COUNT.with(|count| count.set(0));
+
+    let mut cx = Context::create(modules![MyModule]);
+
+    let batches = cx.factory::<Vec<usize>>();
+    assert_eq!(batches.create(), [0, 1]);
+    assert_eq!(batches.create(), [2, 3]);
+}
+
+#[tokio::test]
+async fn create_from_async_constructor_run_by_factory() {
+    #[Transient(auto_register = false)]
+    async fn Number() -> usize {
+        next()
+    }
+
+    #[Transient(auto_register = false)]
+    async fn Pair(#[di(factory)] numbers: AsyncFactory<usize>) -> (usize, usize) {
+        (numbers.create().await, numbers.create().await)
+    }
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            components![Number, Pair]
+        }
+    }
+
+    COUNT.with(|count| count.set(0));
+
+    let mut cx = Context::create(modules![MyModule]);
+
+    let pairs = cx.async_factory::<(usize, usize)>();
+    assert_eq!(pairs.create().await, (0, 1));
+}
+
+#[test]
+fn construction_cycle() {
+    #[Transient(auto_register = false)]
+    fn Recursive(#[di(factory)] this: Factory<u8>) -> u8 {
+        let err = this.try_create().unwrap_err();
+        assert!(matches!(
+            err.kind(),
+            ResolveErrorKind::CircularDependency(_)
+        ));
+        0
+    }
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            components![Recursive]
+        }
+    }
+
+    let mut cx = Context::create(modules![MyModule]);
+
+    assert_eq!(cx.factory::<u8>().create(), 0);
+}
@@ -0,0 +1,233 @@
+use std::{any::TypeId, num::ParseIntError};
+
+use rudi::{
+    components, modules, providers, try_singleton, try_transient_async, Context, DynProvider,
+    FutureExt, Module, ResolveErrorKind, Singleton, Transient,
+};
+
+#[test]
+fn try_singleton_returns_error() {
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![try_singleton(|_| "forty-two".parse::<i32>())]
+        }
+    }
+
+    let mut cx = Context::create(modules![MyModule]);
+
+    let err = cx.try_resolve::<i32>().unwrap_err();
+
+    match err.kind() {
+        ResolveErrorKind::ConstructorFailed(definition, source) => {
+            assert_eq!(definition.key.ty.id, TypeId::of::<i32>());
+            assert!(source.downcast_ref::<ParseIntError>().is_some());
+        }
+        _ => panic!("unexpected error: {}", err),
+    }
+
+    assert!(std::error::Error::source(&err).is_some());
+    assert!(!cx.contains_single::<i32>());
+}
+
+#[test]
+#[should_panic(expected = "failed to construct the instance for")]
+fn resolve_panics_with_constructor_error() {
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![try_singleton(|_| "forty-two".parse::<i32>())]
+        }
+    }
+
+    let mut cx = Context::create(modules![MyModule]);
+    cx.resolve::<i32>();
+}
+
+#[test]
+fn resolve_error_is_propagated_as_is() {
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![try_singleton(|cx| cx.try_resolve::<u8>().map(i32::from))]
+        }
+    }
+
+    let mut cx = Context::create(modules![MyModule]);
+
+    let err = cx.try_resolve::<i32>().unwrap_err();
+
+    assert!(matches!(err.kind(), ResolveErrorKind::NotFoundProvider(_)));
+    assert_eq!(err.key().ty.id, TypeId::of::<u8>());
+    assert_eq!(err.dependency_chain().len(), 1);
+}
+
+#[test]
+fn macro_fallible_propagates_through_dependents() {
+    #[Singleton(fallible, auto_register = false)]
+    fn Number() -> Result<i32, ParseIntError> {
+        "forty-two".parse()
+    }
+
+    #[Transient(auto_register = false)]
+    struct A(#[allow(dead_code)] i32);
+
+    #[derive(Clone, Debug)]
+    struct B;
+
+    #[Singleton(fallible, auto_register = false)]
+    impl B {
+        #[di]
+        fn new(_: A) -> Result<Self, String> {
+            Ok(B)
+        }
+    }
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            components![Number, A, B]
+        }
+    }
+
+    let mut cx = Context::create(modules![MyModule]);
+
+    let err = cx.try_resolve::<B>().unwrap_err();
+
+    match err.kind() {
+        ResolveErrorKind::ConstructorFailed(definition, _) => {
+            assert_eq!(definition.key.ty.id, TypeId::of::<i32>());
+        }
+        _ => panic!("unexpected error: {}", err),
+    }
+
+    let chain = err
+        .dependency_chain()
+        .iter()
+        .map(|key| key.ty.id)
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        chain,
+        vec![TypeId::of::<B>(), TypeId::of::<A>(), TypeId::of::<i32>()]
+    );
+
+    assert!(cx.dependency_chain().is_empty());
+}
+
+#[test]
+fn macro_fallible_returns_error_of_user() {
+    #[Transient(fallible, auto_register = false)]
+    fn Name() -> Result<String, &'static str> {
+        Err("no name")
+    }
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            components![Name]
+        }
+    }
+
+    let mut cx = Context::create(modules![MyModule]);
+
+    let err = cx.try_resolve::<String>().unwrap_err();
+
+    match err.kind() {
+        ResolveErrorKind::ConstructorFailed(_, source) => {
+            assert_eq!(source.to_string(), "no name");
+        }
+        _ => panic!("unexpected error: {}", err),
+    }
+}
+
+#[test]
+fn try_create_returns_error() {
+    #[Singleton(fallible, eager_create, auto_register = false)]
+    fn Number() -> Result<i32, ParseIntError> {
+        "forty-two".parse()
+    }
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            components![Number]
+        }
+    }
+
+    let err = Context::try_create(modules![MyModule]).err().unwrap();
+    assert!(matches!(
+        err.kind(),
+        ResolveErrorKind::ConstructorFailed(..)
+    ));
+
+    let mut cx = Context::default();
+    cx.load_modules(modules![MyModule]);
+    assert!(cx.try_flush().is_err());
+}
+
+#[test]
+#[should_panic(expected = "failed to construct the instance for")]
+fn create_panics_with_constructor_error() {
+    #[Singleton(fallible, eager_create, auto_register = false)]
+    fn Number() -> Result<i32, ParseIntError> {
+        "forty-two".parse()
+    }
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            components![Number]
+        }
+    }
+
+    Context::create(modules![MyModule]);
+}
+
+#[tokio::test]
+async fn fallible_async() {
+    #[Singleton(fallible, auto_register = false)]
+    async fn Number() -> Result<i32, ParseIntError> {
+        "42".parse()
+    }
+
+    #[Transient(async, auto_register = false)]
+    struct A(#[allow(dead_code)] i32);
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![try_transient_async(|_| async {
+                "forty-two".parse::<i64>()
+            }
+            .boxed())]
+        }
+    }
+
+    struct NumberModule;
+
+    impl Module for NumberModule {
+        fn providers() -> Vec<DynProvider> {
+            components![Number, A]
+        }
+    }
+
+    let mut cx = Context::create_async(modules![MyModule, NumberModule]).await;
+
+    assert_eq!(cx.try_resolve_async::<i32>().await.unwrap(), 42);
+    assert!(cx.try_resolve_async::<A>().await.is_ok());
+
+    let err = cx.try_resolve_async::<i64>().await.unwrap_err();
+    assert!(matches!(
+        err.kind(),
+        ResolveErrorKind::ConstructorFailed(..)
+    ));
+}
@@ -0,0 +1,286 @@
+use std::cell::Cell;
+
+use rudi::{
+    components, modules, providers, single_owner, singleton, transient, Context, DynProvider, Lazy,
+    Module, ResolveErrorKind, Singleton, Transient,
+};
+
+#[test]
+fn break_cycle() {
+    thread_local! {
+        static CREATED: Cell<(u32, u32)> = const { Cell::new((0, 0)) };
+    }
+
+    #[derive(Clone)]
+    #[Singleton(init = Self::created, auto_register = false)]
+    struct A(#[di(lazy)] Lazy<B>);
+
+    impl A {
+        fn created(&mut self, _cx: &mut Context) {
+            CREATED.with(|created| created.set((created.get().0 + 1, created.get().1)));
+        }
+    }
+
+    #[derive(Clone)]
+    #[Singleton(init = Self::created, auto_register = false)]
+    struct B(#[allow(dead_code)] A);
+
+    impl B {
+        fn created(&mut self, _cx: &mut Context) {
+            CREATED.with(|created| created.set((created.get().0, created.get().1 + 1)));
+        }
+    }
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            components![A, B]
+        }
+    }
+
+    let mut cx = Context::create(modules![MyModule]);
+
+    let a = cx.resolve::<A>();
+    assert!(a.0.get().is_none());
+    assert!(!cx.contains_single::<B>());
+
+    a.0.resolve();
+
+    // clones of the singleton share the resolved instance
+    assert!(cx.get_single::<A>().0.get().is_some());
+
+    // the instance created by the handle is moved into the context when it is resolved from it
+    cx.resolve::<B>();
+    assert!(cx.contains_single::<B>());
+
+    assert_eq!(CREATED.with(Cell::get), (1, 1));
+}
+
+#[test]
+fn rarely_used_dependency_is_not_created() {
+    thread_local! {
+        static CREATED: Cell<bool> = const { Cell::new(false) };
+    }
+
+    struct Expensive;
+
+    #[Transient(auto_register = false)]
+    struct Service {
+        #[di(name = "expensive", lazy)]
+        expensive: Lazy<Expensive>,
+    }
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            let mut providers = providers![transient(|_| {
+                CREATED.with(|created| created.set(true));
+                Expensive
+            })
+            .name("expensive")];
+            providers.extend(components![Service]);
+            providers
+        }
+    }
+
+    let mut cx = Context::create(modules![MyModule]);
+
+    let service = cx.resolve::<Service>();
+    assert_eq!(service.expensive.key().name, "expensive");
+    assert!(service.expensive.get().is_none());
+    assert!(!CREATED.with(Cell::get));
+
+    service.expensive.resolve();
+    assert!(service.expensive.get().is_some());
+    assert!(CREATED.with(Cell::get));
+}
+
+#[test]
+fn try_resolve() {
+    let mut cx = Context::default();
+
+    let lazy = cx.lazy::<i32>();
+
+    let err = lazy.try_resolve().unwrap_err();
+    assert!(matches!(err.kind(), ResolveErrorKind::NotFoundProvider(_)));
+    assert!(lazy.get().is_none());
+}
+
+#[tokio::test]
+async fn resolve_async() {
+    #[Singleton(auto_register = false)]
+    async fn Number() -> i32 {
+        42
+    }
+
+    #[Transient(auto_register = false)]
+    async fn Holder(#[di(lazy)] number: Lazy<i32>) -> Lazy<i32> {
+        number
+    }
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            components![Number, Holder]
+        }
+    }
+
+    let mut cx = Context::create(modules![MyModule]);
+
+    let lazy = cx.resolve_async::<Lazy<i32>>().await;
+    assert!(lazy.get().is_none());
+    assert_eq!(lazy.resolve_async().await, &42);
+}
+
+#[test]
+fn qualified() {
+    struct Primary;
+
+    #[Transient(auto_register = false)]
+    struct Service(#[di(qualifier = Primary, lazy)] Lazy<i32>);
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            let mut providers =
+                providers![singleton(|_| 1), singleton(|_| 2).qualifier::<Primary>()];
+            providers.extend(components![Service]);
+            providers
+        }
+    }
+
+    let mut cx = Context::create(modules![MyModule]);
+
+    let service = cx.resolve::<Service>();
+    assert_eq!(service.0.resolve(), &2);
+    assert_eq!(cx.lazy_qualified::<i32, Primary>().resolve(), &2);
+}
+
+#[test]
+fn instance_created_by_handle_is_disposed_by_context() {
+    thread_local! {
+        static DISPOSED: Cell<bool> = const { Cell::new(false) };
+    }
+
+    #[derive(Clone)]
+    struct Pool;
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![
+                singleton(|_| Pool).on_dispose(|_| DISPOSED.with(|disposed| disposed.set(true)))
+            ]
+        }
+    }
+
+    let mut cx = Context::create(modules![MyModule]);
+
+    cx.lazy::<Pool>().resolve();
+    assert!(!cx.contains_single::<Pool>());
+
+    cx.shutdown();
+    assert!(DISPOSED.with(Cell::get));
+
+    // the instance is created again after shutdown
+    DISPOSED.with(|disposed| disposed.set(false));
+    cx.lazy::<Pool>().resolve();
+    cx.resolve::<Pool>();
+    cx.shutdown();
+    assert!(DISPOSED.with(Cell::get));
+}
+
+#[test]
+fn resolve_from_constructor_run_by_handle() {
+    thread_local! {
+        static CREATED: Cell<u32> = const { Cell::new(0) };
+    }
+
+    #[Singleton(auto_register = false)]
+    fn Number() -> i32 {
+        CREATED.with(|created| created.set(created.get() + 1));
+        42
+    }
+
+    #[Singleton(auto_register = false)]
+    fn Text(#[di(lazy)] number: Lazy<i32>) -> String {
+        number.resolve().to_string()
+    }
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            components![Number, Text]
+        }
+    }
+
+    let mut cx = Context::create(modules![MyModule]);
+
+    assert_eq!(cx.lazy::<String>().resolve(), "42");
+    assert_eq!(cx.lazy::<i32>().resolve(), &42);
+
+    assert_eq!(cx.resolve::<i32>(), 42);
+    assert!(cx.contains_single::<String>());
+    assert_eq!(CREATED.with(Cell::get), 1);
+}
+
+#[test]
+fn construction_cycle_through_handles() {
+    #[Transient(auto_register = false)]
+    fn A(#[di(lazy)] b: Lazy<u32>) -> i32 {
+        b.try_resolve().map_or(-1, |b| *b as i32)
+    }
+
+    #[Transient(auto_register = false)]
+    fn B(#[di(lazy)] a: Lazy<i32>) -> u32 {
+        let err = a.try_resolve().unwrap_err();
+        assert!(
+            matches!(err.kind(), ResolveErrorKind::CircularDependency(key) if key.ty.name == "i32")
+        );
+        0
+    }
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            components![A, B]
+        }
+    }
+
+    let mut cx = Context::create(modules![MyModule]);
+
+    assert_eq!(cx.lazy::<i32>().resolve(), &0);
+}
+
+#[test]
+fn single_owner_dependency() {
+    struct NotClone(i32);
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![
+                single_owner(|_| NotClone(42)),
+                transient(|cx| {
+                    cx.just_create_single::<NotClone>();
+                    cx.get_single::<NotClone>().0
+                })
+            ]
+        }
+    }
+
+    let mut cx = Context::create(modules![MyModule]);
+
+    assert_eq!(cx.lazy::<i32>().resolve(), &42);
+
+    cx.resolve::<i32>();
+    assert_eq!(cx.get_single::<NotClone>().0, 42);
+}
@@ -0,0 +1,170 @@
+use std::{any, borrow::Cow, collections::HashMap};
+
+use rudi::{
+    components, modules, providers, singleton, transient, transient_async, Context, DependencyKind,
+    DynProvider, FutureExt, Module, Shared, Singleton, Transient,
+};
+
+#[test]
+fn resolve_map_by_type() {
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![
+                transient(|_| 1u8).name("one"),
+                singleton(|_| 2u8).name("two"),
+                transient(|_| 0u16),
+            ]
+        }
+    }
+
+    let mut cx = Context::create(modules![MyModule]);
+
+    let map = cx.resolve_map_by_type::<u8>();
+    assert_eq!(map.len(), 2);
+    assert_eq!(map["one"], 1);
+    assert_eq!(map["two"], 2);
+
+    assert_eq!(
+        cx.get_singles_map_by_type::<u8>(),
+        HashMap::from([("two".into(), &2)])
+    );
+    assert!(cx.resolve_map_by_type::<u32>().is_empty());
+}
+
+#[tokio::test]
+async fn qualified_providers() {
+    struct Primary;
+    struct Replica;
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![
+                singleton(|_| 1).qualifier::<Primary>(),
+                transient(|_| 2).qualifier::<Replica>(),
+            ]
+        }
+    }
+
+    let mut cx = Context::create(modules![MyModule]);
+
+    assert_eq!(cx.resolve_by_type::<i32>(), [1, 2]);
+
+    let expected = HashMap::from([
+        (Cow::Borrowed(any::type_name::<Primary>()), 1),
+        (Cow::Borrowed(any::type_name::<Replica>()), 2),
+    ]);
+    assert_eq!(cx.resolve_map_by_type::<i32>(), expected);
+    assert_eq!(cx.resolve_map_by_type_async::<i32>().await, expected);
+    assert_eq!(
+        cx.get_singles_map_by_type::<i32>(),
+        HashMap::from([(any::type_name::<Primary>().into(), &1)])
+    );
+}
+
+#[test]
+fn child_keeps_names_of_ancestors() {
+    struct ParentModule;
+
+    impl Module for ParentModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![transient(|_| 1u8).name("a"), transient(|_| 2u8).name("b")]
+        }
+    }
+
+    struct ChildModule;
+
+    impl Module for ChildModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![transient(|_| 3u8).name("b")]
+        }
+    }
+
+    let parent = Context::create(modules![ParentModule]);
+    let mut child = Shared::new(parent).child(modules![ChildModule]);
+
+    let map = child.resolve_map_by_type::<u8>();
+    assert_eq!(map.len(), 2);
+    assert_eq!(map["a"], 1);
+    assert_eq!(map["b"], 3);
+}
+
+#[test]
+fn map_attribute() {
+    #[Singleton(name = "json", auto_register = false)]
+    fn Json() -> String {
+        "application/json".to_string()
+    }
+
+    #[Singleton(name = "text", auto_register = false)]
+    fn Text() -> String {
+        "text/plain".to_string()
+    }
+
+    #[Transient(auto_register = false)]
+    struct Plugins(#[di(map)] HashMap<Cow<'static, str>, String>);
+
+    #[Transient(auto_register = false)]
+    fn Count(#[di(map, ref)] plugins: HashMap<Cow<'static, str>, &String>) -> usize {
+        plugins.len()
+    }
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            components![Json, Text, Plugins, Count]
+        }
+    }
+
+    let mut cx = Context::create(modules![MyModule]);
+
+    let Plugins(plugins) = cx.resolve::<Plugins>();
+    assert_eq!(plugins["json"], "application/json");
+    assert_eq!(plugins["text"], "text/plain");
+
+    assert_eq!(cx.resolve::<usize>(), 2);
+
+    let kinds = cx
+        .get_provider::<Plugins>()
+        .unwrap()
+        .dependencies()
+        .iter()
+        .map(|dependency| dependency.kind)
+        .collect::<Vec<_>>();
+    assert_eq!(kinds, [DependencyKind::Map]);
+
+    assert!(cx.validate().is_ok());
+}
+
+#[tokio::test]
+async fn map_attribute_async() {
+    #[Transient(auto_register = false)]
+    async fn Total(#[di(map)] numbers: HashMap<Cow<'static, str>, i32>) -> i64 {
+        numbers.values().map(|n| i64::from(*n)).sum()
+    }
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            let mut providers = providers![
+                transient_async(|_| async { 1 }.boxed()).name("a"),
+                transient(|_| 2).name("b"),
+            ];
+            providers.extend(components![Total]);
+            providers
+        }
+    }
+
+    let mut cx = Context::create(modules![MyModule]);
+
+    let map = cx.resolve_map_by_type_async::<i32>().await;
+    assert_eq!(map["a"], 1);
+    assert_eq!(map["b"], 2);
+
+    assert_eq!(cx.resolve_async::<i64>().await, 3);
+}
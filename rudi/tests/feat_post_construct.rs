@@ -0,0 +1,174 @@
+use std::sync::{Arc, Mutex};
+
+use rudi::{
+    modules, providers, singleton, singleton_async, transient, Context, DynProvider, FutureExt,
+    Module, ResolveErrorKind, Singleton, Transient,
+};
+
+#[derive(Clone, Default)]
+struct Registry(Arc<Mutex<Vec<&'static str>>>);
+
+#[test]
+fn post_construct_runs_before_caching() {
+    #[derive(Clone)]
+    struct Cache(Vec<i32>);
+
+    struct Handler;
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![
+                singleton(|_| Registry::default()),
+                singleton(|_| Cache(Vec::new())).post_construct(|cache, _| cache.0.push(1)),
+                transient(|_| Handler).post_construct(|_, cx| {
+                    cx.resolve::<Registry>().0.lock().unwrap().push("handler")
+                }),
+            ]
+        }
+    }
+
+    let mut cx = Context::create(modules![MyModule]);
+
+    assert_eq!(cx.resolve::<Cache>().0, [1]);
+    assert_eq!(cx.get_single::<Cache>().0, [1]);
+    assert_eq!(cx.resolve::<Cache>().0, [1]);
+
+    cx.resolve::<Handler>();
+    cx.resolve::<Handler>();
+
+    assert_eq!(
+        *cx.get_single::<Registry>().0.lock().unwrap(),
+        ["handler", "handler"]
+    );
+}
+
+#[test]
+fn post_construct_captures_state() {
+    struct Handler;
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            let registry = Registry::default();
+
+            providers![
+                singleton({
+                    let registry = registry.clone();
+                    move |_| registry.clone()
+                }),
+                transient(|_| Handler).post_construct(move |_, _| registry
+                    .0
+                    .lock()
+                    .unwrap()
+                    .push("handler")),
+            ]
+        }
+    }
+
+    let mut cx = Context::create(modules![MyModule]);
+
+    cx.resolve::<Handler>();
+    cx.resolve::<Handler>();
+
+    assert_eq!(
+        *cx.resolve::<Registry>().0.lock().unwrap(),
+        ["handler", "handler"]
+    );
+}
+
+#[tokio::test]
+async fn post_construct_async() {
+    #[derive(Clone)]
+    struct Cache(Vec<i32>);
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![
+                transient(|_| 2),
+                singleton_async(|_| async { Cache(Vec::new()) }.boxed()).post_construct_async(
+                    |cache, cx| {
+                        async {
+                            let n = cx.resolve_async().await;
+                            cache.0.push(n);
+                        }
+                        .boxed()
+                    }
+                ),
+            ]
+        }
+    }
+
+    let mut cx = Context::create(modules![MyModule]);
+
+    assert_eq!(cx.resolve_async::<Cache>().await.0, [2]);
+}
+
+#[test]
+fn post_construct_circular_dependency() {
+    #[derive(Clone)]
+    struct A;
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![singleton(|_| A).post_construct(|_, cx| {
+                let err = cx.try_resolve::<A>().err().unwrap();
+                assert!(matches!(
+                    err.kind(),
+                    ResolveErrorKind::CircularDependency(_)
+                ));
+            })]
+        }
+    }
+
+    let mut cx = Context::create(modules![MyModule]);
+
+    cx.resolve::<A>();
+}
+
+#[tokio::test]
+async fn post_construct_with_macro() {
+    #[derive(Clone)]
+    #[Singleton(auto_register = false)]
+    struct Counter(#[di(default)] Registry);
+
+    #[derive(Clone)]
+    #[Singleton(init = Self::start, auto_register = false)]
+    struct A(Counter);
+
+    impl A {
+        fn start(&mut self, _cx: &mut Context) {
+            self.0 .0 .0.lock().unwrap().push("a");
+        }
+    }
+
+    #[Transient(async, init = Self::start, auto_register = false)]
+    struct B(Counter);
+
+    impl B {
+        async fn start(&mut self, cx: &mut Context) {
+            cx.resolve_async::<A>().await;
+            self.0 .0 .0.lock().unwrap().push("b");
+        }
+    }
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            rudi::components![Counter, A, B]
+        }
+    }
+
+    let mut cx = Context::create(modules![MyModule]);
+
+    cx.resolve_async::<B>().await;
+
+    assert_eq!(*cx.get_single::<Counter>().0 .0.lock().unwrap(), ["a", "b"]);
+}
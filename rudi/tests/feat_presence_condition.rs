@@ -0,0 +1,165 @@
+use rudi::{
+    components, modules, providers, singleton, transient, Context, DynProvider, Module,
+    PresenceCondition, Shared, Singleton, Transient,
+};
+
+trait Cache: Send + Sync {
+    fn name(&self) -> &'static str;
+}
+
+#[derive(Clone)]
+struct MemoryCache;
+
+impl Cache for MemoryCache {
+    fn name(&self) -> &'static str {
+        "memory"
+    }
+}
+
+#[derive(Clone)]
+struct RedisCache;
+
+impl Cache for RedisCache {
+    fn name(&self) -> &'static str {
+        "redis"
+    }
+}
+
+fn into_cache<T: Cache + 'static>(cache: T) -> Shared<dyn Cache> {
+    Shared::new(cache)
+}
+
+struct DefaultModule;
+
+impl Module for DefaultModule {
+    fn providers() -> Vec<DynProvider> {
+        providers![singleton(|_| MemoryCache)
+            .on_missing::<Shared<dyn Cache>>()
+            .bind(into_cache)]
+    }
+}
+
+struct RedisModule;
+
+impl Module for RedisModule {
+    fn providers() -> Vec<DynProvider> {
+        providers![singleton(|_| RedisCache).bind(into_cache)]
+    }
+}
+
+#[test]
+fn default_is_registered_when_missing() {
+    let mut cx = Context::create(modules![DefaultModule]);
+
+    assert_eq!(cx.resolve::<Shared<dyn Cache>>().name(), "memory");
+    assert!(cx.contains_provider::<MemoryCache>());
+
+    let definition = cx.get_provider::<Shared<dyn Cache>>().unwrap().definition();
+    assert!(definition.conditional);
+    assert_eq!(definition.presence.len(), 1);
+    assert!(matches!(
+        definition.presence[0],
+        PresenceCondition::Missing(ty) if ty.name.contains("Cache")
+    ));
+}
+
+#[test]
+fn default_is_skipped_regardless_of_module_order() {
+    for modules in [
+        modules![DefaultModule, RedisModule],
+        modules![RedisModule, DefaultModule],
+    ] {
+        let mut cx = Context::create(modules);
+
+        assert_eq!(cx.resolve::<Shared<dyn Cache>>().name(), "redis");
+        assert!(!cx.contains_provider::<MemoryCache>());
+    }
+}
+
+#[test]
+fn providers_inserted_by_conditions_are_taken_into_account() {
+    struct ConditionModule;
+
+    impl Module for ConditionModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![singleton(|_| RedisCache)
+                .condition(Some(|_| true))
+                .bind(into_cache)]
+        }
+    }
+
+    let mut cx = Context::create(modules![DefaultModule, ConditionModule]);
+
+    assert_eq!(cx.resolve::<Shared<dyn Cache>>().name(), "redis");
+}
+
+#[test]
+fn first_default_wins() {
+    struct OtherDefaultModule;
+
+    impl Module for OtherDefaultModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![singleton(|_| RedisCache)
+                .on_missing::<Shared<dyn Cache>>()
+                .bind(into_cache)]
+        }
+    }
+
+    let mut cx = Context::create(modules![DefaultModule, OtherDefaultModule]);
+
+    assert_eq!(cx.resolve::<Shared<dyn Cache>>().name(), "memory");
+    assert!(!cx.contains_provider::<RedisCache>());
+}
+
+#[test]
+fn on_present() {
+    struct MetricsModule;
+
+    impl Module for MetricsModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![transient(|_| "cache metrics").on_present::<Shared<dyn Cache>>()]
+        }
+    }
+
+    let cx = Context::create(modules![MetricsModule]);
+    assert!(!cx.contains_provider::<&str>());
+
+    let cx = Context::create(modules![MetricsModule, RedisModule]);
+    assert!(cx.contains_provider::<&str>());
+}
+
+#[test]
+fn child_sees_providers_of_parent() {
+    let parent = Context::create(modules![RedisModule]);
+    let mut child = Shared::new(parent).child(modules![DefaultModule]);
+
+    assert!(!child.contains_provider::<MemoryCache>());
+    assert_eq!(child.resolve::<Shared<dyn Cache>>().name(), "redis");
+}
+
+#[test]
+fn presence_attributes() {
+    #[Singleton(on_missing = Shared<dyn Cache>, binds = [into_cache], auto_register = false)]
+    fn DefaultCache() -> MemoryCache {
+        MemoryCache
+    }
+
+    #[Transient(on_present = Shared<dyn Cache>, auto_register = false)]
+    fn CacheName(#[di(ref)] cache: &Shared<dyn Cache>) -> &'static str {
+        cache.name()
+    }
+
+    struct AttributeModule;
+
+    impl Module for AttributeModule {
+        fn providers() -> Vec<DynProvider> {
+            components![DefaultCache, CacheName]
+        }
+    }
+
+    let mut cx = Context::create(modules![AttributeModule]);
+    assert_eq!(cx.resolve::<&'static str>(), "memory");
+
+    let mut cx = Context::create(modules![AttributeModule, RedisModule]);
+    assert_eq!(cx.resolve::<&'static str>(), "redis");
+}
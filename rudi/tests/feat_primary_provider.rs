@@ -0,0 +1,269 @@
+use rudi::{
+    components, modules, providers, singleton, transient, Context, Dependency, DependencyKind,
+    DynProvider, MaybeSendSync, Module, ResolveErrorKind, Shared, Singleton, Transient,
+    ValidationErrorKind,
+};
+
+#[test]
+fn unnamed_resolve_falls_back_to_primary() {
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![
+                transient(|_| 1).name("a"),
+                transient(|_| 2).name("b").primary(true),
+                transient(|_| 3).name("c"),
+            ]
+        }
+    }
+
+    let mut cx = Context::create(modules![MyModule]);
+
+    assert_eq!(cx.resolve::<i32>(), 2);
+    assert_eq!(cx.resolve_with_name::<i32>("a"), 1);
+    assert!(cx.contains_provider::<i32>());
+    assert_eq!(cx.get_provider::<i32>().unwrap().definition().key.name, "b");
+    assert_eq!(cx.factory::<i32>().create(), 2);
+}
+
+#[test]
+fn default_name_takes_precedence() {
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![transient(|_| 1).name("a").primary(true), transient(|_| 0)]
+        }
+    }
+
+    let mut cx = Context::create(modules![MyModule]);
+
+    assert_eq!(cx.resolve::<i32>(), 0);
+}
+
+#[test]
+fn primary_single() {
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![
+                singleton(|_| 1u8).name("a"),
+                singleton(|_| 2u8).name("b").primary(true),
+            ]
+        }
+    }
+
+    let mut cx = Context::create(modules![MyModule]);
+
+    assert!(!cx.contains_single::<u8>());
+    cx.just_create_single::<u8>();
+    assert!(cx.contains_single::<u8>());
+    assert_eq!(cx.get_single::<u8>(), &2);
+    assert_eq!(cx.get_single_option_with_name::<u8>("a"), None);
+}
+
+#[test]
+fn child_falls_back_to_primary_of_parent() {
+    struct ParentModule;
+
+    impl Module for ParentModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![transient(|_| 1).name("a").primary(true)]
+        }
+    }
+
+    struct ChildModule;
+
+    impl Module for ChildModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![transient(|_| 2).name("b")]
+        }
+    }
+
+    let parent = Context::create(modules![ParentModule]);
+    let mut child = Shared::new(parent).child(modules![ChildModule]);
+
+    assert_eq!(child.resolve::<i32>(), 1);
+}
+
+#[test]
+#[should_panic(expected = "multiple primary providers of the same type registered")]
+fn two_primaries_panic() {
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![
+                transient(|_| 1).name("a").primary(true),
+                transient(|_| 2).name("b").primary(true),
+            ]
+        }
+    }
+
+    Context::create(modules![MyModule]);
+}
+
+#[test]
+fn two_primaries_error() {
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![
+                transient(|_| 1).name("a").primary(true),
+                transient(|_| 2).name("b").primary(true),
+                transient(|_| 3).name("c"),
+            ]
+        }
+    }
+
+    let mut cx = Context::default();
+
+    let err = cx.try_load_modules(modules![MyModule]).unwrap_err();
+    assert!(matches!(
+        err.kind(),
+        ResolveErrorKind::MultiplePrimaryProviders(definitions) if definitions.len() == 2
+    ));
+}
+
+#[test]
+fn bound_providers_are_not_primary() {
+    trait Greet: MaybeSendSync {
+        fn greet(&self) -> &'static str;
+    }
+
+    struct Hello;
+
+    impl Greet for Hello {
+        fn greet(&self) -> &'static str {
+            "hello"
+        }
+    }
+
+    struct Hi;
+
+    impl Greet for Hi {
+        fn greet(&self) -> &'static str {
+            "hi"
+        }
+    }
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![
+                transient(|_| Shared::new(Hello))
+                    .name("hello")
+                    .primary(true)
+                    .bind(|hello| -> Shared<dyn Greet> { hello }),
+                transient(|_| Shared::new(Hi))
+                    .name("hi")
+                    .primary(true)
+                    .bind(|hi| -> Shared<dyn Greet> { hi }),
+            ]
+        }
+    }
+
+    let mut cx = Context::try_create(modules![MyModule]).unwrap();
+
+    assert_eq!(cx.resolve::<Shared<Hello>>().greet(), "hello");
+    assert_eq!(cx.resolve::<Shared<Hi>>().greet(), "hi");
+    assert!(cx.resolve_option::<Shared<dyn Greet>>().is_none());
+    assert_eq!(
+        cx.resolve_with_name::<Shared<dyn Greet>>("hi").greet(),
+        "hi"
+    );
+}
+
+#[test]
+fn primary_attribute() {
+    #[Singleton(name = "postgres", primary, auto_register = false)]
+    fn Postgres() -> String {
+        "postgres".to_string()
+    }
+
+    #[Singleton(name = "sqlite", auto_register = false)]
+    fn Sqlite() -> String {
+        "sqlite".to_string()
+    }
+
+    #[Transient(auto_register = false)]
+    fn Url(#[di(ref)] database: &String) -> &'static str {
+        if database == "postgres" {
+            "postgres://localhost"
+        } else {
+            "sqlite::memory:"
+        }
+    }
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            components![Postgres, Sqlite, Url]
+        }
+    }
+
+    let mut cx = Context::create(modules![MyModule]);
+
+    assert!(cx.validate().is_ok());
+    assert_eq!(cx.resolve::<&'static str>(), "postgres://localhost");
+}
+
+#[test]
+fn validate_reports_ambiguous_without_primary() {
+    #[Transient(auto_register = false)]
+    fn Port(_host: String) -> u16 {
+        80
+    }
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            let mut providers = providers![
+                transient(|_| "a".to_string()).name("a"),
+                transient(|_| "b".to_string()).name("b"),
+            ];
+            providers.extend(components![Port]);
+            providers
+        }
+    }
+
+    let cx = Context::create(modules![MyModule]);
+
+    let errors = cx.validate().unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        errors[0].kind(),
+        ValidationErrorKind::Ambiguous(candidates) if candidates.len() == 2
+    ));
+}
+
+#[test]
+fn cycle_through_primary() {
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![
+                transient(|cx| cx.resolve::<u16>() as u8)
+                    .name("a")
+                    .primary(true)
+                    .dependencies(vec![Dependency::new::<u16>("", DependencyKind::Required)]),
+                transient(|cx| u16::from(cx.resolve::<u8>()))
+                    .dependencies(vec![Dependency::new::<u8>("", DependencyKind::Required)]),
+            ]
+        }
+    }
+
+    let err = Context::try_create(modules![MyModule]).err().unwrap();
+
+    assert!(matches!(
+        err.kind(),
+        ResolveErrorKind::CircularDependencies(cycles) if cycles.len() == 1
+    ));
+}
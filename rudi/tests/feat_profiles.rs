@@ -0,0 +1,119 @@
+use rudi::{
+    components, modules, providers, singleton, transient, Context, DynProvider, Module, Shared,
+    Transient,
+};
+
+struct MyModule;
+
+impl Module for MyModule {
+    fn providers() -> Vec<DynProvider> {
+        providers![
+            transient(|_| "memory").profiles(["test"]),
+            transient(|_| "file").profiles(["dev", "prod"]),
+            transient(|_| 42),
+        ]
+    }
+}
+
+#[test]
+fn inactive_providers_are_filtered_out() {
+    let mut cx = Context::options()
+        .profiles(["test"])
+        .create(modules![MyModule]);
+
+    assert_eq!(cx.profiles(), ["test"]);
+    assert_eq!(cx.resolve::<&str>(), "memory");
+    assert_eq!(cx.resolve::<i32>(), 42);
+
+    let mut cx = Context::options()
+        .profiles(["prod"])
+        .create(modules![MyModule]);
+
+    assert_eq!(cx.resolve::<&str>(), "file");
+}
+
+#[test]
+fn no_active_profile() {
+    let mut cx = Context::create(modules![MyModule]);
+
+    assert!(cx.profiles().is_empty());
+    assert!(!cx.contains_provider::<&str>());
+    assert_eq!(cx.resolve::<i32>(), 42);
+}
+
+#[test]
+fn bound_providers_share_profiles() {
+    struct BindModule;
+
+    impl Module for BindModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![singleton(|_| 1).profiles(["test"]).bind(Shared::new)]
+        }
+    }
+
+    let cx = Context::options()
+        .profiles(["dev"])
+        .create(modules![BindModule]);
+
+    assert!(!cx.contains_provider::<i32>());
+    assert!(!cx.contains_provider::<Shared<i32>>());
+}
+
+#[test]
+fn condition_queries_profiles() {
+    struct ConditionModule;
+
+    impl Module for ConditionModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![
+                transient(|_| 1).condition(Some(|cx| cx.is_profile_active("test"))),
+                transient(|_| 2).condition(Some(|cx| !cx.is_profile_active("test"))),
+            ]
+        }
+    }
+
+    let mut cx = Context::options()
+        .profiles(["test"])
+        .create(modules![ConditionModule]);
+
+    assert_eq!(cx.resolve::<i32>(), 1);
+}
+
+#[test]
+fn child_inherits_profiles() {
+    let parent = Context::options().profiles(["dev"]).create(modules![]);
+    let mut child = Shared::new(parent).child(modules![MyModule]);
+
+    assert!(child.is_profile_active("dev"));
+    assert_eq!(child.resolve::<&str>(), "file");
+}
+
+#[test]
+fn profile_attribute() {
+    #[Transient(profile = "test", auto_register = false)]
+    fn Memory() -> String {
+        "sqlite::memory:".to_string()
+    }
+
+    #[Transient(profile = ["dev", "prod"], auto_register = false)]
+    fn File() -> String {
+        "sqlite://data.db".to_string()
+    }
+
+    struct AttributeModule;
+
+    impl Module for AttributeModule {
+        fn providers() -> Vec<DynProvider> {
+            components![Memory, File]
+        }
+    }
+
+    let mut cx = Context::options()
+        .profiles(["dev"])
+        .create(modules![AttributeModule]);
+
+    assert_eq!(cx.resolve::<String>(), "sqlite://data.db");
+
+    let definition = cx.get_provider::<String>().unwrap().definition();
+    assert_eq!(definition.profiles, ["dev", "prod"]);
+}
@@ -0,0 +1,158 @@
+use rudi::{
+    components, modules, providers, singleton, transient, Context, DynProvider, Module, Shared,
+    Transient,
+};
+
+#[test]
+fn registration_order_by_default() {
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            (0..32)
+                .map(|n| transient(move |_| n).name(n.to_string()).into())
+                .collect()
+        }
+    }
+
+    let mut cx = Context::create(modules![MyModule]);
+
+    assert_eq!(cx.resolve_by_type::<i32>(), (0..32).collect::<Vec<_>>());
+}
+
+#[test]
+fn sorted_by_order_then_registration_order() {
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![
+                singleton(|_| "c").name("c").order(10),
+                singleton(|_| "a").name("a"),
+                singleton(|_| "d").name("d").order(10),
+                singleton(|_| "first").name("first").order(-1),
+                singleton(|_| "b").name("b"),
+            ]
+        }
+    }
+
+    let mut cx = Context::create(modules![MyModule]);
+
+    assert_eq!(cx.resolve_by_type::<&str>(), ["first", "a", "b", "c", "d"]);
+    assert_eq!(
+        cx.get_singles_by_type::<&str>(),
+        [&"first", &"a", &"b", &"c", &"d"]
+    );
+
+    let orders = cx
+        .get_providers_by_type::<&str>()
+        .into_iter()
+        .map(|provider| provider.definition().order)
+        .collect::<Vec<_>>();
+    assert_eq!(orders, [-1, 0, 0, 10, 10]);
+}
+
+#[test]
+fn bound_providers_keep_order() {
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![
+                transient(|_| 2).name("two").order(2).bind(Shared::new),
+                transient(|_| 1).name("one").order(1).bind(Shared::new),
+            ]
+        }
+    }
+
+    let mut cx = Context::create(modules![MyModule]);
+
+    assert_eq!(
+        cx.resolve_by_type::<Shared<i32>>(),
+        [Shared::new(1), Shared::new(2)]
+    );
+}
+
+#[test]
+fn child_shadows_in_place() {
+    struct ParentModule;
+
+    impl Module for ParentModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![
+                transient(|_| 1u8).name("a"),
+                transient(|_| 2u8).name("b"),
+                transient(|_| 3u8).name("c"),
+            ]
+        }
+    }
+
+    struct ChildModule;
+
+    impl Module for ChildModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![
+                transient(|_| 0u8).name("first").order(-1),
+                transient(|_| 20u8).name("b"),
+            ]
+        }
+    }
+
+    let parent = Context::create(modules![ParentModule]);
+    let mut child = Shared::new(parent).child(modules![ChildModule]);
+
+    assert_eq!(child.resolve_by_type::<u8>(), [0, 1, 20, 3]);
+}
+
+#[test]
+fn order_attribute() {
+    trait Middleware {
+        fn name(&self) -> &'static str;
+    }
+
+    struct Log;
+
+    impl Middleware for Log {
+        fn name(&self) -> &'static str {
+            "log"
+        }
+    }
+
+    struct Auth;
+
+    impl Middleware for Auth {
+        fn name(&self) -> &'static str {
+            "auth"
+        }
+    }
+
+    #[Transient(name = "log", order = 20, auto_register = false)]
+    fn NewLog() -> Shared<dyn Middleware> {
+        Shared::new(Log)
+    }
+
+    #[Transient(name = "auth", order = 10, auto_register = false)]
+    fn NewAuth() -> Shared<dyn Middleware> {
+        Shared::new(Auth)
+    }
+
+    #[Transient(auto_register = false)]
+    fn Chain(#[di(vec)] middlewares: Vec<Shared<dyn Middleware>>) -> Vec<&'static str> {
+        middlewares
+            .iter()
+            .map(|middleware| middleware.name())
+            .collect()
+    }
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            components![NewLog, NewAuth, Chain]
+        }
+    }
+
+    let mut cx = Context::create(modules![MyModule]);
+
+    assert_eq!(cx.resolve::<Vec<&'static str>>(), ["auth", "log"]);
+}
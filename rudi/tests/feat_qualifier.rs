@@ -0,0 +1,237 @@
+use std::any;
+
+use rudi::{
+    components, modules, providers, singleton, singleton_async, transient, Context, Dependency,
+    DependencyKind, DynProvider, FutureExt, Module, ResolveErrorKind, Shared, Singleton, Transient,
+    ValidationErrorKind,
+};
+
+struct Primary;
+
+struct Replica;
+
+#[test]
+fn resolve_qualified() {
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![
+                transient(|_| 1).qualifier::<Primary>(),
+                transient(|_| 2).qualifier::<Replica>(),
+                transient(|_| 3),
+            ]
+        }
+    }
+
+    let mut cx = Context::create(modules![MyModule]);
+
+    assert_eq!(cx.resolve_qualified::<i32, Primary>(), 1);
+    assert_eq!(cx.resolve_qualified::<i32, Replica>(), 2);
+    assert_eq!(cx.resolve::<i32>(), 3);
+
+    assert_eq!(cx.resolve_option_qualified::<u8, Primary>(), None);
+
+    let err = cx.try_resolve_qualified::<u8, Primary>().err().unwrap();
+    assert!(matches!(
+        err.kind(),
+        ResolveErrorKind::NotFoundProvider(key) if key.qualifier.unwrap().name.ends_with("Primary")
+    ));
+}
+
+#[test]
+fn qualifier_does_not_fall_back() {
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![
+                transient(|_| 1).qualifier::<Primary>(),
+                transient(|_| 2).name("replica").primary(true),
+            ]
+        }
+    }
+
+    let mut cx = Context::create(modules![MyModule]);
+
+    assert_eq!(cx.resolve::<i32>(), 2);
+    assert_eq!(cx.resolve_option_qualified::<i32, Replica>(), None);
+}
+
+#[test]
+fn collections() {
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![
+                singleton(|_| 1).qualifier::<Primary>(),
+                singleton(|_| 2).name("two"),
+            ]
+        }
+    }
+
+    let mut cx = Context::create(modules![MyModule]);
+
+    assert_eq!(cx.resolve_by_type::<i32>(), [1, 2]);
+    assert_eq!(cx.get_providers_by_type::<i32>().len(), 2);
+    assert_eq!(cx.get_singles_by_type::<i32>(), [&1, &2]);
+    assert_eq!(cx.get_single_qualified::<i32, Primary>(), &1);
+
+    let map = cx.resolve_map_by_type::<i32>();
+    assert_eq!(map.len(), 2);
+    assert_eq!(map[any::type_name::<Primary>()], 1);
+    assert_eq!(map["two"], 2);
+}
+
+#[test]
+fn eager_create_and_bind() {
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn eager_create() -> bool {
+            true
+        }
+
+        fn providers() -> Vec<DynProvider> {
+            providers![singleton(|_| 1).qualifier::<Primary>().bind(Shared::new)]
+        }
+    }
+
+    let mut cx = Context::create(modules![MyModule]);
+
+    assert_eq!(cx.get_single_option_qualified::<i32, Primary>(), Some(&1));
+    assert_eq!(
+        cx.get_single_option_qualified::<Shared<i32>, Primary>(),
+        Some(&Shared::new(1))
+    );
+    assert_eq!(cx.get_single_option::<Shared<i32>>(), None);
+    assert_eq!(
+        cx.resolve_qualified::<Shared<i32>, Primary>(),
+        Shared::new(1)
+    );
+}
+
+#[test]
+fn qualifier_attribute() {
+    #[Singleton(qualifier = Primary, auto_register = false)]
+    fn PrimaryUrl() -> String {
+        "postgres://primary".to_string()
+    }
+
+    #[Singleton(qualifier = Replica, auto_register = false)]
+    fn ReplicaUrl() -> String {
+        "postgres://replica".to_string()
+    }
+
+    #[Transient(auto_register = false)]
+    struct Database {
+        #[di(qualifier = Primary)]
+        primary: String,
+        #[di(qualifier = Replica, option)]
+        replica: Option<String>,
+        #[di(qualifier = Primary, default = 5432)]
+        port: u16,
+    }
+
+    #[Transient(auto_register = false)]
+    fn Urls(
+        #[di(qualifier = Primary, ref = String)] primary: &str,
+        #[di(qualifier = Replica, ref, option)] replica: Option<&String>,
+    ) -> Vec<String> {
+        let mut urls = vec![primary.to_string()];
+        urls.extend(replica.cloned());
+        urls
+    }
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            components![PrimaryUrl, ReplicaUrl, Database, Urls]
+        }
+    }
+
+    let mut cx = Context::create(modules![MyModule]);
+
+    assert!(cx.validate().is_ok());
+
+    let Database {
+        primary,
+        replica,
+        port,
+    } = cx.resolve();
+    assert_eq!(primary, "postgres://primary");
+    assert_eq!(replica.as_deref(), Some("postgres://replica"));
+    assert_eq!(port, 5432);
+
+    assert_eq!(
+        cx.resolve::<Vec<String>>(),
+        ["postgres://primary", "postgres://replica"]
+    );
+
+    let dependencies = cx.get_provider::<Database>().unwrap().dependencies();
+    assert_eq!(
+        dependencies[0],
+        Dependency::new::<String>("", DependencyKind::Required).qualifier::<Primary>()
+    );
+}
+
+#[test]
+fn validate_reports_missing_qualifier() {
+    #[Transient(auto_register = false)]
+    fn Port(#[di(qualifier = Replica)] _url: String) -> u16 {
+        5432
+    }
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            let mut providers = providers![
+                transient(|_| "a".to_string()).qualifier::<Primary>(),
+                transient(|_| "b".to_string()).name("b"),
+            ];
+            providers.extend(components![Port]);
+            providers
+        }
+    }
+
+    let cx = Context::create(modules![MyModule]);
+
+    let errors = cx.validate().unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert!(matches!(
+        errors[0].kind(),
+        ValidationErrorKind::NotFoundProvider
+    ));
+}
+
+#[tokio::test]
+async fn qualifier_attribute_async() {
+    #[Transient(auto_register = false)]
+    async fn Sum(
+        #[di(qualifier = Primary)] primary: i32,
+        #[di(qualifier = Replica, ref)] replica: &i32,
+    ) -> i64 {
+        i64::from(primary + replica)
+    }
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            let mut providers = providers![
+                singleton_async(|_| async { 1 }.boxed()).qualifier::<Primary>(),
+                singleton(|_| 2).qualifier::<Replica>(),
+            ];
+            providers.extend(components![Sum]);
+            providers
+        }
+    }
+
+    let mut cx = Context::create(modules![MyModule]);
+
+    assert_eq!(cx.resolve_qualified_async::<i32, Primary>().await, 1);
+    assert_eq!(cx.resolve_async::<i64>().await, 3);
+}
@@ -0,0 +1,159 @@
+use std::{thread, time::Duration};
+
+use rudi::{
+    modules, providers, singleton, singleton_async, transient, Color, Context, DynProvider,
+    FutureExt, Module, Scope,
+};
+
+#[test]
+fn eagerly_created_instances() {
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn eager_create() -> bool {
+            true
+        }
+
+        fn providers() -> Vec<DynProvider> {
+            providers![
+                singleton(|_| {
+                    thread::sleep(Duration::from_millis(20));
+                    1u8
+                }),
+                singleton(|cx| {
+                    thread::sleep(Duration::from_millis(10));
+                    u16::from(cx.resolve::<u8>())
+                }),
+                transient(|_| 1u32),
+            ]
+        }
+    }
+
+    let mut cx = Context::create(modules![MyModule]);
+
+    cx.resolve::<u32>();
+
+    let report = cx.startup_report();
+
+    let created = report
+        .created()
+        .iter()
+        .map(|instance| {
+            (
+                instance.definition.key.ty.name,
+                instance.order,
+                instance.depth,
+            )
+        })
+        .collect::<Vec<_>>();
+
+    // `u16` depends on `u8`, so `u8` may be created either by itself or by `u16`
+    assert!(
+        created == [("u8", 1, 0), ("u16", 2, 0)] || created == [("u8", 1, 1), ("u16", 2, 0)],
+        "{:?}",
+        created
+    );
+
+    let u16 = &report.created()[1];
+    assert_eq!(u16.definition.scope, Scope::Singleton);
+    assert_eq!(u16.definition.color, Some(Color::Sync));
+    assert!(u16.own_time >= Duration::from_millis(10));
+    assert!(u16.total_time >= u16.own_time);
+
+    assert!(report.total_time() >= Duration::from_millis(30));
+    assert!(report.never_resolved().is_empty());
+
+    let text = report.to_string();
+    assert!(text.starts_with("2 instances created in "));
+    assert!(text.contains("#2 u16 [Singleton, sync]"));
+    assert!(text.ends_with("0 providers never resolved:"));
+}
+
+#[test]
+fn nested_time_is_excluded_from_own_time() {
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![
+                transient(|_| {
+                    thread::sleep(Duration::from_millis(30));
+                    1u8
+                }),
+                singleton(|cx| u16::from(cx.resolve::<u8>())).eager_create(true),
+            ]
+        }
+    }
+
+    let cx = Context::create(modules![MyModule]);
+
+    let report = cx.startup_report();
+
+    let [u8, u16] = report.created() else {
+        panic!("{}", report);
+    };
+
+    assert_eq!(u8.depth, 1);
+    assert_eq!(u16.depth, 0);
+    assert!(u16.total_time >= Duration::from_millis(30));
+    assert!(u16.own_time < Duration::from_millis(30));
+    assert_eq!(report.total_time(), u16.total_time);
+}
+
+#[test]
+fn never_resolved_providers() {
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![
+                transient(|_| 1u8),
+                transient(|_| 1u16),
+                transient(|_| 1u32).name("unused"),
+            ]
+        }
+    }
+
+    let mut cx = Context::create(modules![MyModule]);
+
+    assert!(cx.startup_report().created().is_empty());
+
+    cx.resolve::<u8>();
+
+    let report = cx.startup_report();
+
+    let never_resolved = report
+        .never_resolved()
+        .iter()
+        .map(|definition| definition.key.ty.name)
+        .collect::<Vec<_>>();
+
+    assert_eq!(never_resolved, ["u16", "u32"]);
+    assert!(report.to_string().ends_with(
+        "2 providers never resolved:\n  \
+        u16 [Transient, sync]\n  \
+        u32 (name: \"unused\") [Transient, sync]"
+    ));
+}
+
+#[tokio::test]
+async fn async_eager_creation() {
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn eager_create() -> bool {
+            true
+        }
+
+        fn providers() -> Vec<DynProvider> {
+            providers![singleton_async(|_| async { 1u8 }.boxed())]
+        }
+    }
+
+    let cx = Context::options().create_async(modules![MyModule]).await;
+
+    let report = cx.startup_report();
+
+    assert_eq!(report.created().len(), 1);
+    assert_eq!(report.created()[0].definition.color, Some(Color::Async));
+}
@@ -0,0 +1,203 @@
+#![cfg(feature = "send")]
+
+use std::sync::{
+    atomic::{AtomicUsize, Ordering},
+    Arc,
+};
+
+use rudi::{
+    sync::{
+        components, modules, providers, singleton_async, transient, Context, DynProvider,
+        FutureExt, Module, ResolveError, ResolveErrorKind, SyncContext,
+    },
+    Singleton, Transient,
+};
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+fn context_is_send_sync() {
+    assert_send_sync::<Context>();
+    assert_send_sync::<SyncContext>();
+    assert_send_sync::<DynProvider>();
+    assert_send_sync::<ResolveError>();
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn singleton_is_created_once() {
+    static COUNT: AtomicUsize = AtomicUsize::new(0);
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![singleton_async(|_| async {
+                tokio::task::yield_now().await;
+                Arc::new(COUNT.fetch_add(1, Ordering::SeqCst))
+            }
+            .boxed())]
+        }
+    }
+
+    let cx = SyncContext::new(Context::create(modules![MyModule]));
+
+    let handles = (0..32)
+        .map(|_| {
+            let cx = cx.clone();
+            tokio::spawn(async move { cx.resolve::<Arc<usize>>().await })
+        })
+        .collect::<Vec<_>>();
+
+    for handle in handles {
+        assert_eq!(*handle.await.unwrap(), 0);
+    }
+
+    assert_eq!(COUNT.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn independent_constructors_overlap() {
+    static STARTED: AtomicUsize = AtomicUsize::new(0);
+
+    // returns whether the other constructor has started while this one is running
+    async fn slow() -> bool {
+        STARTED.fetch_add(1, Ordering::SeqCst);
+
+        for _ in 0..10_000 {
+            if STARTED.load(Ordering::SeqCst) >= 2 {
+                return true;
+            }
+
+            tokio::task::yield_now().await;
+        }
+
+        false
+    }
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![
+                singleton_async(|_| slow().boxed())
+                    .name("a")
+                    .dependencies(vec![]),
+                singleton_async(|_| slow().boxed())
+                    .name("b")
+                    .dependencies(vec![]),
+            ]
+        }
+    }
+
+    let cx = SyncContext::new(Context::create(modules![MyModule]));
+
+    let handles = ["a", "b"]
+        .map(|name| {
+            let cx = cx.clone();
+            tokio::spawn(async move { cx.resolve_with_name::<bool>(name).await })
+        })
+        .into_iter()
+        .collect::<Vec<_>>();
+
+    for handle in handles {
+        assert!(handle.await.unwrap());
+    }
+
+    let cx = cx.read().await;
+    assert!(*cx.get_single_with_name::<bool>("a"));
+    assert!(*cx.get_single_with_name::<bool>("b"));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn declared_singleton_is_created_once() {
+    static COUNT: AtomicUsize = AtomicUsize::new(0);
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            providers![singleton_async(|_| async {
+                tokio::task::yield_now().await;
+                Arc::new(COUNT.fetch_add(1, Ordering::SeqCst))
+            }
+            .boxed())
+            .dependencies(vec![])]
+        }
+    }
+
+    let cx = SyncContext::new(Context::create(modules![MyModule]));
+
+    let handles = (0..32)
+        .map(|_| {
+            let cx = cx.clone();
+            tokio::spawn(async move { cx.resolve::<Arc<usize>>().await })
+        })
+        .collect::<Vec<_>>();
+
+    for handle in handles {
+        assert_eq!(*handle.await.unwrap(), 0);
+    }
+
+    assert_eq!(COUNT.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn resolve_with_macro() {
+    #[derive(Clone)]
+    #[Singleton(async, auto_register = false)]
+    #[di(rudi_path = rudi::sync)]
+    struct A(#[di(name = "number")] i32);
+
+    #[Transient(async, auto_register = false)]
+    #[di(rudi_path = rudi::sync)]
+    struct B(A);
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            let mut providers = providers![transient(|_| 42).name("number")];
+            providers.extend(components![A, B]);
+            providers
+        }
+    }
+
+    let cx: SyncContext = Context::create(modules![MyModule]).into();
+
+    let b = tokio::spawn({
+        let cx = cx.clone();
+        async move { cx.resolve::<B>().await }
+    })
+    .await
+    .unwrap();
+
+    assert_eq!(b.0 .0, 42);
+    assert!(cx.read().await.contains_single::<A>());
+    assert!(cx.resolve_option::<String>().await.is_none());
+    assert_eq!(cx.resolve_by_type::<A>().await.len(), 1);
+
+    let err = cx.try_resolve::<String>().await.unwrap_err();
+    assert!(matches!(err.kind(), ResolveErrorKind::NotFoundProvider(_)));
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn auto_register_with_macro() {
+    #[derive(Clone)]
+    #[Singleton(name = "auto")]
+    #[di(rudi_path = rudi::sync)]
+    struct C;
+
+    let cx = SyncContext::new(Context::auto_register());
+
+    tokio::spawn({
+        let cx = cx.clone();
+        async move { cx.resolve_with_name::<C>("auto").await }
+    })
+    .await
+    .unwrap();
+
+    assert!(cx.read().await.contains_single_with_name::<C>("auto"));
+    assert!(rudi::Context::auto_register()
+        .get_provider_with_name::<C>("auto")
+        .is_none());
+}
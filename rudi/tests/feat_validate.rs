@@ -0,0 +1,248 @@
+use rudi::{
+    components, modules, providers, singleton, AsyncFactory, Context, Dependency, DependencyKind,
+    DynProvider, Factory, Lazy, Module, Shared, SingleOwner, Singleton, Transient,
+    ValidationErrorKind,
+};
+
+#[derive(Clone)]
+#[Singleton]
+struct Config;
+
+#[Transient]
+struct Service(#[allow(dead_code)] Config);
+
+#[test]
+fn auto_register_module() {
+    Context::auto_register().validate().unwrap();
+}
+
+#[test]
+fn declared_dependencies() {
+    type MaybeNumber = Option<i32>;
+
+    #[Transient(auto_register = false)]
+    #[allow(dead_code)]
+    struct A(
+        Config,
+        #[di(name = "a", option)] MaybeNumber,
+        #[di(default = 1)] u8,
+        #[di(vec)] Vec<u16>,
+        #[di(lazy)] Lazy<u32>,
+        #[di(factory)] Factory<u64>,
+        #[di(factory)] AsyncFactory<i64>,
+    );
+
+    #[Transient(auto_register = false)]
+    fn B(#[di(ref)] _a: &Config, #[di(option, ref)] _b: Option<&u8>) -> i8 {
+        1
+    }
+
+    let a = <A as rudi::DefaultProvider>::provider();
+
+    assert_eq!(
+        a.dependencies(),
+        [
+            Dependency::new::<Config>("", DependencyKind::Required),
+            Dependency::new::<i32>("a", DependencyKind::Option),
+            Dependency::new::<u8>("", DependencyKind::Default),
+            Dependency::new::<u16>("", DependencyKind::Vec),
+            Dependency::new::<u32>("", DependencyKind::Lazy),
+            Dependency::new::<u64>("", DependencyKind::Factory),
+            Dependency::new::<i64>("", DependencyKind::AsyncFactory),
+        ]
+    );
+
+    let b = <B as rudi::DefaultProvider>::provider();
+
+    assert_eq!(
+        b.dependencies(),
+        [
+            Dependency::new::<Config>("", DependencyKind::Required).by_ref(),
+            Dependency::new::<u8>("", DependencyKind::Option).by_ref(),
+        ]
+    );
+}
+
+#[test]
+fn not_found_and_ambiguous() {
+    #[Transient(auto_register = false)]
+    #[allow(dead_code)]
+    struct A(
+        i32,
+        #[di(name = "x")] u8,
+        #[di(option)] Option<u16>,
+        #[di(default)] u32,
+    );
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            let mut providers = providers![
+                singleton(|_| 1i32).name("one"),
+                singleton(|_| 2i32).name("two"),
+                singleton(|_| 1u8).name("y"),
+            ];
+            providers.extend(components![A]);
+            providers
+        }
+    }
+
+    let cx = Context::create(modules![MyModule]);
+
+    let errors = cx.validate().unwrap_err();
+    assert_eq!(errors.len(), 2);
+
+    let ValidationErrorKind::Ambiguous(candidates) = errors[0].kind() else {
+        panic!("{}", errors[0]);
+    };
+    assert_eq!(candidates.len(), 2);
+    assert_eq!(errors[0].dependency().key.name, "");
+
+    assert!(matches!(
+        errors[1].kind(),
+        ValidationErrorKind::NotFoundProvider
+    ));
+    assert_eq!(errors[1].dependency().key.name, "x");
+    assert_eq!(
+        errors[1].dependent().key.ty.name,
+        std::any::type_name::<A>()
+    );
+}
+
+#[test]
+fn scope_mismatch() {
+    #[Transient(auto_register = false)]
+    struct A;
+
+    #[SingleOwner(auto_register = false)]
+    struct B;
+
+    #[derive(Clone)]
+    #[Singleton(auto_register = false)]
+    struct C;
+
+    #[Transient(auto_register = false)]
+    fn D(#[di(ref)] _a: &A, _b: B, #[di(factory)] _c: Factory<C>) -> i32 {
+        1
+    }
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            components![A, B, C, D]
+        }
+    }
+
+    let cx = Context::create(modules![MyModule]);
+
+    let errors = cx.validate().unwrap_err();
+    assert_eq!(errors.len(), 3);
+    assert!(errors
+        .iter()
+        .all(|e| matches!(e.kind(), ValidationErrorKind::ScopeMismatch(_))));
+}
+
+#[test]
+fn async_constructor_in_sync() {
+    #[derive(Clone)]
+    #[Singleton(async, auto_register = false)]
+    struct A;
+
+    #[derive(Clone)]
+    #[Singleton(auto_register = false)]
+    struct B(#[allow(dead_code)] A);
+
+    #[allow(dead_code)]
+    #[derive(Clone)]
+    #[Singleton(async, auto_register = false)]
+    struct C(A, #[di(lazy)] Lazy<A>);
+
+    #[allow(dead_code)]
+    #[derive(Clone)]
+    #[Singleton(async, auto_register = false)]
+    struct D(
+        #[di(factory)] Factory<i32>,
+        #[di(factory)] AsyncFactory<i32>,
+    );
+
+    #[Transient(auto_register = false)]
+    async fn Number() -> i32 {
+        1
+    }
+
+    struct MyModule;
+
+    impl Module for MyModule {
+        fn providers() -> Vec<DynProvider> {
+            components![A, B, C, D, Number]
+        }
+    }
+
+    let cx = Context::create(modules![MyModule]);
+
+    let errors = cx.validate().unwrap_err();
+    assert_eq!(errors.len(), 2);
+    assert!(errors
+        .iter()
+        .all(|e| matches!(e.kind(), ValidationErrorKind::AsyncConstructorInSync(_))));
+
+    let dependents = errors
+        .iter()
+        .map(|e| e.dependent().key.ty.name)
+        .collect::<Vec<_>>();
+    assert!(dependents.contains(&std::any::type_name::<B>()));
+    assert!(dependents.contains(&std::any::type_name::<D>()));
+}
+
+#[test]
+fn binding_and_child_context() {
+    trait Greet: Send + Sync {}
+
+    #[derive(Clone)]
+    #[Singleton(binds = [Self::into_greet], auto_register = false)]
+    struct Greeter;
+
+    impl Greet for Greeter {}
+
+    impl Greeter {
+        fn into_greet(self) -> Shared<dyn Greet> {
+            Shared::new(self)
+        }
+    }
+
+    #[Transient(auto_register = false)]
+    struct Hello(#[allow(dead_code)] Shared<dyn Greet>);
+
+    struct ParentModule;
+
+    impl Module for ParentModule {
+        fn providers() -> Vec<DynProvider> {
+            components![Greeter]
+        }
+    }
+
+    struct ChildModule;
+
+    impl Module for ChildModule {
+        fn providers() -> Vec<DynProvider> {
+            components![Hello]
+        }
+    }
+
+    let parent = Shared::new(Context::create(modules![ParentModule]));
+    parent.validate().unwrap();
+
+    let greet = parent.get_provider::<Shared<dyn Greet>>().unwrap();
+    assert_eq!(
+        greet.dependencies(),
+        [Dependency::new::<Greeter>("", DependencyKind::Required)]
+    );
+
+    let child = parent.child(modules![ChildModule]);
+    child.validate().unwrap();
+
+    let orphan = Context::create(modules![ChildModule]);
+    assert_eq!(orphan.validate().unwrap_err().len(), 1);
+}